@@ -0,0 +1,108 @@
+//! 签名与数据库操作的性能基线；数据库使用 SQLite `:memory:`，不含 RPC 延迟
+//!
+//! 运行：`cargo bench --bench signing`
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use lotus_sign::chain::{cbor, Address, BigInt, Message};
+use lotus_sign::crypto::{self, CipherAlgorithm};
+use lotus_sign::db::{Store, WalletKey};
+use lotus_sign::wallet::{KeyType, PrivateKey, Wallet};
+
+const PASSWORD: &str = "bench-password";
+const BATCH_SIZE: usize = 100;
+
+/// 生成密钥并加密写入内存数据库，返回地址
+fn insert_key(store: &Store, key_type: KeyType) -> String {
+    let key = PrivateKey::generate(key_type).unwrap();
+    let address = key.address().unwrap().to_string();
+    let encrypted = crypto::encrypt(&key.private_key, &crypto::derive_key(PASSWORD), CipherAlgorithm::default()).unwrap();
+    store
+        .insert_key(&WalletKey::new(address.clone(), key_type.as_str().to_string(), encrypted))
+        .unwrap();
+    address
+}
+
+/// 各字段均非零的典型转账消息
+fn sample_message(from: &str, nonce: u64) -> Message {
+    Message {
+        version: 0,
+        to: Address::from_string("f01234").unwrap(),
+        from: Address::from_string(from).unwrap(),
+        nonce,
+        value: BigInt::from_str("1000000000000000000"),
+        gas_limit: 1_000_000,
+        gas_fee_cap: BigInt::from_str("100000"),
+        gas_premium: BigInt::from_str("100000"),
+        method: 0,
+        params: vec![0x80],
+    }
+}
+
+fn bench_sign(c: &mut Criterion) {
+    let store = Store::open(":memory:", None).unwrap();
+    let wallet = Wallet::new(&store, PASSWORD);
+
+    for key_type in [KeyType::Secp256k1, KeyType::BLS] {
+        let address = insert_key(&store, key_type);
+        let msg = sample_message(&address, 0);
+        c.bench_function(&format!("wallet_sign/{}", key_type.as_str()), |b| {
+            b.iter(|| wallet.sign(&msg, &address).unwrap())
+        });
+
+        let messages: Vec<Message> = (0..BATCH_SIZE as u64).map(|n| sample_message(&address, n)).collect();
+        let mut group = c.benchmark_group(format!("batch_sign/{}", key_type.as_str()));
+        group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+        group.bench_function(format!("{}_messages", BATCH_SIZE), |b| {
+            let batch: Vec<(&Message, &str)> = messages.iter().map(|m| (m, address.as_str())).collect();
+            b.iter(|| wallet.batch_sign(&batch))
+        });
+        group.finish();
+    }
+}
+
+fn bench_cbor(c: &mut Criterion) {
+    let store = Store::open(":memory:", None).unwrap();
+    let address = insert_key(&store, KeyType::Secp256k1);
+    let msg = sample_message(&address, 0);
+    let bytes = cbor::serialize_message(&msg).unwrap();
+
+    c.bench_function("cbor/serialize_message", |b| b.iter(|| cbor::serialize_message(&msg).unwrap()));
+    c.bench_function("cbor/compute_cid_bytes", |b| b.iter(|| cbor::compute_cid_bytes(&bytes)));
+}
+
+fn bench_crypto(c: &mut Criterion) {
+    let enc_key = crypto::derive_key(PASSWORD);
+    let plaintext = [0x42u8; 32];
+
+    // 在没有 AES 硬件加速的设备上比较两种算法
+    for cipher in [CipherAlgorithm::Aes256Gcm, CipherAlgorithm::ChaCha20Poly1305] {
+        let ciphertext = crypto::encrypt(&plaintext, &enc_key, cipher).unwrap();
+        c.bench_function(&format!("crypto/encrypt/{}", cipher.as_str()), |b| {
+            b.iter(|| crypto::encrypt(&plaintext, &enc_key, cipher).unwrap())
+        });
+        c.bench_function(&format!("crypto/decrypt/{}", cipher.as_str()), |b| {
+            b.iter_batched(|| ciphertext.clone(), |data| crypto::decrypt(&data, &enc_key, cipher).unwrap(), BatchSize::SmallInput)
+        });
+    }
+}
+
+/// 同步查询与 `_async` 变体（spawn_blocking）的对比，后者的差值即线程池调度开销
+fn bench_db(c: &mut Criterion) {
+    let store = Store::open(":memory:", None).unwrap();
+    let address = insert_key(&store, KeyType::Secp256k1);
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("store");
+    group.bench_function("get_key", |b| b.iter(|| store.get_key(&address).unwrap()));
+    group.bench_function("get_key_async", |b| {
+        b.iter(|| rt.block_on(store.get_key_async(&address)).unwrap())
+    });
+    group.bench_function("list_keys", |b| b.iter(|| store.list_keys().unwrap()));
+    group.bench_function("list_keys_async", |b| {
+        b.iter(|| rt.block_on(store.list_keys_async()).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_sign, bench_cbor, bench_crypto, bench_db);
+criterion_main!(benches);
@@ -0,0 +1,19 @@
+//! 地址解析：任意字符串与字节都不应 panic，合法地址可往返
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lotus_sign::chain::Address;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = run(data);
+});
+
+fn run(data: &[u8]) -> anyhow::Result<()> {
+    let _ = Address::from_bytes(data);
+
+    let s = std::str::from_utf8(data)?;
+    let addr = Address::from_string(s)?;
+    let _ = Address::from_string(&addr.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+//! 消息序列化与 CID 计算：任意字段组合的 Message 与任意字节都不应 panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lotus_sign::chain::{cbor, Message};
+
+fuzz_target!(|input: (Message, Vec<u8>)| {
+    let _ = run(input);
+});
+
+fn run((msg, raw): (Message, Vec<u8>)) -> anyhow::Result<()> {
+    cbor::compute_cid_bytes(&raw);
+    let _ = cbor::cid_from_string(&String::from_utf8_lossy(&raw));
+
+    let bytes = cbor::serialize_message(&msg)?;
+    let cid = cbor::compute_cid_bytes(&bytes);
+    // CID 字符串必须能解析回相同字节
+    assert_eq!(cbor::cid_from_string(&cbor::cid_to_string(&cid))?, cid);
+
+    // JSON 往返要求地址的字符串形式可解析
+    let json = serde_json::to_string(&msg)?;
+    let parsed: Message = serde_json::from_str(&json)?;
+    assert_eq!(cbor::serialize_message(&parsed)?, bytes);
+    Ok(())
+}
@@ -0,0 +1,41 @@
+/*
+ * lotus-sign C 接口
+ *
+ * 构建：cargo build --release --no-default-features --features ffi
+ * 链接：-Iinclude -Ltarget/release -llotus_sign
+ *
+ * 所有函数成功返回 0、失败返回 -1。失败时若 out_error 非空，*out_error 指向
+ * 一个错误字符串，需调用 lotus_sign_free_error 释放。输出缓冲区长度包含结尾的 NUL。
+ */
+
+#ifndef LOTUS_SIGN_H
+#define LOTUS_SIGN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* 生成新密钥；key_type 为 "secp256k1" 或 "bls"，私钥以 hex 输出（65 字节缓冲区足够） */
+int32_t lotus_sign_generate_key(const char *key_type,
+                                char *out_address, size_t out_address_len,
+                                char *out_privkey_hex, size_t out_privkey_len,
+                                char **out_error);
+
+/* 签名 Lotus JSON 格式的消息，输出 SignedMessage JSON；私钥必须对应消息的 From 地址 */
+int32_t lotus_sign_message(const char *msg_json,
+                           const char *privkey_hex,
+                           const char *key_type,
+                           char *out_signed, size_t out_len,
+                           char **out_error);
+
+/* 释放 out_error 返回的错误字符串；ptr 为 NULL 时不做任何事 */
+void lotus_sign_free_error(char *ptr);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LOTUS_SIGN_H */
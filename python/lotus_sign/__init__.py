@@ -0,0 +1,5 @@
+"""lotus-sign 的 Python 绑定：本地生成密钥、签名 Filecoin 消息并计算 CID"""
+
+from ._native import LotusSign
+
+__all__ = ["LotusSign"]
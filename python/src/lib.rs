@@ -0,0 +1,85 @@
+//! lotus-sign 的 Python 绑定（PyO3 + maturin）
+//!
+//! 消息以 Lotus JSON 字段名的 dict 传入传出；Value、GasFeeCap、GasPremium 可为
+//! int 或十进制字符串。私钥以 hex 传递，由调用方负责保管
+
+use lotus_sign_core::chain::{cbor, format_fil, Message};
+use lotus_sign_core::wallet::{self, KeyType, PrivateKey};
+use num_bigint::BigInt as NumBigInt;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyInt};
+use std::str::FromStr;
+
+/// Lotus JSON 中以字符串编码的大整数字段
+const BIGINT_FIELDS: &[&str] = &["Value", "GasFeeCap", "GasPremium"];
+
+#[pyclass(module = "lotus_sign")]
+struct LotusSign;
+
+#[pymethods]
+impl LotusSign {
+    /// 生成新密钥，返回 (address, private_key_hex)
+    #[staticmethod]
+    fn generate_key(key_type: &str) -> PyResult<(String, String)> {
+        let key = PrivateKey::generate(KeyType::from_str(key_type).map_err(value_error)?).map_err(value_error)?;
+        let address = key.address().map_err(value_error)?.to_string();
+        Ok((address, hex::encode(&key.private_key)))
+    }
+
+    /// 签名消息，返回 {"Message": {...}, "Signature": {"Type": ..., "Data": ...}}；私钥必须对应 From 地址
+    #[staticmethod]
+    fn sign_message<'py>(
+        message: &Bound<'py, PyDict>,
+        private_key_hex: &str,
+        key_type: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let msg = message_from_dict(message)?;
+        let key_bytes = hex::decode(private_key_hex).map_err(value_error)?;
+        let key = PrivateKey::from_bytes(KeyType::from_str(key_type).map_err(value_error)?, &key_bytes)
+            .map_err(value_error)?;
+        let signed = wallet::sign_message_with_key(msg, &key).map_err(value_error)?;
+        let json = serde_json::to_string(&signed).map_err(value_error)?;
+        message.py().import("json")?.call_method1("loads", (json,))
+    }
+
+    /// 计算消息 CID（multibase base32）
+    #[staticmethod]
+    fn compute_cid(message: &Bound<'_, PyDict>) -> PyResult<String> {
+        let msg = message_from_dict(message)?;
+        Ok(cbor::compute_cid(&cbor::serialize_message(&msg).map_err(value_error)?))
+    }
+
+    /// 将 attoFIL 十进制字符串格式化为 FIL，如 "1500000000000000000" → "1.5 FIL"
+    #[staticmethod]
+    fn format_fil(attofil: &str) -> PyResult<String> {
+        let value = NumBigInt::from_str(attofil.trim())
+            .map_err(|_| PyValueError::new_err(format!("invalid attoFIL amount: {}", attofil)))?;
+        Ok(format_fil(&value))
+    }
+}
+
+/// 经 json 模块转换为 Lotus JSON 再反序列化，大整数字段中的 int 先转为字符串
+fn message_from_dict(message: &Bound<'_, PyDict>) -> PyResult<Message> {
+    let message = message.copy()?;
+    for field in BIGINT_FIELDS {
+        if let Some(value) = message.get_item(field)? {
+            if value.is_instance_of::<PyInt>() {
+                message.set_item(field, value.str()?)?;
+            }
+        }
+    }
+    let json: String = message.py().import("json")?.call_method1("dumps", (message,))?.extract()?;
+    serde_json::from_str(&json).map_err(|e| PyValueError::new_err(format!("invalid message: {}", e)))
+}
+
+fn value_error(e: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(format!("{:#}", e))
+}
+
+#[pymodule]
+#[pyo3(name = "_native")]
+fn lotus_sign_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<LotusSign>()?;
+    Ok(())
+}
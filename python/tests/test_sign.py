@@ -0,0 +1,126 @@
+"""lotus_sign Python 绑定测试：maturin develop && pytest python/tests"""
+
+import base64
+import re
+
+import pytest
+
+from lotus_sign import LotusSign
+
+# Zondax filecoin-signing-tools 的 secp256k1 测试向量
+SECP_PRIVATE_KEY = base64.b64decode("8VcW07ADswS4BV2cxi5rnIadVsyTDDhY1NfDH19T8Uo=").hex()
+SECP_ADDRESS = "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+
+# 与上面私钥对应的转账消息；CID 按 DAG-CBOR + blake2b-256 独立计算
+VECTOR_MESSAGE = {
+    "Version": 0,
+    "To": "t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy",
+    "From": "t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba",
+    "Nonce": 1,
+    "Value": "100000",
+    "GasLimit": 25000,
+    "GasFeeCap": "2500",
+    "GasPremium": "2500",
+    "Method": 0,
+    "Params": "",
+}
+VECTOR_CID = "bafy2bzacebo6ag3aqa3uz6gttzfwhnsa7ogk75uswgkxri5alza2b7kenvepi"
+
+
+def message_from(address, **overrides):
+    msg = dict(VECTOR_MESSAGE, From=address, To="f01234")
+    msg.update(overrides)
+    return msg
+
+
+@pytest.mark.parametrize("key_type, prefix", [("secp256k1", "f1"), ("bls", "f3")])
+def test_generate_key(key_type, prefix):
+    address, private_key = LotusSign.generate_key(key_type)
+    assert address.startswith(prefix)
+    assert re.fullmatch(r"[0-9a-f]{64}", private_key)
+
+
+def test_generate_key_is_random():
+    assert LotusSign.generate_key("secp256k1") != LotusSign.generate_key("secp256k1")
+
+
+def test_generate_key_rejects_unknown_type():
+    with pytest.raises(ValueError, match="unknown key type"):
+        LotusSign.generate_key("ed25519")
+
+
+def test_compute_cid_vector():
+    assert LotusSign.compute_cid(VECTOR_MESSAGE) == VECTOR_CID
+
+
+def test_compute_cid_accepts_int_amounts():
+    msg = dict(VECTOR_MESSAGE, Value=100000, GasFeeCap=2500, GasPremium=2500)
+    assert LotusSign.compute_cid(msg) == VECTOR_CID
+
+
+def test_compute_cid_depends_on_nonce():
+    assert LotusSign.compute_cid(dict(VECTOR_MESSAGE, Nonce=2)) != VECTOR_CID
+
+
+def test_compute_cid_rejects_invalid_message():
+    with pytest.raises(ValueError, match="invalid message"):
+        LotusSign.compute_cid({"To": "f01234"})
+
+
+def test_sign_message_vector_address():
+    signed = LotusSign.sign_message(VECTOR_MESSAGE, SECP_PRIVATE_KEY, "secp256k1")
+    assert signed["Message"]["From"] == SECP_ADDRESS
+    assert signed["Signature"]["Type"] == 1
+    assert len(base64.b64decode(signed["Signature"]["Data"])) == 65
+
+
+def test_sign_message_secp256k1_is_deterministic():
+    first = LotusSign.sign_message(VECTOR_MESSAGE, SECP_PRIVATE_KEY, "secp256k1")
+    second = LotusSign.sign_message(VECTOR_MESSAGE, SECP_PRIVATE_KEY, "secp256k1")
+    assert first["Signature"] == second["Signature"]
+
+
+@pytest.mark.parametrize("key_type, sig_type, sig_len", [("secp256k1", 1, 65), ("bls", 2, 96)])
+def test_sign_message_generated_key(key_type, sig_type, sig_len):
+    address, private_key = LotusSign.generate_key(key_type)
+    signed = LotusSign.sign_message(message_from(address), private_key, key_type)
+    assert signed["Message"]["From"] == address
+    assert signed["Message"]["Value"] == "100000"
+    assert signed["Signature"]["Type"] == sig_type
+    assert len(base64.b64decode(signed["Signature"]["Data"])) == sig_len
+
+
+def test_sign_message_rejects_mismatched_from():
+    _, private_key = LotusSign.generate_key("secp256k1")
+    with pytest.raises(ValueError, match="message is from"):
+        LotusSign.sign_message(VECTOR_MESSAGE, private_key, "secp256k1")
+
+
+def test_sign_message_rejects_bad_private_key():
+    with pytest.raises(ValueError):
+        LotusSign.sign_message(VECTOR_MESSAGE, "not-hex", "secp256k1")
+
+
+def test_sign_message_does_not_modify_input():
+    msg = dict(VECTOR_MESSAGE, Value=100000)
+    LotusSign.sign_message(msg, SECP_PRIVATE_KEY, "secp256k1")
+    assert msg["Value"] == 100000
+
+
+@pytest.mark.parametrize(
+    "attofil, expected",
+    [
+        ("0", "0 FIL"),
+        ("1000000000000000000", "1 FIL"),
+        ("1500000000000000000", "1.5 FIL"),
+        ("1", "0.000000000000000001 FIL"),
+        ("123000000000000000000000", "123000 FIL"),
+    ],
+)
+def test_format_fil(attofil, expected):
+    assert LotusSign.format_fil(attofil) == expected
+
+
+def test_format_fil_rejects_invalid_amount():
+    with pytest.raises(ValueError, match="invalid attoFIL amount"):
+        LotusSign.format_fil("1.5")
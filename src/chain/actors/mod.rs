@@ -0,0 +1,167 @@
+pub mod multisig;
+pub mod paych;
+
+use super::{cbor, Address, BigInt};
+use anyhow::{anyhow, Result};
+use ciborium::Value;
+use serde::Serialize;
+
+// Miner Actor Method Numbers
+pub const METHOD_WITHDRAW_BALANCE: u64 = 16;
+pub const METHOD_CHANGE_OWNER: u64 = 23;
+pub const METHOD_CHANGE_WORKER: u64 = 3;
+pub const METHOD_CONFIRM_CHANGE_WORKER: u64 = 21;
+pub const METHOD_CHANGE_BENEFICIARY: u64 = 29;
+
+// Market Actor Method Numbers
+pub const METHOD_MARKET_WITHDRAW: u64 = 2;
+
+// Power Actor Method Numbers
+pub const METHOD_CREATE_MINER: u64 = 2;
+
+// Init Actor Method Numbers
+pub const METHOD_INIT_EXEC: u64 = 2;
+
+// Verified Registry Actor Method Numbers
+pub const METHOD_ADD_VERIFIED_CLIENT: u64 = 4;
+
+// Init Actor Address
+pub const INIT_ACTOR: &str = "f01";
+
+// Storage Market Actor Address
+pub const STORAGE_MARKET_ACTOR: &str = "f05";
+
+// Storage Power Actor Address
+pub const STORAGE_POWER_ACTOR: &str = "f04";
+
+// Verified Registry Actor Address
+pub const VERIFIED_REGISTRY_ACTOR: &str = "f06";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WithdrawBalanceParams {
+    pub amount: BigInt,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeOwnerParams {
+    pub new_owner: Address,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeWorkerParams {
+    pub new_worker: Address,
+    pub new_control_addresses: Vec<Address>,
+}
+
+/// FIP-0029 ChangeBeneficiary 参数；new_quota 为 attoFIL，new_expiration 为纪元
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeBeneficiaryParams {
+    pub new_beneficiary: Address,
+    pub new_quota: BigInt,
+    pub new_expiration: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketWithdrawParams {
+    pub provider_or_client: Address,
+    pub amount: BigInt,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddVerifiedClientParams {
+    pub address: Address,
+    pub allowance: BigInt,
+}
+
+/// Init Actor Exec 参数：按 CodeCID 创建新 Actor
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecParams {
+    pub code_cid: Vec<u8>,
+    pub constructor_params: Vec<u8>,
+}
+
+/// Power Actor CreateMiner 参数；Power Actor 内部会调用 Init Actor Exec
+/// 并使用当前网络版本对应的 Miner CodeCID，因此调用方无需提供 CodeCID
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateMinerParams {
+    pub owner: Address,
+    pub worker: Address,
+    pub window_post_proof_type: i64,
+    pub peer: Vec<u8>,
+    pub multiaddrs: Vec<Vec<u8>>,
+}
+
+// 从链上参数解码（元组编码：结构体为数组，地址与 BigInt 为字节串）
+
+fn decode_params(data: &[u8]) -> Result<Value> {
+    ciborium::from_reader(data).map_err(|e| anyhow!("invalid CBOR: {}", e))
+}
+
+fn tuple_fields(value: &Value, len: usize, name: &str) -> Result<Vec<Value>> {
+    match value {
+        Value::Array(f) if f.len() == len => Ok(f.clone()),
+        _ => Err(anyhow!("{} is not a {}-element array", name, len)),
+    }
+}
+
+fn value_bytes(value: &Value) -> Result<&[u8]> {
+    match value {
+        Value::Bytes(b) => Ok(b),
+        _ => Err(anyhow!("expected CBOR byte string")),
+    }
+}
+
+fn value_address(value: &Value) -> Result<Address> {
+    Address::from_bytes(value_bytes(value)?)
+}
+
+fn value_bigint(value: &Value) -> Result<BigInt> {
+    cbor::bigint_from_bytes(value_bytes(value)?)
+}
+
+impl WithdrawBalanceParams {
+    /// 链上格式：[AmountRequested]
+    pub fn from_cbor(data: &[u8]) -> Result<Self> {
+        let f = tuple_fields(&decode_params(data)?, 1, "WithdrawBalanceParams")?;
+        Ok(Self { amount: value_bigint(&f[0])? })
+    }
+}
+
+impl ChangeOwnerParams {
+    /// 链上格式：新 Owner 地址本身（透明编码，不是数组）
+    pub fn from_cbor(data: &[u8]) -> Result<Self> {
+        Ok(Self { new_owner: value_address(&decode_params(data)?)? })
+    }
+}
+
+impl ChangeWorkerParams {
+    /// 链上格式：[NewWorker, [NewControlAddrs...]]
+    pub fn from_cbor(data: &[u8]) -> Result<Self> {
+        let f = tuple_fields(&decode_params(data)?, 2, "ChangeWorkerParams")?;
+        let new_control_addresses = match &f[1] {
+            Value::Array(addrs) => addrs.iter().map(value_address).collect::<Result<_>>()?,
+            _ => return Err(anyhow!("ChangeWorkerParams control addresses is not an array")),
+        };
+        Ok(Self { new_worker: value_address(&f[0])?, new_control_addresses })
+    }
+}
+
+impl ChangeBeneficiaryParams {
+    /// 链上格式：[NewBeneficiary, NewQuota, NewExpiration]
+    pub fn from_cbor(data: &[u8]) -> Result<Self> {
+        let f = tuple_fields(&decode_params(data)?, 3, "ChangeBeneficiaryParams")?;
+        let new_expiration = match &f[2] {
+            Value::Integer(i) => i64::try_from(i128::from(*i))?,
+            _ => return Err(anyhow!("ChangeBeneficiaryParams expiration is not an integer")),
+        };
+        Ok(Self { new_beneficiary: value_address(&f[0])?, new_quota: value_bigint(&f[1])?, new_expiration })
+    }
+}
+
+impl MarketWithdrawParams {
+    /// 链上格式：[ProviderOrClientAddress, Amount]
+    pub fn from_cbor(data: &[u8]) -> Result<Self> {
+        let f = tuple_fields(&decode_params(data)?, 2, "MarketWithdrawParams")?;
+        Ok(Self { provider_or_client: value_address(&f[0])?, amount: value_bigint(&f[1])? })
+    }
+}
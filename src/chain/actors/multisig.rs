@@ -0,0 +1,91 @@
+//! Multisig Actor 方法与参数
+
+use super::super::{cbor, hamt, Address, BigInt};
+use anyhow::{anyhow, Result};
+use ciborium::Value;
+use serde::Serialize;
+
+// Multisig Actor Method Numbers
+pub const METHOD_PROPOSE: u64 = 2;
+pub const METHOD_APPROVE: u64 = 3;
+pub const METHOD_CANCEL: u64 = 4;
+
+/// Multisig 构造参数（通过 Init Actor Exec 创建）
+#[derive(Debug, Clone, Serialize)]
+pub struct ConstructorParams {
+    pub signers: Vec<Address>,
+    pub num_approvals_threshold: u64,
+    pub unlock_duration: i64,
+    pub start_epoch: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposeParams {
+    pub to: Address,
+    pub value: BigInt,
+    pub method: u64,
+    pub params: Vec<u8>,
+}
+
+/// Approve / Cancel 参数；proposal_hash 为空时 Actor 不校验提案内容
+#[derive(Debug, Clone, Serialize)]
+pub struct TxnIDParams {
+    pub id: i64,
+    pub proposal_hash: Vec<u8>,
+}
+
+/// 计算提案哈希的输入：blake2b-256(cbor(ProposalHashData))
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposalHashData {
+    pub requester: Address,
+    pub to: Address,
+    pub value: BigInt,
+    pub method: u64,
+    pub params: Vec<u8>,
+}
+
+/// 待处理的多签交易（PendingTxns HAMT 中的一项）
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub id: i64,
+    pub to: Address,
+    pub value: BigInt,
+    pub method: u64,
+    #[allow(dead_code)]
+    pub params: Vec<u8>,
+    pub approved: Vec<Address>,
+}
+
+impl Transaction {
+    /// 从 HAMT 键值解码：键为 TxnID，值为 [To, Value, Method, Params, Approved]
+    pub fn from_hamt_entry(key: &[u8], value: &Value) -> Result<Self> {
+        let fields = match value {
+            Value::Array(f) if f.len() == 5 => f,
+            _ => return Err(anyhow!("multisig transaction is not a 5-element array")),
+        };
+        let bytes = |v: &Value| match v {
+            Value::Bytes(b) => Ok(b.clone()),
+            _ => Err(anyhow!("expected byte string in multisig transaction")),
+        };
+
+        let method = match &fields[2] {
+            Value::Integer(i) => u64::try_from(i128::from(*i))?,
+            _ => return Err(anyhow!("invalid multisig transaction method")),
+        };
+        let approved = match &fields[4] {
+            Value::Array(signers) => signers.iter()
+                .map(|s| Address::from_bytes(&bytes(s)?))
+                .collect::<Result<_>>()?,
+            _ => return Err(anyhow!("invalid multisig approvals")),
+        };
+
+        Ok(Self {
+            id: hamt::decode_int_key(key)?,
+            to: Address::from_bytes(&bytes(&fields[0])?)?,
+            value: cbor::bigint_from_bytes(&bytes(&fields[1])?)?,
+            method,
+            params: bytes(&fields[3])?,
+            approved,
+        })
+    }
+}
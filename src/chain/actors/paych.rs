@@ -0,0 +1,90 @@
+//! 支付通道凭证（Payment Channel Voucher）
+
+use super::super::{cbor, Address, BigInt, Signature};
+use anyhow::{anyhow, Result};
+use ciborium::Value;
+
+/// 已签名凭证，字段顺序与 Filecoin 规范的 CBOR 元组一致：
+/// [ChannelAddr, TimeLockMin, TimeLockMax, SecretHash, Extra, Lane, Nonce, Amount, MinSettleHeight, Merges, Signature]
+/// Extra 与 Merges 暂不支持，始终编码为 null 与空数组
+#[derive(Debug, Clone)]
+pub struct SignedVoucher {
+    pub channel_addr: Address,
+    pub time_lock_min: i64,
+    pub time_lock_max: i64,
+    pub secret_hash: Vec<u8>,
+    pub lane: u64,
+    pub nonce: u64,
+    pub amount: BigInt,
+    pub min_settle_height: i64,
+    pub signature: Option<Signature>,
+}
+
+impl SignedVoucher {
+    pub fn new(channel_addr: Address, lane: u64, nonce: u64, amount: BigInt) -> Self {
+        Self {
+            channel_addr,
+            time_lock_min: 0,
+            time_lock_max: 0,
+            secret_hash: Vec::new(),
+            lane,
+            nonce,
+            amount,
+            min_settle_height: 0,
+            signature: None,
+        }
+    }
+
+    /// 待签名字节：Signature 置空后的 CBOR 编码（与 Lotus SigningBytes 一致）
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        cbor::serialize_voucher(self, false)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        cbor::serialize_voucher(self, true)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let value: Value = ciborium::from_reader(data)?;
+        let fields = match value {
+            Value::Array(f) if f.len() == 11 => f,
+            _ => return Err(anyhow!("voucher is not an 11-element array")),
+        };
+        let bytes = |v: &Value| match v {
+            Value::Bytes(b) => Ok(b.clone()),
+            _ => Err(anyhow!("expected byte string in voucher")),
+        };
+        let int = |v: &Value| match v {
+            Value::Integer(i) => Ok(i128::from(*i)),
+            _ => Err(anyhow!("expected integer in voucher")),
+        };
+
+        if !fields[4].is_null() {
+            return Err(anyhow!("vouchers with Extra are not supported"));
+        }
+        if !matches!(&fields[9], Value::Array(m) if m.is_empty()) {
+            return Err(anyhow!("vouchers with Merges are not supported"));
+        }
+        let signature = match &fields[10] {
+            Value::Null => None,
+            v => {
+                let raw = bytes(v)?;
+                let (&sig_type, data) = raw.split_first()
+                    .ok_or_else(|| anyhow!("empty voucher signature"))?;
+                Some(Signature { sig_type, data: data.to_vec() })
+            }
+        };
+
+        Ok(Self {
+            channel_addr: Address::from_bytes(&bytes(&fields[0])?)?,
+            time_lock_min: i64::try_from(int(&fields[1])?)?,
+            time_lock_max: i64::try_from(int(&fields[2])?)?,
+            secret_hash: bytes(&fields[3])?,
+            lane: u64::try_from(int(&fields[5])?)?,
+            nonce: u64::try_from(int(&fields[6])?)?,
+            amount: cbor::bigint_from_bytes(&bytes(&fields[7])?)?,
+            min_settle_height: i64::try_from(int(&fields[8])?)?,
+            signature,
+        })
+    }
+}
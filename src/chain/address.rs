@@ -5,25 +5,70 @@
 //! - f1: secp256k1 地址（未压缩公钥的 20 字节 blake2b 哈希）
 //! - f2: Actor 地址
 //! - f3: BLS 地址（48 字节公钥）
+//! - f4: 委托地址（FIP-0055，由命名空间 Actor 管理，如 EVM 的 f410f...）
 
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// 以太坊地址管理器（EAM）的 Actor ID，其委托地址显示为 f410f...
+#[allow(dead_code)]
+pub const ETH_NAMESPACE: u64 = 10;
 
 /// Filecoin 地址协议类型
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Protocol {
-    ID = 0,         // f0 - Actor ID
-    Secp256k1 = 1,  // f1 - secp256k1 公钥哈希
-    Actor = 2,      // f2 - Actor 地址
-    BLS = 3,        // f3 - BLS 公钥
+    ID,             // f0 - Actor ID
+    Secp256k1,      // f1 - secp256k1 公钥哈希
+    Actor,          // f2 - Actor 地址
+    BLS,            // f3 - BLS 公钥
+    // f4 - 委托地址：管理 Actor 的 ID 与其分配的子地址
+    Delegated { namespace: u64, subaddress: Vec<u8> },
+}
+
+impl Protocol {
+    /// 二进制格式中的协议字节
+    pub fn code(&self) -> u8 {
+        match self {
+            Protocol::ID => 0,
+            Protocol::Secp256k1 => 1,
+            Protocol::Actor => 2,
+            Protocol::BLS => 3,
+            Protocol::Delegated { .. } => 4,
+        }
+    }
+}
+
+/// 地址字符串的网络前缀；二进制格式不含网络信息，同一地址在两个网络的载荷相同
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "native", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    /// 主网，f 前缀
+    #[default]
+    Mainnet,
+    /// 测试网（Calibration 等），t 前缀
+    #[serde(alias = "calibnet")]
+    #[cfg_attr(feature = "native", value(alias = "calibnet"))]
+    Testnet,
+}
+
+impl Network {
+    pub fn prefix(self) -> char {
+        match self {
+            Network::Mainnet => 'f',
+            Network::Testnet => 't',
+        }
+    }
 }
 
 /// Filecoin 地址结构体
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Address {
-    pub protocol: Protocol,  // 地址类型 (f0-f3)
-    pub payload: Vec<u8>,    // 地址特定数据
+    pub protocol: Protocol,  // 地址类型 (f0-f4)
+    pub payload: Vec<u8>,    // 二进制载荷（f4 为 varint(namespace) || subaddress）
 }
 
 impl Address {
@@ -46,10 +91,26 @@ impl Address {
         })
     }
 
+    /// 创建委托地址（f4），子地址最长 54 字节
+    #[allow(dead_code)]
+    pub fn new_delegated(namespace: u64, subaddress: &[u8]) -> Result<Self> {
+        if subaddress.len() > MAX_SUBADDRESS_LEN {
+            return Err(anyhow!("subaddress too long: {} bytes", subaddress.len()));
+        }
+        let mut payload = encode_uvarint(namespace);
+        payload.extend_from_slice(subaddress);
+        Ok(Self {
+            protocol: Protocol::Delegated { namespace, subaddress: subaddress.to_vec() },
+            payload,
+        })
+    }
+
     /// 从字符串格式解析地址（如 "f1abc..." 或 "t1abc..."）
     /// 格式：[网络][协议][base32_载荷_带校验和]
+    /// f4 格式：[网络]4[namespace]f[base32_子地址_带校验和]
     pub fn from_string(s: &str) -> Result<Self> {
-        if s.len() < 3 {
+        // 以下按字节切片，非 ASCII 输入会切在字符中间
+        if s.len() < 3 || !s.is_ascii() {
             return Err(anyhow!("invalid address"));
         }
 
@@ -59,31 +120,116 @@ impl Address {
         }
 
         let protocol = match &s[1..2] {
-            "0" => Protocol::ID,
+            "0" => {
+                // ID 地址载荷为十进制 actor ID 的 uvarint 编码，不带校验和
+                let id: u64 = s[2..].parse().map_err(|_| anyhow!("invalid ID address"))?;
+                return Ok(Self { protocol: Protocol::ID, payload: encode_uvarint(id) });
+            }
             "1" => Protocol::Secp256k1,
             "2" => Protocol::Actor,
             "3" => Protocol::BLS,
+            "4" => {
+                let (namespace, encoded) = s[2..]
+                    .split_once('f')
+                    .ok_or_else(|| anyhow!("invalid delegated address"))?;
+                let namespace: u64 = namespace.parse()
+                    .map_err(|_| anyhow!("invalid delegated namespace"))?;
+                return Self::new_delegated(namespace, &base32_decode(encoded)?);
+            }
             _ => return Err(anyhow!("invalid protocol")),
         };
 
         let payload = base32_decode(&s[2..])?;
         Ok(Self { protocol, payload })
     }
+
+    /// 从二进制格式解析地址：[协议字节 || 载荷]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (&protocol_byte, payload) = bytes
+            .split_first()
+            .ok_or_else(|| anyhow!("empty address bytes"))?;
+
+        let protocol = match protocol_byte {
+            0 => Protocol::ID,
+            1 => Protocol::Secp256k1,
+            2 => Protocol::Actor,
+            3 => Protocol::BLS,
+            4 => {
+                let (namespace, len) = decode_uvarint(payload)?;
+                return Self::new_delegated(namespace, &payload[len..]);
+            }
+            _ => return Err(anyhow!("invalid protocol")),
+        };
+
+        Ok(Self { protocol, payload: payload.to_vec() })
+    }
+}
+
+impl Address {
+    /// 地址去掉网络与协议前缀（f4 还去掉命名空间）后的前 8 个字符，用于快速区分地址
+    pub fn fingerprint(&self) -> String {
+        let s = self.to_string();
+        let body = match self.protocol {
+            Protocol::Delegated { .. } => s[2..].split_once('f').map_or(&s[2..], |(_, rest)| rest),
+            _ => &s[2..],
+        };
+        body.chars().take(8).collect()
+    }
+
+    /// 以指定网络前缀格式化，如测试网的 "t1abc..."；Display 固定使用主网前缀
+    pub fn to_string_with_network(&self, network: Network) -> String {
+        let s = self.to_string();
+        format!("{}{}", network.prefix(), &s[1..])
+    }
+}
+
+// 与派生的 PartialEq 一致：协议与载荷都相同的地址哈希值相同
+impl Hash for Address {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.protocol.code().hash(state);
+        self.payload.hash(state);
+    }
 }
 
 impl fmt::Display for Address {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let protocol_byte = match self.protocol {
-            Protocol::ID => 0u8,
-            Protocol::Secp256k1 => 1u8,
-            Protocol::Actor => 2u8,
-            Protocol::BLS => 3u8,
-        };
+        let protocol_byte = self.protocol.code();
+        if let Protocol::Delegated { namespace, subaddress } = &self.protocol {
+            // 校验和覆盖完整二进制地址，但只编码子地址部分
+            let checksum = address_checksum(protocol_byte, &self.payload);
+            return write!(f, "f4{}f{}", namespace, base32_encode_raw(subaddress, &checksum));
+        }
+        if self.protocol == Protocol::ID {
+            // 载荷不是合法 varint 时退回通用格式；Display 返回错误会使 to_string panic
+            if let Ok((id, _)) = decode_uvarint(&self.payload) {
+                return write!(f, "f0{}", id);
+            }
+        }
         let prefix = format!("f{}", protocol_byte);
         write!(f, "{}{}", prefix, base32_encode_with_checksum(protocol_byte, &self.payload))
     }
 }
 
+/// 模糊测试用：按协议生成载荷长度合法的地址
+#[cfg(feature = "fuzz")]
+impl<'a> arbitrary::Arbitrary<'a> for Address {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let address = match u.int_in_range(0..=4)? {
+            0 => Self { protocol: Protocol::ID, payload: encode_uvarint(u.arbitrary()?) },
+            1 => Self { protocol: Protocol::Secp256k1, payload: u.bytes(20)?.to_vec() },
+            2 => Self { protocol: Protocol::Actor, payload: u.bytes(20)?.to_vec() },
+            3 => Self { protocol: Protocol::BLS, payload: u.bytes(48)?.to_vec() },
+            _ => {
+                let namespace = u.arbitrary()?;
+                let len = u.int_in_range(0..=MAX_SUBADDRESS_LEN)?;
+                Self::new_delegated(namespace, u.bytes(len)?)
+                    .map_err(|_| arbitrary::Error::IncorrectFormat)?
+            }
+        };
+        Ok(address)
+    }
+}
+
 impl Serialize for Address {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where S: serde::Serializer {
@@ -99,6 +245,31 @@ impl<'de> Deserialize<'de> for Address {
     }
 }
 
+/// FIP-0048 规定的委托子地址最大长度
+const MAX_SUBADDRESS_LEN: usize = 54;
+
+fn encode_uvarint(mut n: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    while n >= 0x80 {
+        out.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+    out
+}
+
+/// 解码无符号 varint，返回 (值, 占用字节数)
+fn decode_uvarint(data: &[u8]) -> Result<(u64, usize)> {
+    let mut n: u64 = 0;
+    for (i, &b) in data.iter().enumerate().take(10) {
+        n |= ((b & 0x7f) as u64) << (7 * i);
+        if b & 0x80 == 0 {
+            return Ok((n, i + 1));
+        }
+    }
+    Err(anyhow!("invalid varint in address"))
+}
+
 fn blake2b_hash(data: &[u8], size: usize) -> Vec<u8> {
     use blake2b_simd::Params;
     Params::new()
@@ -111,16 +282,22 @@ fn blake2b_hash(data: &[u8], size: usize) -> Vec<u8> {
 /// 使用 base32 编码载荷并附加校验和
 /// 校验和 = blake2b-32([协议字节 || 载荷])
 fn base32_encode_with_checksum(protocol: u8, payload: &[u8]) -> String {
-    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+    base32_encode_raw(payload, &address_checksum(protocol, payload))
+}
 
-    // 校验和是 [协议 || 载荷] 的 blake2b-32
+/// 校验和是 [协议 || 载荷] 的 blake2b-32
+fn address_checksum(protocol: u8, payload: &[u8]) -> Vec<u8> {
     let mut checksum_input = vec![protocol];
     checksum_input.extend_from_slice(payload);
-    let checksum = blake2b_hash(&checksum_input, 4);
+    blake2b_hash(&checksum_input, 4)
+}
+
+/// 编码 数据 + 校验和
+fn base32_encode_raw(payload: &[u8], checksum: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
 
-    // 编码 载荷 + 校验和
     let mut data = payload.to_vec();
-    data.extend_from_slice(&checksum);
+    data.extend_from_slice(checksum);
 
     let mut result = String::new();
     let mut buffer: u64 = 0;
@@ -189,3 +366,56 @@ fn base32_decode(s: &str) -> Result<Vec<u8>> {
     }
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 以太坊地址 0xd4c5fb16488aa48081296299d54b0c648c9333da 对应的 f4 地址
+    const F4_ADDRESS: &str = "f410f2tc7wfsirksibajjmkm5ksymmsgjgm62hjnomwa";
+    const ETH_ADDRESS: &str = "d4c5fb16488aa48081296299d54b0c648c9333da";
+
+    #[test]
+    fn f4_string_round_trip() {
+        let addr = Address::from_string(F4_ADDRESS).unwrap();
+        assert!(matches!(addr.protocol, Protocol::Delegated { namespace: ETH_NAMESPACE, .. }));
+        assert_eq!(addr.to_string(), F4_ADDRESS);
+    }
+
+    #[test]
+    fn f4_bytes_round_trip() {
+        let addr = Address::from_string(F4_ADDRESS).unwrap();
+        let mut bytes = vec![addr.protocol.code()];
+        bytes.extend_from_slice(&addr.payload);
+        assert_eq!(bytes[0], 4);
+        assert_eq!(Address::from_bytes(&bytes).unwrap(), addr);
+    }
+
+    #[test]
+    fn f4_matches_new_delegated() {
+        let addr = Address::new_delegated(ETH_NAMESPACE, &hex::decode(ETH_ADDRESS).unwrap()).unwrap();
+        assert_eq!(addr.to_string(), F4_ADDRESS);
+        assert_eq!(addr, Address::from_string(F4_ADDRESS).unwrap());
+    }
+
+    #[test]
+    fn equal_addresses_hash_equal() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+
+        let hash = |addr: &Address| {
+            let mut hasher = DefaultHasher::new();
+            addr.hash(&mut hasher);
+            hasher.finish()
+        };
+        let a = Address::from_string("f01234").unwrap();
+        let b = Address::from_string("t01234").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+
+        let f4 = Address::from_string(F4_ADDRESS).unwrap();
+        let set: HashSet<Address> = [a.clone(), b, f4.clone(), f4, Address::from_string("f01235").unwrap()].into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&a));
+    }
+}
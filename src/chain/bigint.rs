@@ -17,9 +17,39 @@ impl BigInt {
         Self(NumBigInt::from(0))
     }
 
+    /// 宽松解析：非法输入视为 0，因此不实现 `FromStr`
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         Self(NumBigInt::from_str(s).unwrap_or(NumBigInt::from(0)))
     }
+
+    /// 计算 self / denominator，保留 precision 位小数并返回十进制字符串
+    /// 分母为零时返回 "0"
+    pub fn scale_fraction(&self, denominator: &BigInt, precision: u32) -> String {
+        use num_traits::Zero;
+        if denominator.0.is_zero() {
+            return "0".to_string();
+        }
+        let scale = NumBigInt::from(10u64).pow(precision);
+        let scaled = &self.0 * &scale / &denominator.0;
+        let int_part = &scaled / &scale;
+        let dec_part = &scaled % &scale;
+        if precision == 0 {
+            return int_part.to_string();
+        }
+        format!("{}.{:0>width$}", int_part, dec_part.to_string(), width = precision as usize)
+    }
+}
+
+/// 模糊测试用：符号与不超过 32 字节的大端数值
+#[cfg(feature = "fuzz")]
+impl<'a> arbitrary::Arbitrary<'a> for BigInt {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use num_bigint::Sign;
+        let sign = if u.arbitrary()? { Sign::Minus } else { Sign::Plus };
+        let len = u.int_in_range(0..=32)?;
+        Ok(Self(NumBigInt::from_bytes_be(sign, u.bytes(len)?)))
+    }
 }
 
 impl fmt::Display for BigInt {
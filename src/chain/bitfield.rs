@@ -0,0 +1,189 @@
+//! RLE+ 位域（go-bitfield），actor 状态中用于表示扇区编号集合
+//!
+//! 编码按字节从低位到高位读取：2 位版本号（00）、1 位首个游程的值，之后为交替游程：
+//! `1` 表示长度 1，`01` 后跟 4 位长度，`00` 后跟 LEB128 varint 长度
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Deserializer};
+
+/// 解码后的位域：runs 为从 0 游程开始交替的游程长度
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitfieldRle {
+    runs: Vec<u64>,
+}
+
+impl BitfieldRle {
+    /// 解码 RLE+ 字节
+    pub fn from_rleplus(data: &[u8]) -> Result<Self> {
+        if data.is_empty() {
+            return Ok(Self::default());
+        }
+        let mut reader = BitReader { data, pos: 0 };
+        if reader.read(2).ok_or_else(|| anyhow!("RLE+ bitfield too short"))? != 0 {
+            return Err(anyhow!("unsupported RLE+ version"));
+        }
+        let first_set = reader.read(1).ok_or_else(|| anyhow!("RLE+ bitfield too short"))? == 1;
+
+        let mut runs = Vec::new();
+        if first_set {
+            runs.push(0);
+        }
+        // 末尾不足一个游程的填充位均为 0，读到长度 0 或数据结束即停止
+        while let Some(len) = reader.read_run()? {
+            runs.push(len);
+        }
+        Ok(Self { runs })
+    }
+
+    /// 从 Lotus JSON 使用的游程数组构造，第一个游程为 0
+    pub fn from_runs(runs: Vec<u64>) -> Self {
+        Self { runs }
+    }
+
+    /// 置位的编号（升序）
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        self.ranges().flat_map(|(start, end)| start..end)
+    }
+
+    /// 置位区间 [start, end)
+    pub fn ranges(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        let mut offset = 0u64;
+        self.runs.iter().enumerate().filter_map(move |(i, &len)| {
+            let start = offset;
+            offset = offset.saturating_add(len);
+            (i % 2 == 1 && len > 0).then_some((start, offset))
+        })
+    }
+
+    /// 置位的数量
+    pub fn count(&self) -> u64 {
+        self.runs.iter().skip(1).step_by(2).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+}
+
+/// Lotus 以游程数组（如 `[3, 2]` 表示 {3, 4}）返回位域；也接受 base64 编码的 RLE+ 字节
+impl<'de> Deserialize<'de> for BitfieldRle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        use base64::Engine;
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Runs(Vec<u64>),
+            Base64(String),
+        }
+
+        match Option::<Repr>::deserialize(deserializer)? {
+            None => Ok(Self::default()),
+            Some(Repr::Runs(runs)) => Ok(Self::from_runs(runs)),
+            Some(Repr::Base64(s)) => {
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(&s)
+                    .map_err(serde::de::Error::custom)?;
+                Self::from_rleplus(&bytes).map_err(serde::de::Error::custom)
+            }
+        }
+    }
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    /// 读取 n 位（低位在前），剩余位数不足时返回 None
+    fn read(&mut self, n: usize) -> Option<u64> {
+        if self.pos + n > self.data.len() * 8 {
+            return None;
+        }
+        let mut value = 0u64;
+        for i in 0..n {
+            let bit = (self.data[(self.pos + i) / 8] >> ((self.pos + i) % 8)) & 1;
+            value |= u64::from(bit) << i;
+        }
+        self.pos += n;
+        Some(value)
+    }
+
+    fn read_run(&mut self) -> Result<Option<u64>> {
+        match self.read(1) {
+            None => return Ok(None),
+            Some(1) => return Ok(Some(1)),
+            Some(_) => {}
+        }
+        match self.read(1) {
+            None => Ok(None),
+            Some(1) => Ok(self.read(4).filter(|&len| len > 0)),
+            Some(_) => self.read_varint(),
+        }
+    }
+
+    fn read_varint(&mut self) -> Result<Option<u64>> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let Some(byte) = self.read(8) else { return Ok(None) };
+            value |= (byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value > 0).then_some(value));
+            }
+        }
+        Err(anyhow!("RLE+ run length overflows u64"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按读取顺序给出的位打包为字节（低位在前）
+    fn pack(bits: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+        for (i, &bit) in bits.iter().enumerate() {
+            bytes[i / 8] |= bit << (i % 8);
+        }
+        bytes
+    }
+
+    /// 字节的 8 位（低位在前）
+    fn byte_bits(byte: u8) -> Vec<u8> {
+        (0..8).map(|i| (byte >> i) & 1).collect()
+    }
+
+    #[test]
+    fn decodes_go_bitfield_vector() {
+        let bf = BitfieldRle::from_rleplus(&[0x7c, 0x47, 0x22, 0x02]).unwrap();
+        let expected: Vec<u64> = [0, 2, 4, 5, 6].into_iter().chain(11..=27).collect();
+        assert_eq!(bf.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(bf.count(), expected.len() as u64);
+    }
+
+    #[test]
+    fn empty_input_is_empty_bitfield() {
+        let bf = BitfieldRle::from_rleplus(&[]).unwrap();
+        assert!(bf.is_empty());
+        assert_eq!(bf, BitfieldRle::default());
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        // 版本位 01
+        let err = BitfieldRle::from_rleplus(&[0x01]).unwrap_err();
+        assert!(err.to_string().contains("unsupported RLE+ version"));
+    }
+
+    #[test]
+    fn decodes_varint_run() {
+        // 版本 00、首个游程为 0，然后 varint 长度 200（0xc8 0x01）的 0 游程与长度 1 的 1 游程
+        let mut bits = vec![0, 0, 0, 0, 0];
+        bits.extend(byte_bits(0xc8));
+        bits.extend(byte_bits(0x01));
+        bits.push(1);
+        let bf = BitfieldRle::from_rleplus(&pack(&bits)).unwrap();
+        assert_eq!(bf.iter().collect::<Vec<_>>(), vec![200]);
+    }
+}
@@ -3,9 +3,15 @@
 //! 本模块实现 Filecoin 特定的 CBOR 编码，用于消息序列化和 CID 计算。
 //! Filecoin 使用自定义 CBOR 格式，消息被编码为固定的 10 元素数组。
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::Serialize;
 use super::{Message, Address, BigInt};
+use super::actors::paych::SignedVoucher;
+use super::actors::multisig::{ConstructorParams, ProposalHashData, ProposeParams, TxnIDParams};
+use super::actors::{
+    AddVerifiedClientParams, ChangeBeneficiaryParams, ChangeOwnerParams, ChangeWorkerParams, CreateMinerParams, ExecParams,
+    MarketWithdrawParams, WithdrawBalanceParams,
+};
 
 /// 通用 CBOR 序列化（使用 ciborium 库）
 /// 注意：此函数不用于 Filecoin 消息，请使用 serialize_message()
@@ -60,12 +66,193 @@ pub fn serialize_message(msg: &Message) -> Result<Vec<u8>> {
     Ok(buf)
 }
 
+/// 将支付通道凭证序列化为 CBOR 元组（11 元素数组）
+/// with_signature 为 false 时 Signature 编码为 null，得到待签名字节
+pub fn serialize_voucher(v: &SignedVoucher, with_signature: bool) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x8b = 11 个元素
+    buf.push(0x8b);
+    write_address(&mut buf, &v.channel_addr);
+    write_cbor_int(&mut buf, v.time_lock_min);
+    write_cbor_int(&mut buf, v.time_lock_max);
+    write_cbor_bytes(&mut buf, &v.secret_hash);
+    // Extra: null
+    buf.push(0xf6);
+    write_cbor_uint(&mut buf, v.lane);
+    write_cbor_uint(&mut buf, v.nonce);
+    write_bigint(&mut buf, &v.amount);
+    write_cbor_int(&mut buf, v.min_settle_height);
+    // Merges: 空数组
+    buf.push(0x80);
+
+    // 签名：[类型字节 || 签名数据]
+    match (&v.signature, with_signature) {
+        (Some(sig), true) => {
+            let mut sig_bytes = vec![sig.sig_type];
+            sig_bytes.extend_from_slice(&sig.data);
+            write_cbor_bytes(&mut buf, &sig_bytes);
+        }
+        _ => buf.push(0xf6),
+    }
+
+    buf
+}
+
+/// 将 WithdrawBalance 参数序列化为 CBOR 元组：[AmountRequested]
+pub fn serialize_withdraw_balance(p: &WithdrawBalanceParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x81 = 1 个元素
+    buf.push(0x81);
+    write_bigint(&mut buf, &p.amount);
+    buf
+}
+
+/// 将 ChangeOwnerAddress 参数序列化为新 Owner 地址本身（透明编码，不是数组）
+pub fn serialize_change_owner(p: &ChangeOwnerParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_address(&mut buf, &p.new_owner);
+    buf
+}
+
+/// 将 Market WithdrawBalance 参数序列化为 CBOR 元组：[ProviderOrClientAddress, Amount]
+pub fn serialize_market_withdraw(p: &MarketWithdrawParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x82 = 2 个元素
+    buf.push(0x82);
+    write_address(&mut buf, &p.provider_or_client);
+    write_bigint(&mut buf, &p.amount);
+    buf
+}
+
+/// 将 ChangeBeneficiary 参数序列化为 CBOR 元组（3 元素数组）
+pub fn serialize_change_beneficiary(p: &ChangeBeneficiaryParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x83 = 3 个元素
+    buf.push(0x83);
+    write_address(&mut buf, &p.new_beneficiary);
+    write_bigint(&mut buf, &p.new_quota);
+    write_cbor_int(&mut buf, p.new_expiration);
+    buf
+}
+
+/// 将 ChangeWorkerAddress 参数序列化为 CBOR 元组：[NewWorker, [NewControlAddrs...]]
+pub fn serialize_change_worker(p: &ChangeWorkerParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x82 = 2 个元素
+    buf.push(0x82);
+    write_address(&mut buf, &p.new_worker);
+    write_cbor_array_header(&mut buf, p.new_control_addresses.len() as u64);
+    for addr in &p.new_control_addresses {
+        write_address(&mut buf, addr);
+    }
+    buf
+}
+
+/// 将 AddVerifiedClient 参数序列化为 CBOR 元组：[Address, Allowance]
+pub fn serialize_add_verified_client(p: &AddVerifiedClientParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x82 = 2 个元素
+    buf.push(0x82);
+    write_address(&mut buf, &p.address);
+    write_bigint(&mut buf, &p.allowance);
+    buf
+}
+
+/// 将 CreateMiner 参数序列化为 CBOR 元组：[Owner, Worker, WindowPoStProofType, Peer, [Multiaddrs...]]
+/// Peer 与每个 multiaddr 均为字节串
+pub fn serialize_create_miner(p: &CreateMinerParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x85 = 5 个元素
+    buf.push(0x85);
+    write_address(&mut buf, &p.owner);
+    write_address(&mut buf, &p.worker);
+    write_cbor_int(&mut buf, p.window_post_proof_type);
+    write_cbor_bytes(&mut buf, &p.peer);
+    write_cbor_array_header(&mut buf, p.multiaddrs.len() as u64);
+    for addr in &p.multiaddrs {
+        write_cbor_bytes(&mut buf, addr);
+    }
+    buf
+}
+
+/// 将 Init Actor Exec 参数序列化为 CBOR 元组：[CodeCID, ConstructorParams]
+pub fn serialize_exec_params(p: &ExecParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x82 = 2 个元素
+    buf.push(0x82);
+    write_cid(&mut buf, &p.code_cid);
+    write_cbor_bytes(&mut buf, &p.constructor_params);
+    buf
+}
+
+/// 将多签构造参数序列化为 CBOR 元组：[[Signers...], NumApprovalsThreshold, UnlockDuration, StartEpoch]
+pub fn serialize_constructor_params(p: &ConstructorParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x84 = 4 个元素
+    buf.push(0x84);
+    write_cbor_array_header(&mut buf, p.signers.len() as u64);
+    for addr in &p.signers {
+        write_address(&mut buf, addr);
+    }
+    write_cbor_uint(&mut buf, p.num_approvals_threshold);
+    write_cbor_int(&mut buf, p.unlock_duration);
+    write_cbor_int(&mut buf, p.start_epoch);
+    buf
+}
+
+/// 将多签 Propose 参数序列化为 CBOR 元组：[To, Value, Method, Params]
+pub fn serialize_propose_params(p: &ProposeParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x84 = 4 个元素
+    buf.push(0x84);
+    write_address(&mut buf, &p.to);
+    write_bigint(&mut buf, &p.value);
+    write_cbor_uint(&mut buf, p.method);
+    write_cbor_bytes(&mut buf, &p.params);
+    buf
+}
+
+/// 将多签 Approve / Cancel 参数序列化为 CBOR 元组：[ID, ProposalHash]
+pub fn serialize_txn_id_params(p: &TxnIDParams) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x82 = 2 个元素
+    buf.push(0x82);
+    write_cbor_int(&mut buf, p.id);
+    write_cbor_bytes(&mut buf, &p.proposal_hash);
+    buf
+}
+
+/// 将提案哈希输入序列化为 CBOR 元组：[Requester, To, Value, Method, Params]
+pub fn serialize_proposal_hash_data(p: &ProposalHashData) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // 数组头：0x85 = 5 个元素
+    buf.push(0x85);
+    write_address(&mut buf, &p.requester);
+    write_address(&mut buf, &p.to);
+    write_bigint(&mut buf, &p.value);
+    write_cbor_uint(&mut buf, p.method);
+    write_cbor_bytes(&mut buf, &p.params);
+    buf
+}
+
 /// 返回 CID 原始字节（用于签名）
 ///
 /// CID 格式：[version(1), codec(varint), multihash]
 /// - version: 0x01 (CIDv1)
-/// - codec: 0x71 (dag-cbor, varint 编码为 0xa0 0xe4 0x02)
-/// - multihash: [hash_type(0x20=blake2b-256), length(0x20=32), hash_bytes]
+/// - codec: 0x71 (dag-cbor)
+/// - multihash: [hash_type(0xb220=blake2b-256, varint 编码为 0xa0 0xe4 0x02), length(0x20=32), hash_bytes]
 pub fn compute_cid_bytes(data: &[u8]) -> Vec<u8> {
     use blake2b_simd::Params;
     let hash = Params::new()
@@ -79,18 +266,45 @@ pub fn compute_cid_bytes(data: &[u8]) -> Vec<u8> {
 }
 
 /// 返回 CID 的 multibase 编码字符串（用于显示）
-#[allow(dead_code)]
 pub fn compute_cid(data: &[u8]) -> String {
     multibase_encode(&compute_cid_bytes(data))
 }
 
-#[allow(dead_code)]
+/// 解析 multibase base32 编码的 CID 字符串（"b" 前缀，如 "bafk2bz..."）为原始字节
+pub fn cid_from_string(s: &str) -> Result<Vec<u8>> {
+    let data = s
+        .strip_prefix('b')
+        .ok_or_else(|| anyhow!("unsupported CID multibase (expected base32 'b' prefix): {}", s))?;
+
+    let mut result = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in data.chars() {
+        let val = match c {
+            'a'..='z' => c as u32 - 'a' as u32,
+            '2'..='7' => c as u32 - '2' as u32 + 26,
+            _ => return Err(anyhow!("invalid base32 char in CID: {}", c)),
+        };
+        buffer = (buffer << 5) | val;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(result)
+}
+
+/// 将 CID 原始字节编码为字符串（multibase base32）
+pub fn cid_to_string(cid: &[u8]) -> String {
+    multibase_encode(cid)
+}
+
 fn multibase_encode(data: &[u8]) -> String {
-    // Base32 小写编码，带 'b' 前缀（multibase 格式）
+    // RFC 4648 base32 小写、无填充，带 'b' 前缀（multibase 格式）
     format!("b{}", base32_encode(data))
 }
 
-#[allow(dead_code)]
 fn base32_encode(data: &[u8]) -> String {
     const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
     let mut result = String::new();
@@ -135,6 +349,13 @@ fn write_cbor_uint(buf: &mut Vec<u8>, val: u64) {
     }
 }
 
+/// 写入数组头（CBOR 主类型 4），长度编码与无符号整数相同
+fn write_cbor_array_header(buf: &mut Vec<u8>, len: u64) {
+    let start = buf.len();
+    write_cbor_uint(buf, len);
+    buf[start] |= 0x80;
+}
+
 /// 写入有符号整数（正数用主类型 0，负数用主类型 1）
 fn write_cbor_int(buf: &mut Vec<u8>, val: i64) {
     if val >= 0 {
@@ -177,17 +398,20 @@ fn write_cbor_bytes(buf: &mut Vec<u8>, data: &[u8]) {
     buf.extend_from_slice(data);
 }
 
+/// 写入 CID 链接（CBOR tag 42），内容为 0x00 前缀加 CID 原始字节
+fn write_cid(buf: &mut Vec<u8>, cid: &[u8]) {
+    buf.extend_from_slice(&[0xd8, 0x2a]);
+    let mut bytes = vec![0x00];
+    bytes.extend_from_slice(cid);
+    write_cbor_bytes(buf, &bytes);
+}
+
 /// 写入 Filecoin 地址为 CBOR 字节串
 /// 格式：[协议字节 || 载荷]
 fn write_address(buf: &mut Vec<u8>, addr: &Address) {
     // 地址序列化为字节：[协议字节 || 载荷]
-    let protocol_byte = match addr.protocol {
-        super::address::Protocol::ID => 0u8,
-        super::address::Protocol::Secp256k1 => 1u8,
-        super::address::Protocol::Actor => 2u8,
-        super::address::Protocol::BLS => 3u8,
-    };
-    let mut addr_bytes = vec![protocol_byte];
+    // f4 的载荷已是 varint(namespace) || subaddress
+    let mut addr_bytes = vec![addr.protocol.code()];
     addr_bytes.extend_from_slice(&addr.payload);
     write_cbor_bytes(buf, &addr_bytes);
 }
@@ -212,3 +436,118 @@ fn write_bigint(buf: &mut Vec<u8>, val: &BigInt) {
         write_cbor_bytes(buf, &bigint_bytes);
     }
 }
+
+// CBOR 解码辅助函数
+
+/// 从 CBOR 字节串解码 Filecoin BigInt
+/// 格式：[符号字节 || 大端字节]，空字节串表示零
+pub fn deserialize_bigint(data: &[u8], offset: &mut usize) -> Result<BigInt> {
+    let bytes = read_cbor_bytes(data, offset)?;
+    bigint_from_bytes(bytes)
+}
+
+/// 解析 Filecoin BigInt 字节：[符号字节 || 大端字节]，空字节表示零
+pub fn bigint_from_bytes(bytes: &[u8]) -> Result<BigInt> {
+    use num_bigint::{BigInt as NumBigInt, Sign};
+
+    let Some((&sign_byte, magnitude)) = bytes.split_first() else {
+        return Ok(BigInt(NumBigInt::from(0)));
+    };
+    let sign = match sign_byte {
+        0x00 => Sign::Plus,
+        0x01 => Sign::Minus,
+        b => return Err(anyhow!("invalid bigint sign byte: 0x{:02x}", b)),
+    };
+    Ok(BigInt(NumBigInt::from_bytes_be(sign, magnitude)))
+}
+
+/// 从 CBOR 字节串解码 Filecoin 地址
+pub fn deserialize_address(data: &[u8], offset: &mut usize) -> Result<Address> {
+    let bytes = read_cbor_bytes(data, offset)?;
+    Address::from_bytes(bytes)
+}
+
+/// 读取字节串（CBOR 主类型 2），offset 前进到字节串之后
+fn read_cbor_bytes<'a>(data: &'a [u8], offset: &mut usize) -> Result<&'a [u8]> {
+    let header = *data.get(*offset).ok_or_else(|| anyhow!("unexpected end of CBOR data"))?;
+    if header >> 5 != 2 {
+        return Err(anyhow!("expected CBOR byte string, got major type {}", header >> 5));
+    }
+    *offset += 1;
+
+    let len = match header & 0x1f {
+        n @ 0..=23 => n as usize,
+        n @ 24..=27 => {
+            let size = 1usize << (n - 24);
+            let raw = data
+                .get(*offset..*offset + size)
+                .ok_or_else(|| anyhow!("unexpected end of CBOR data"))?;
+            *offset += size;
+            raw.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64) as usize
+        }
+        n => return Err(anyhow!("unsupported CBOR length encoding: {}", n)),
+    };
+
+    let end = offset.checked_add(len).ok_or_else(|| anyhow!("CBOR byte string too long"))?;
+    let bytes = data
+        .get(*offset..end)
+        .ok_or_else(|| anyhow!("unexpected end of CBOR data"))?;
+    *offset = end;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 空 CBOR 数组（0x80）的 CID，与 Lotus 中的空对象 CID 相同
+    const EMPTY_ARRAY_CID: &str = "bafy2bzacebc3bt6cedhoyw34drrmjvazhu4oj25er2ebk4u445pzycvq4ta4a";
+
+    #[test]
+    fn compute_cid_matches_known_cid() {
+        assert_eq!(compute_cid(&[0x80]), EMPTY_ARRAY_CID);
+        assert_eq!(cid_from_string(EMPTY_ARRAY_CID).unwrap(), compute_cid_bytes(&[0x80]));
+    }
+
+    #[test]
+    fn multibase_encode_uses_rfc4648_base32() {
+        // RFC 4648 第 10 节测试向量，小写无填充
+        assert_eq!(multibase_encode(b""), "b");
+        assert_eq!(multibase_encode(b"f"), "bmy");
+        assert_eq!(multibase_encode(b"foob"), "bmzxw6yq");
+        assert_eq!(multibase_encode(b"foobar"), "bmzxw6ytboi");
+    }
+
+    fn attofil(n: i64) -> BigInt {
+        BigInt(num_bigint::BigInt::from(n))
+    }
+
+    #[test]
+    fn bigint_from_bytes_handles_sign_byte() {
+        // 空字节串与只有符号字节时均为零
+        assert_eq!(bigint_from_bytes(&[]).unwrap(), attofil(0));
+        assert_eq!(bigint_from_bytes(&[0x00]).unwrap(), attofil(0));
+        assert_eq!(bigint_from_bytes(&[0x01]).unwrap(), attofil(0));
+        assert_eq!(bigint_from_bytes(&[0x00, 0x01, 0x00]).unwrap(), attofil(256));
+        assert_eq!(bigint_from_bytes(&[0x01, 0x01, 0x00]).unwrap(), attofil(-256));
+        // 大端字节的前导零不影响取值
+        assert_eq!(bigint_from_bytes(&[0x00, 0x00, 0x00, 0x05]).unwrap(), attofil(5));
+        assert!(bigint_from_bytes(&[0x02, 0x05]).is_err());
+        assert!(bigint_from_bytes(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn deserialize_round_trips_write_helpers() {
+        let addr = Address::from_string("f1abjxfbp274xpdqcpuaykwkfb43omjotacm2p3za").unwrap();
+        let mut buf = Vec::new();
+        write_bigint(&mut buf, &attofil(-1_000_000_007));
+        write_bigint(&mut buf, &attofil(0));
+        write_address(&mut buf, &addr);
+
+        let mut offset = 0;
+        assert_eq!(deserialize_bigint(&buf, &mut offset).unwrap(), attofil(-1_000_000_007));
+        assert_eq!(deserialize_bigint(&buf, &mut offset).unwrap(), attofil(0));
+        assert_eq!(deserialize_address(&buf, &mut offset).unwrap(), addr);
+        assert_eq!(offset, buf.len());
+    }
+}
@@ -5,7 +5,14 @@ use std::str::FromStr;
 // 1 FIL = 10^18 attoFIL
 pub const FILECOIN_PRECISION: u64 = 1_000_000_000_000_000_000;
 
-#[allow(dead_code)]
+// 1 nanoFIL = 10^9 attoFIL
+pub const NANOFIL_PRECISION: u64 = 1_000_000_000;
+
+// 基础费用调整参数（与 Lotus build 参数一致）
+pub const BLOCK_GAS_TARGET: i64 = 5_000_000_000;
+pub const BASE_FEE_MAX_CHANGE_DENOM: i64 = 8;
+pub const MINIMUM_BASE_FEE: i64 = 100;
+
 pub fn parse_fil(s: &str) -> Result<NumBigInt> {
     let s = s.trim();
 
@@ -18,14 +25,14 @@ pub fn parse_fil(s: &str) -> Result<NumBigInt> {
         _ => return Err(anyhow!("unrecognized unit: {}", unit)),
     };
 
-    // 解析数字
-    let value = if num_str.contains('.') {
-        parse_decimal(num_str, is_attofil)?
-    } else {
-        NumBigInt::from_str(num_str)
-            .map_err(|_| anyhow!("invalid number: {}", num_str))?
-    };
+    // 小数按 18 位精度解析，结果已是 attoFIL
+    if num_str.contains('.') {
+        let value = parse_decimal(num_str, is_attofil)?;
+        return Ok(if is_attofil { value / NumBigInt::from(FILECOIN_PRECISION) } else { value });
+    }
 
+    let value = NumBigInt::from_str(num_str)
+        .map_err(|_| anyhow!("invalid number: {}", num_str))?;
     if is_attofil {
         Ok(value)
     } else {
@@ -73,3 +80,73 @@ pub fn format_fil(attofil: &NumBigInt) -> String {
         format!("{}.{} FIL", int_part, trimmed)
     }
 }
+
+/// 将字节数格式化为二进制单位（KiB/MiB/GiB/TiB/PiB/EiB），最多保留两位小数
+pub fn format_bytes_power(bytes: &NumBigInt) -> String {
+    const UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    let mut unit_idx = 0;
+    let mut divisor = NumBigInt::from(1u64);
+    while unit_idx + 1 < UNITS.len() && *bytes >= &divisor * NumBigInt::from(1024u64) {
+        divisor *= NumBigInt::from(1024u64);
+        unit_idx += 1;
+    }
+
+    let hundredths = bytes * NumBigInt::from(100u64) / &divisor;
+    let int_part = &hundredths / NumBigInt::from(100u64);
+    let dec_part = &hundredths % NumBigInt::from(100u64);
+
+    if dec_part == NumBigInt::from(0) {
+        format!("{} {}", int_part, UNITS[unit_idx])
+    } else {
+        let dec_str = format!("{:02}", dec_part);
+        format!("{}.{} {}", int_part, dec_str.trim_end_matches('0'), UNITS[unit_idx])
+    }
+}
+
+/// 解析字节数，支持纯数字或带二进制单位（如 "1TiB"、"512 GiB"）
+pub fn parse_bytes_power(s: &str) -> Result<NumBigInt> {
+    let (num_str, unit) = split_number_unit(s.trim());
+    let shift = match unit.to_lowercase().as_str() {
+        "" | "b" => 0,
+        "kib" => 10,
+        "mib" => 20,
+        "gib" => 30,
+        "tib" => 40,
+        "pib" => 50,
+        "eib" => 60,
+        _ => return Err(anyhow!("unrecognized unit: {}", unit)),
+    };
+    let value = NumBigInt::from_str(num_str)
+        .map_err(|_| anyhow!("invalid number: {}", num_str))?;
+    Ok(value << shift)
+}
+
+/// 计算下一纪元的基础费用（Lotus ComputeNextBaseFee）
+/// 每区块平均 gas 用量高于目标值则上调，低于则下调，单次最多变化 1/8（12.5%）
+pub fn compute_next_base_fee(base_fee: &NumBigInt, gas_limit_used: i64, blocks: usize) -> NumBigInt {
+    let blocks = blocks.max(1) as i64;
+    let delta = (gas_limit_used / blocks - BLOCK_GAS_TARGET)
+        .clamp(-BLOCK_GAS_TARGET, BLOCK_GAS_TARGET);
+
+    let change = base_fee * NumBigInt::from(delta)
+        / NumBigInt::from(BLOCK_GAS_TARGET)
+        / NumBigInt::from(BASE_FEE_MAX_CHANGE_DENOM);
+    let next = base_fee + change;
+
+    next.max(NumBigInt::from(MINIMUM_BASE_FEE))
+}
+
+/// 将 attoFIL 格式化为 nanoFIL（去除末尾的零）
+pub fn format_nanofil(attofil: &NumBigInt) -> String {
+    let precision = NumBigInt::from(NANOFIL_PRECISION);
+    let int_part = attofil / &precision;
+    let dec_part = attofil % &precision;
+
+    if dec_part == NumBigInt::from(0) {
+        format!("{} nanoFIL", int_part)
+    } else {
+        let dec_str = format!("{:09}", dec_part);
+        format!("{}.{} nanoFIL", int_part, dec_str.trim_end_matches('0'))
+    }
+}
@@ -0,0 +1,115 @@
+//! HAMT 节点解码（go-hamt-ipld v3 格式）
+//!
+//! Actor 状态中的映射（如多签的 PendingTxns）以 HAMT 存储，根字段只是一个 CID，
+//! 需要通过 ChainReadObj 逐个读取节点。节点格式：[bitfield, [pointer...]]，
+//! pointer 为子节点链接（CID，CBOR tag 42）或内联的 KV 桶 [[key, value]...]
+
+use anyhow::{anyhow, Result};
+use ciborium::Value;
+
+/// CBOR 中 CID 链接使用的 tag
+const CID_TAG: u64 = 42;
+
+pub enum Pointer {
+    /// 子节点 CID 原始字节
+    Link(Vec<u8>),
+    /// 内联的键值对
+    Bucket(Vec<(Vec<u8>, Value)>),
+}
+
+/// 解码单个 HAMT 节点
+pub fn decode_node(data: &[u8]) -> Result<Vec<Pointer>> {
+    let node: Value = ciborium::from_reader(data)?;
+    let Value::Array(fields) = node else {
+        return Err(anyhow!("HAMT node is not an array"));
+    };
+    let Some(Value::Array(pointers)) = fields.get(1) else {
+        return Err(anyhow!("HAMT node has no pointer array"));
+    };
+
+    pointers.iter().map(decode_pointer).collect()
+}
+
+fn decode_pointer(value: &Value) -> Result<Pointer> {
+    match value {
+        Value::Tag(CID_TAG, inner) => Ok(Pointer::Link(cid_from_link(inner)?)),
+        Value::Array(kvs) => {
+            let entries = kvs.iter()
+                .map(|kv| match kv {
+                    Value::Array(pair) if pair.len() == 2 => match &pair[0] {
+                        Value::Bytes(key) => Ok((key.clone(), pair[1].clone())),
+                        _ => Err(anyhow!("HAMT key is not a byte string")),
+                    },
+                    _ => Err(anyhow!("HAMT entry is not a [key, value] pair")),
+                })
+                .collect::<Result<_>>()?;
+            Ok(Pointer::Bucket(entries))
+        }
+        _ => Err(anyhow!("unexpected HAMT pointer")),
+    }
+}
+
+/// tag 42 内的字节带有 0x00 multibase identity 前缀
+pub fn cid_from_link(value: &Value) -> Result<Vec<u8>> {
+    match value {
+        Value::Bytes(b) if b.first() == Some(&0) => Ok(b[1..].to_vec()),
+        _ => Err(anyhow!("invalid CID link")),
+    }
+}
+
+/// 解码 IntKey（abi.IntKey 使用 zigzag 有符号 varint）
+pub fn decode_int_key(key: &[u8]) -> Result<i64> {
+    let mut ux: u64 = 0;
+    for (i, &b) in key.iter().enumerate() {
+        if i >= 10 {
+            return Err(anyhow!("varint key too long"));
+        }
+        ux |= ((b & 0x7f) as u64) << (7 * i);
+        if b & 0x80 == 0 {
+            let x = (ux >> 1) as i64;
+            return Ok(if ux & 1 != 0 { !x } else { x });
+        }
+    }
+    Err(anyhow!("truncated varint key"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// dag-cbor / blake2b-256 CID（摘要固定为 32 个 0xab）
+    fn link_cid() -> Vec<u8> {
+        let mut cid = vec![0x01, 0x71, 0xa0, 0xe4, 0x02, 0x20];
+        cid.extend([0xab; 32]);
+        cid
+    }
+
+    #[test]
+    fn decodes_bucket_and_link() {
+        // [h'03', [[[h'02', 7]], 42(h'00' || cid)]]：一个含 IntKey 1 → 7 的桶与一个子节点链接
+        let mut data = hex::decode("824103828182410207d82a5827").unwrap();
+        data.push(0x00);
+        data.extend(link_cid());
+
+        let pointers = decode_node(&data).unwrap();
+        assert_eq!(pointers.len(), 2);
+        match &pointers[0] {
+            Pointer::Bucket(entries) => {
+                assert_eq!(entries.len(), 1);
+                assert_eq!(decode_int_key(&entries[0].0).unwrap(), 1);
+                assert_eq!(entries[0].1, Value::Integer(7.into()));
+            }
+            Pointer::Link(_) => panic!("expected a bucket"),
+        }
+        match &pointers[1] {
+            Pointer::Link(cid) => assert_eq!(cid, &link_cid()),
+            Pointer::Bucket(_) => panic!("expected a link"),
+        }
+    }
+
+    #[test]
+    fn rejects_node_without_pointer_array() {
+        // [h'00']
+        assert!(decode_node(&hex::decode("814100").unwrap()).is_err());
+    }
+}
@@ -1,7 +1,9 @@
-use super::{Address, BigInt};
+use super::{cbor, Address, BigInt, Protocol};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct Message {
     #[serde(rename = "Version")]
     pub version: u64,
@@ -41,6 +43,41 @@ pub struct SignedMessage {
     pub signature: Signature,
 }
 
+impl SignedMessage {
+    /// 无需访问本地密钥库，直接验证消息签名
+    ///
+    /// secp256k1 (f1)：从签名恢复公钥并比对派生地址；
+    /// BLS (f3)：f3 地址载荷即为 48 字节公钥，可直接验证。
+    /// 签名类型与发送地址协议不一致（或发送地址为 f0/f2）时返回错误
+    pub fn verify(&self) -> Result<bool> {
+        let cid_bytes = cbor::compute_cid_bytes(&cbor::serialize_message(&self.message)?);
+        crate::wallet::verify_signature(&self.message.from, &cid_bytes, &self.signature)
+    }
+
+    /// 使用给定公钥验证签名：先确认公钥对应发送地址，再验证签名
+    /// secp256k1 公钥可为 33 字节（压缩）或 65 字节（未压缩），BLS 公钥为 48 字节
+    pub fn verify_with_pubkey(&self, pubkey: &[u8]) -> Result<bool> {
+        let derived = match self.message.from.protocol {
+            Protocol::Secp256k1 => {
+                let pk = secp256k1::PublicKey::from_slice(pubkey)?;
+                Address::new_secp256k1(&pk.serialize_uncompressed())?
+            }
+            Protocol::BLS => {
+                if pubkey.len() != 48 {
+                    anyhow::bail!("invalid BLS public key length: {}", pubkey.len());
+                }
+                Address::new_bls(pubkey)?
+            }
+            _ => anyhow::bail!("cannot verify signature for address {}", self.message.from),
+        };
+
+        if derived != self.message.from {
+            return Ok(false);
+        }
+        self.verify()
+    }
+}
+
 mod base64_bytes {
     use base64::{engine::general_purpose::STANDARD, Engine};
     use serde::{Deserialize, Deserializer, Serializer};
@@ -50,9 +87,58 @@ mod base64_bytes {
         s.serialize_str(&STANDARD.encode(bytes))
     }
 
+    /// Lotus 对空参数返回 null，按空字节处理
     pub fn deserialize<'de, D>(d: D) -> Result<Vec<u8>, D::Error>
     where D: Deserializer<'de> {
-        let s = String::deserialize(d)?;
-        STANDARD.decode(&s).map_err(serde::de::Error::custom)
+        match Option::<String>::deserialize(d)? {
+            Some(s) => STANDARD.decode(&s).map_err(serde::de::Error::custom),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::{sign_message_with_key, KeyType, PrivateKey};
+
+    fn signed_by(key: &PrivateKey) -> SignedMessage {
+        let from = key.address().unwrap();
+        let msg = Message {
+            version: 0,
+            to: from.clone(),
+            from,
+            nonce: 1,
+            value: BigInt::from_str("1000"),
+            gas_limit: 1_000_000,
+            gas_fee_cap: BigInt::from_str("100000"),
+            gas_premium: BigInt::from_str("100000"),
+            method: 0,
+            params: vec![],
+        };
+        sign_message_with_key(msg, key).unwrap()
+    }
+
+    #[test]
+    fn verify_with_pubkey_accepts_the_sender_key() {
+        for key_type in [KeyType::Secp256k1, KeyType::BLS] {
+            let key = PrivateKey::generate(key_type).unwrap();
+            assert!(signed_by(&key).verify_with_pubkey(&key.public_key).unwrap());
+        }
+    }
+
+    #[test]
+    fn verify_with_pubkey_rejects_another_key() {
+        for key_type in [KeyType::Secp256k1, KeyType::BLS] {
+            let key = PrivateKey::generate(key_type).unwrap();
+            let other = PrivateKey::generate(key_type).unwrap();
+            assert!(!signed_by(&key).verify_with_pubkey(&other.public_key).unwrap());
+        }
+    }
+
+    #[test]
+    fn verify_with_pubkey_rejects_malformed_bls_key() {
+        let key = PrivateKey::generate(KeyType::BLS).unwrap();
+        assert!(signed_by(&key).verify_with_pubkey(&key.public_key[..47]).is_err());
     }
 }
@@ -1,12 +1,16 @@
 mod address;
 mod message;
 mod bigint;
+mod bitfield;
 pub mod cbor;
-mod actors;
+pub mod actors;
 pub mod fil;
+pub mod hamt;
+pub mod rlp;
 
-pub use address::Address;
+pub use address::{Address, Network, Protocol};
 pub use message::{Message, SignedMessage, Signature};
 pub use bigint::BigInt;
+pub use bitfield::BitfieldRle;
 pub use actors::*;
-pub use fil::format_fil;
+pub use fil::{format_fil, parse_fil, format_nanofil, format_bytes_power, parse_bytes_power};
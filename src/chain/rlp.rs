@@ -0,0 +1,101 @@
+//! RLP 编解码（仅用于以太坊 legacy 交易）
+//!
+//! legacy 交易是由字节串组成的扁平列表：
+//! [nonce, gasPrice, gasLimit, to, value, data, (chainId, 0, 0 | v, r, s)]
+
+use anyhow::{anyhow, Result};
+
+/// 解码由字节串组成的 RLP 列表，不支持嵌套列表
+pub fn decode_list(data: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let (is_list, start, len) = read_header(data)?;
+    if !is_list {
+        return Err(anyhow!("RLP payload is not a list"));
+    }
+    if start + len != data.len() {
+        return Err(anyhow!("trailing bytes after RLP list"));
+    }
+
+    let mut items = Vec::new();
+    let mut rest = &data[start..];
+    while !rest.is_empty() {
+        let (is_list, start, len) = read_header(rest)?;
+        if is_list {
+            return Err(anyhow!("nested RLP lists are not supported"));
+        }
+        items.push(rest[start..start + len].to_vec());
+        rest = &rest[start + len..];
+    }
+    Ok(items)
+}
+
+/// 将字节串列表编码为 RLP 列表
+pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    for item in items {
+        if item.len() == 1 && item[0] < 0x80 {
+            payload.push(item[0]);
+        } else {
+            write_header(&mut payload, 0x80, item.len());
+            payload.extend_from_slice(item);
+        }
+    }
+    let mut out = Vec::new();
+    write_header(&mut out, 0xc0, payload.len());
+    out.extend_from_slice(&payload);
+    out
+}
+
+/// 整数的 RLP 表示：去除前导零的大端字节，零为空串
+pub fn encode_uint(n: u64) -> Vec<u8> {
+    trim_leading_zeros(&n.to_be_bytes())
+}
+
+pub fn trim_leading_zeros(bytes: &[u8]) -> Vec<u8> {
+    let first = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    bytes[first..].to_vec()
+}
+
+/// 将 RLP 整数字节解码为 u64
+pub fn decode_uint(bytes: &[u8]) -> Result<u64> {
+    if bytes.len() > 8 {
+        return Err(anyhow!("RLP integer too large"));
+    }
+    Ok(bytes.iter().fold(0u64, |n, &b| (n << 8) | b as u64))
+}
+
+/// 读取 RLP 头，返回 (是否为列表, 载荷起始偏移, 载荷长度)
+fn read_header(data: &[u8]) -> Result<(bool, usize, usize)> {
+    let &prefix = data.first().ok_or_else(|| anyhow!("unexpected end of RLP data"))?;
+    let (is_list, start, len) = match prefix {
+        0x00..=0x7f => (false, 0, 1),
+        0x80..=0xb7 => (false, 1, (prefix - 0x80) as usize),
+        0xb8..=0xbf => {
+            let n = (prefix - 0xb7) as usize;
+            (false, 1 + n, read_length(data, n)?)
+        }
+        0xc0..=0xf7 => (true, 1, (prefix - 0xc0) as usize),
+        0xf8..=0xff => {
+            let n = (prefix - 0xf7) as usize;
+            (true, 1 + n, read_length(data, n)?)
+        }
+    };
+    if start.checked_add(len).is_none_or(|end| end > data.len()) {
+        return Err(anyhow!("RLP item exceeds input length"));
+    }
+    Ok((is_list, start, len))
+}
+
+fn read_length(data: &[u8], n: usize) -> Result<usize> {
+    let bytes = data.get(1..1 + n).ok_or_else(|| anyhow!("truncated RLP length"))?;
+    Ok(decode_uint(bytes)? as usize)
+}
+
+fn write_header(buf: &mut Vec<u8>, offset: u8, len: usize) {
+    if len <= 55 {
+        buf.push(offset + len as u8);
+    } else {
+        let len_bytes = trim_leading_zeros(&(len as u64).to_be_bytes());
+        buf.push(offset + 55 + len_bytes.len() as u8);
+        buf.extend_from_slice(&len_bytes);
+    }
+}
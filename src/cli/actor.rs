@@ -1,107 +1,542 @@
+use crate::chain::{format_bytes_power, format_fil, parse_fil};
 use crate::config::Config;
-use crate::db::Store;
-use crate::rpc::LotusApi;
+use crate::db::{Store, WorkerRotation};
+use crate::rpc::{Cid, LotusApi, EPOCH_DURATION_SECONDS};
 use anyhow::Result;
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueHint};
+use std::time::Duration;
 
 #[derive(Args)]
 pub struct ActorCmd {
     #[command(subcommand)]
     pub command: ActorSubCmd,
+    #[command(flatten)]
+    pub fees: super::FeeArgs,
 }
 
 #[derive(Subcommand)]
 pub enum ActorSubCmd {
+    /// 查看矿工信息：owner、worker、控制地址余额与各截止期的扇区数
     Info {
         miner: String,
-    },
-    Withdraw {
+        /// 同时查询算力与可用余额
         #[arg(long)]
+        full: bool,
+    },
+    /// 查看矿工 WindowPoSt 截止期时间表
+    Deadlines {
+        #[arg(long, value_hint = ValueHint::Other)]
         miner: String,
+    },
+    Withdraw {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: Option<String>,
+        #[arg(long, conflicts_with = "miner")]
+        miner_label: Option<String>,
         #[arg(long)]
         amount: String,
-        #[arg(long)]
-        from: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, conflicts_with = "from")]
+        from_label: Option<String>,
+        #[command(flatten)]
+        wait: super::WaitArgs,
     },
     SetOwner {
-        #[arg(long)]
-        miner: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: Option<String>,
+        #[arg(long, conflicts_with = "miner")]
+        miner_label: Option<String>,
         #[arg(long)]
         new_owner: String,
-        #[arg(long)]
-        from: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, conflicts_with = "from")]
+        from_label: Option<String>,
         #[arg(long, default_value = "false")]
         really_do_it: bool,
     },
     ProposeChangeWorker {
-        #[arg(long)]
-        miner: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: Option<String>,
+        #[arg(long, conflicts_with = "miner")]
+        miner_label: Option<String>,
         #[arg(long)]
         new_worker: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, conflicts_with = "from")]
+        from_label: Option<String>,
+        #[arg(long, default_value = "false")]
+        really_do_it: bool,
+    },
+    ProposeMultisigWithdraw {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: Option<String>,
+        #[arg(long, conflicts_with = "miner")]
+        miner_label: Option<String>,
         #[arg(long)]
-        from: String,
+        amount: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        msig: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, conflicts_with = "from")]
+        from_label: Option<String>,
         #[arg(long, default_value = "false")]
         really_do_it: bool,
     },
-    ConfirmChangeWorker {
+    CreateMiner {
         #[arg(long)]
-        miner: String,
+        owner: String,
+        #[arg(long)]
+        worker: String,
+        /// WindowPoSt 证明类型（如 8 = StackedDrgWindow32GiBV1P1）
         #[arg(long)]
-        from: String,
+        window_post_proof_type: i64,
+        /// 十六进制编码的 libp2p PeerID
+        #[arg(long, default_value = "")]
+        peer_id: String,
+        /// 初始抵押（attoFIL）
+        #[arg(long, default_value = "0")]
+        initial_collateral: String,
+        /// 省略时使用默认地址
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, default_value = "false")]
+        really_do_it: bool,
+    },
+    /// 修改矿工受益人（FIP-0029）；新受益人不是 owner 时，需由新受益人以相同参数再次执行确认
+    SetBeneficiary {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: Option<String>,
+        #[arg(long, conflicts_with = "miner")]
+        miner_label: Option<String>,
+        #[arg(long, value_hint = ValueHint::Other)]
+        new_beneficiary: String,
+        /// 受益人可提取的额度（单位：FIL）
+        #[arg(long)]
+        quota: String,
+        /// 额度到期纪元
+        #[arg(long)]
+        expiration: i64,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, conflicts_with = "from")]
+        from_label: Option<String>,
+        #[arg(long, default_value = "false")]
+        really_do_it: bool,
+    },
+    /// 为矿工添加控制地址（须由 owner 发送）
+    AddControl {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: Option<String>,
+        #[arg(long, conflicts_with = "miner")]
+        miner_label: Option<String>,
+        #[arg(long, value_hint = ValueHint::Other)]
+        control: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, conflicts_with = "from")]
+        from_label: Option<String>,
+        #[arg(long, default_value = "false")]
+        really_do_it: bool,
+    },
+    /// 从矿工移除控制地址（须由 owner 发送）
+    RemoveControl {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: Option<String>,
+        #[arg(long, conflicts_with = "miner")]
+        miner_label: Option<String>,
+        #[arg(long, value_hint = ValueHint::Other)]
+        control: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, conflicts_with = "from")]
+        from_label: Option<String>,
+        #[arg(long, default_value = "false")]
+        really_do_it: bool,
+    },
+    ConfirmChangeWorker {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: Option<String>,
+        #[arg(long, conflicts_with = "miner")]
+        miner_label: Option<String>,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        #[arg(long, conflicts_with = "from")]
+        from_label: Option<String>,
+        #[arg(long, default_value = "false")]
+        really_do_it: bool,
+    },
+    /// 更换 worker 的完整流程：提议 → 等待上链 → 等到可确认纪元 → 确认；
+    /// 进度保存在数据库中，中断后以 --resume-from-confirm 继续
+    RotateWorker {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: String,
+        #[arg(long, value_hint = ValueHint::Other, required_unless_present = "resume_from_confirm")]
+        new_worker: Option<String>,
+        /// 发送提议与确认消息的 owner 地址
+        #[arg(long, value_hint = ValueHint::Other, required_unless_present = "resume_from_confirm")]
+        owner_from: Option<String>,
+        /// 按保存的进度继续：等到可确认纪元后发送确认
+        #[arg(long, conflicts_with_all = ["new_worker", "owner_from"])]
+        resume_from_confirm: bool,
         #[arg(long, default_value = "false")]
         really_do_it: bool,
     },
 }
 
-pub async fn run(cmd: ActorCmd, cfg: &Config, store: &Store) -> Result<()> {
-    let api = LotusApi::new(&cfg.lotus.host, cfg.lotus.token.clone());
+/// rotate-worker 等待提议与确认消息上链的确认纪元数
+const ROTATION_CONFIDENCE: u64 = 3;
+/// 等待可确认纪元时查询链头的最长间隔
+const ROTATION_POLL_INTERVAL_SECS: i64 = 600;
+
+pub async fn run(cmd: ActorCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    let api = LotusApi::from_config(cfg).with_miner_info_cache(store, cfg.miner_info_ttl_secs());
 
     match cmd.command {
-        ActorSubCmd::Info { miner } => {
+        ActorSubCmd::Info { miner, full } => {
             let info = api.state_miner_info(&miner).await?;
-            let balance = api.state_miner_available_balance(&miner).await?;
+            let controls = info.control_addresses.clone().unwrap_or_default();
+            let (balances, sectors) = futures::join!(
+                futures::future::join_all(controls.iter().map(|a| api.wallet_balance(a))),
+                deadline_sectors(&api, &miner),
+            );
+
+            let display = |address: &str| super::display_address(cfg, address);
+            println!("Miner: {}", display(&miner));
+            println!("Owner: {}", display(&info.owner));
+            println!("Worker: {}", display(&info.worker));
+            if let Some(beneficiary) = &info.beneficiary {
+                println!("Beneficiary: {}", display(beneficiary));
+            }
+            if let Some(term) = &info.beneficiary_term {
+                println!(
+                    "Beneficiary Quota: {} (used: {}, expires at epoch {})",
+                    format_fil(&term.quota.0), format_fil(&term.used_quota.0), term.expiration
+                );
+            }
+            if let Some(pending) = &info.pending_beneficiary_term {
+                println!(
+                    "Pending Beneficiary: {} (quota: {}, expires at epoch {}, approved by beneficiary: {}, by nominee: {})",
+                    display(&pending.new_beneficiary), format_fil(&pending.new_quota.0), pending.new_expiration,
+                    pending.approved_by_beneficiary, pending.approved_by_nominee
+                );
+            }
+            println!("Peer ID: {}", info.peer_id.as_deref().unwrap_or("-"));
+            println!("Sector Size: {}", format_bytes_power(&num_bigint::BigInt::from(info.sector_size)));
+
+            if controls.is_empty() {
+                println!("Control Addresses: none");
+            } else {
+                println!("Control Addresses:");
+                for (address, balance) in controls.iter().zip(balances) {
+                    match balance {
+                        Ok(balance) => println!("  {:<44} {}", display(address), format_fil(&balance.0)),
+                        Err(e) => println!("  {:<44} balance unavailable: {:#}", display(address), e),
+                    }
+                }
+            }
+
+            // 截止期查询失败不影响其余信息的显示
+            match sectors {
+                Ok(sectors) => print_deadline_sectors(&sectors),
+                Err(e) => eprintln!("WARNING: cannot get sectors per deadline: {:#}", e),
+            }
+
+            if full {
+                let (balance, power) = futures::try_join!(
+                    api.state_miner_available_balance(&miner),
+                    api.state_miner_power(&miner),
+                )?;
+                println!();
+                println!("Available Balance: {}", format_fil(&balance.0));
+                println!("{}", super::miner::format_power_line(&power));
+                println!(
+                    "Network: {} raw / {} QA",
+                    format_bytes_power(&power.total_power.raw_byte_power.0),
+                    format_bytes_power(&power.total_power.quality_adj_power.0),
+                );
+                println!("Has Min Power: {}", power.has_min_power);
+            }
+        }
+        ActorSubCmd::Deadlines { miner } => {
+            let deadlines = api.state_miner_deadlines(&miner).await?;
+            let Some(current_epoch) = deadlines.first().map(|d| d.current_epoch) else {
+                return Ok(());
+            };
 
             println!("Miner: {}", miner);
-            println!("Owner: {}", info.owner);
-            println!("Worker: {}", info.worker);
-            println!("Available Balance: {} attoFIL", balance);
+            println!("Current Epoch: {}", current_epoch);
+            println!("{:<6} {:<10} {:<12} {:<12} {:<24} {:<10}", "Index", "Status", "Open", "Close", "Opens At (UTC)", "Mutable");
+            println!("{}", "-".repeat(78));
+            for d in deadlines {
+                let status = if d.is_open(current_epoch) { "proving" } else { "" };
+                let opens_at = chrono::Utc::now() + chrono::Duration::seconds(d.opens_in_seconds(current_epoch));
+                println!(
+                    "{:<6} {:<10} {:<12} {:<12} {:<24} {:<10}",
+                    d.index, status, d.open, d.close,
+                    opens_at.format("%Y-%m-%d %H:%M:%S"),
+                    if d.is_mutable(current_epoch) { "yes" } else { "no" },
+                );
+            }
+        }
+        ActorSubCmd::Withdraw { miner, miner_label, amount, from, from_label, wait } => {
+            let miner = super::resolve_address_arg(store, miner, miner_label, "miner")?;
+            let from = super::resolve_from_arg(store, from, from_label)?;
+            use crate::service::Executor;
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.miner_withdraw(&miner, &from, &amount).await? else { return Ok(()) };
+            super::report_sent(cfg, &executor.api, &cid, &wait, out, || println!("Message CID: {}", cid.root)).await?;
+        }
+        ActorSubCmd::SetOwner { miner, miner_label, new_owner, from, from_label, really_do_it } => {
+            let miner = super::resolve_address_arg(store, miner, miner_label, "miner")?;
+            let from = super::resolve_from_arg(store, from, from_label)?;
+            if !really_do_it {
+                println!("Pass --really-do-it to actually execute this action");
+                return Ok(());
+            }
+            use crate::service::Executor;
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.change_owner(&miner, &new_owner, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+        ActorSubCmd::ProposeChangeWorker { miner, miner_label, new_worker, from, from_label, really_do_it } => {
+            let miner = super::resolve_address_arg(store, miner, miner_label, "miner")?;
+            let from = super::resolve_from_arg(store, from, from_label)?;
+            if !really_do_it {
+                println!("Pass --really-do-it to actually execute this action");
+                return Ok(());
+            }
+            use crate::service::Executor;
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.propose_change_worker(&miner, &new_worker, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+        ActorSubCmd::ProposeMultisigWithdraw { miner, miner_label, amount, msig, from, from_label, really_do_it } => {
+            let miner = super::resolve_address_arg(store, miner, miner_label, "miner")?;
+            let from = super::resolve_from_arg(store, from, from_label)?;
+            if !really_do_it {
+                println!("Pass --really-do-it to actually execute this action");
+                return Ok(());
+            }
+            use crate::service::Executor;
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.multisig_propose_miner_withdraw(&msig, &miner, &from, &amount).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+        ActorSubCmd::CreateMiner { owner, worker, window_post_proof_type, peer_id, initial_collateral, from, really_do_it } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            if !really_do_it {
+                println!("Pass --really-do-it to actually execute this action");
+                return Ok(());
+            }
+            use crate::service::Executor;
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.create_miner(&owner, &worker, window_post_proof_type, &peer_id, &from, &initial_collateral).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+        ActorSubCmd::SetBeneficiary { miner, miner_label, new_beneficiary, quota, expiration, from, from_label, really_do_it } => {
+            let miner = super::resolve_address_arg(store, miner, miner_label, "miner")?;
+            let from = super::resolve_from_arg(store, from, from_label)?;
+            let new_beneficiary = super::resolve_address_or_name(store, &new_beneficiary)?;
+            let quota = parse_fil(&quota)?;
+            if !really_do_it {
+                println!("Pass --really-do-it to actually execute this action");
+                return Ok(());
+            }
+            use crate::service::Executor;
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.change_beneficiary(&miner, &new_beneficiary, &quota.to_string(), expiration, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
         }
-        ActorSubCmd::Withdraw { miner, amount, from } => {
+        ActorSubCmd::AddControl { miner, miner_label, control, from, from_label, really_do_it } => {
+            let miner = super::resolve_address_arg(store, miner, miner_label, "miner")?;
+            let from = super::resolve_from_arg(store, from, from_label)?;
+            let control = super::resolve_address_or_name(store, &control)?;
+            if !really_do_it {
+                println!("Pass --really-do-it to actually execute this action");
+                return Ok(());
+            }
             use crate::service::Executor;
-            let executor = Executor::new(cfg, store);
-            let cid = executor.miner_withdraw(&miner, &from, &amount).await?;
-            println!("Message CID: {}", cid.root);
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.add_control_address(&miner, &control, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
         }
-        ActorSubCmd::SetOwner { miner, new_owner, from, really_do_it } => {
+        ActorSubCmd::RemoveControl { miner, miner_label, control, from, from_label, really_do_it } => {
+            let miner = super::resolve_address_arg(store, miner, miner_label, "miner")?;
+            let from = super::resolve_from_arg(store, from, from_label)?;
+            let control = super::resolve_address_or_name(store, &control)?;
             if !really_do_it {
                 println!("Pass --really-do-it to actually execute this action");
                 return Ok(());
             }
             use crate::service::Executor;
-            let executor = Executor::new(cfg, store);
-            let cid = executor.change_owner(&miner, &new_owner, &from).await?;
-            println!("Message CID: {}", cid.root);
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.remove_control_address(&miner, &control, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
         }
-        ActorSubCmd::ProposeChangeWorker { miner, new_worker, from, really_do_it } => {
+        ActorSubCmd::ConfirmChangeWorker { miner, miner_label, from, from_label, really_do_it } => {
+            let miner = super::resolve_address_arg(store, miner, miner_label, "miner")?;
+            let from = super::resolve_from_arg(store, from, from_label)?;
             if !really_do_it {
                 println!("Pass --really-do-it to actually execute this action");
                 return Ok(());
             }
             use crate::service::Executor;
-            let executor = Executor::new(cfg, store);
-            let cid = executor.propose_change_worker(&miner, &new_worker, &from).await?;
-            println!("Message CID: {}", cid.root);
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let Some(cid) = executor.confirm_change_worker(&miner, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
         }
-        ActorSubCmd::ConfirmChangeWorker { miner, from, really_do_it } => {
+        ActorSubCmd::RotateWorker { miner, new_worker, owner_from, resume_from_confirm, really_do_it } => {
+            let miner = super::resolve_address_or_name(store, &miner)?;
             if !really_do_it {
                 println!("Pass --really-do-it to actually execute this action");
                 return Ok(());
             }
             use crate::service::Executor;
-            let executor = Executor::new(cfg, store);
-            let cid = executor.confirm_change_worker(&miner, &from).await?;
-            println!("Message CID: {}", cid.root);
+            // 先创建 Executor：需要输入密码时在开始前询问，而不是在数小时的等待之后
+            let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+            let rotation = match (resume_from_confirm, new_worker, owner_from) {
+                (true, _, _) => {
+                    let rotation = store.get_worker_rotation(&miner)?.ok_or_else(|| anyhow::anyhow!(
+                        "no worker rotation in progress for {}; start one with --new-worker and --owner-from", miner
+                    ))?;
+                    println!("Resuming rotation of {} to {} (proposed in {})", miner, rotation.new_worker, rotation.propose_cid);
+                    if cfg.dry_run {
+                        // 确认消息要到变更纪元之后才能执行，此前无法估算 gas
+                        println!("Dry run: the remaining steps (wait for the change epoch, confirm) were not run");
+                        return Ok(());
+                    }
+                    rotation
+                }
+                (false, Some(new_worker), Some(owner_from)) => {
+                    if let Some(existing) = store.get_worker_rotation(&miner)? {
+                        anyhow::bail!(
+                            "a rotation of {} to {} is already in progress (proposed in {}); continue it with --resume-from-confirm",
+                            miner, existing.new_worker, existing.propose_cid
+                        );
+                    }
+                    let new_worker = super::resolve_address_or_name(store, &new_worker)?;
+                    let owner = super::resolve_key_address(store, &owner_from)?;
+                    println!("[1/5] Proposing {} as the new worker of {}", new_worker, miner);
+                    let Some(cid) = executor.propose_change_worker(&miner, &new_worker, &owner).await? else {
+                        println!("Dry run: steps 2-5 (wait for inclusion, wait for the change epoch, confirm) were not run");
+                        return Ok(());
+                    };
+                    println!("      Message CID: {}", cid.root);
+                    let rotation = WorkerRotation {
+                        miner: miner.clone(),
+                        new_worker,
+                        owner,
+                        propose_cid: cid.root,
+                        change_epoch: None,
+                        created_at: chrono::Utc::now(),
+                    };
+                    store.insert_worker_rotation(&rotation)?;
+                    rotation
+                }
+                _ => anyhow::bail!("--new-worker and --owner-from are required unless --resume-from-confirm is given"),
+            };
+            rotate_worker(cfg, store, &executor, rotation).await?;
         }
     }
     Ok(())
 }
+
+/// 一个截止期的扇区数（所有分区之和）
+struct DeadlineSectors {
+    index: u64,
+    sectors: u64,
+    faulty: u64,
+}
+
+/// 先通过 StateMinerDeadlines 取得截止期数量，再并发查询每个截止期的分区
+async fn deadline_sectors(api: &LotusApi, miner: &str) -> Result<Vec<DeadlineSectors>> {
+    let count = api.state_miner_deadline_count(miner).await?;
+    let partitions = futures::future::try_join_all((0..count).map(|i| api.state_miner_partitions(miner, i))).await?;
+    Ok(partitions
+        .into_iter()
+        .zip(0..)
+        .map(|(partitions, index)| DeadlineSectors {
+            index,
+            sectors: partitions.iter().map(|p| p.all_sectors.count()).sum(),
+            faulty: partitions.iter().map(|p| p.faulty_sectors.count()).sum(),
+        })
+        .collect())
+}
+
+/// 只列出有扇区的截止期
+fn print_deadline_sectors(deadlines: &[DeadlineSectors]) {
+    let total: u64 = deadlines.iter().map(|d| d.sectors).sum();
+    let faulty: u64 = deadlines.iter().map(|d| d.faulty).sum();
+    let used: Vec<&DeadlineSectors> = deadlines.iter().filter(|d| d.sectors > 0).collect();
+    println!("Sectors: {} (faulty: {}) in {} of {} deadlines", total, faulty, used.len(), deadlines.len());
+    if used.is_empty() {
+        return;
+    }
+    println!("  {:<10} {:<10} Faulty", "Deadline", "Sectors");
+    for d in used {
+        println!("  {:<10} {:<10} {}", d.index, d.sectors, d.faulty);
+    }
+}
+
+/// rotate-worker 的第 2-5 步；每步完成后保存进度，任何一步中断都可以重新执行
+async fn rotate_worker(cfg: &Config, store: &Store, executor: &crate::service::Executor<'_>, rotation: WorkerRotation) -> Result<()> {
+    let api = &executor.api;
+    let miner = &rotation.miner;
+
+    let change_epoch = match rotation.change_epoch {
+        Some(epoch) => epoch,
+        None => {
+            println!("[2/5] Waiting for the proposal to be included ({} confirmations)...", ROTATION_CONFIDENCE);
+            let cid = Cid { root: rotation.propose_cid.clone() };
+            let lookup = api.state_wait_msg(&cid, ROTATION_CONFIDENCE).await?;
+            if lookup.receipt.exit_code != 0 {
+                store.delete_worker_rotation(miner)?;
+                return super::check_exit_code(&cid, lookup.receipt.exit_code);
+            }
+            println!("      Included at epoch {}", lookup.height);
+
+            println!("[3/5] Reading the worker change epoch");
+            let info = api.state_miner_info_uncached(miner).await?;
+            let Some((pending_worker, epoch)) = info.pending_worker_change() else {
+                anyhow::bail!("{} has no pending worker change", miner);
+            };
+            let expected = api.state_lookup_id(&rotation.new_worker).await?;
+            if pending_worker != expected {
+                anyhow::bail!("the pending worker change of {} is to {}, not {}; it was replaced by another proposal", miner, pending_worker, rotation.new_worker);
+            }
+            store.set_worker_rotation_epoch(miner, epoch)?;
+            println!("      New worker can be confirmed at epoch {}", epoch);
+            epoch
+        }
+    };
+
+    println!("[4/5] Waiting for epoch {}", change_epoch);
+    loop {
+        let height = api.chain_head().await?["Height"].as_i64().unwrap_or(0);
+        if height >= change_epoch {
+            break;
+        }
+        let remaining = (change_epoch - height) * EPOCH_DURATION_SECONDS;
+        println!("      Epoch {}: about {} minutes left", height, (remaining + 59) / 60);
+        let wait = remaining.clamp(1, ROTATION_POLL_INTERVAL_SECS) as u64;
+        tokio::time::sleep(Duration::from_secs(wait)).await;
+    }
+
+    println!("[5/5] Confirming the worker change");
+    // dry-run 在第 1 步之后已返回，确认消息总会被广播
+    let cid = executor.confirm_change_worker(miner, &rotation.owner).await?
+        .ok_or_else(|| anyhow::anyhow!("confirmation was not pushed (dry run)"))?;
+    println!("      Message CID: {}", cid.root);
+    let lookup = api.state_wait_msg(&cid, ROTATION_CONFIDENCE).await?;
+    // 确认失败时保留进度，可以再次 --resume-from-confirm
+    super::check_exit_code(&cid, lookup.receipt.exit_code)?;
+    store.delete_worker_rotation(miner)?;
+
+    let info = api.state_miner_info_uncached(miner).await?;
+    println!("Worker of {} is now {}", super::display_address(cfg, miner), super::display_address(cfg, &info.worker));
+    Ok(())
+}
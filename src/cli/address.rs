@@ -0,0 +1,84 @@
+//! 地址工具命令：主网 / 测试网前缀转换与地址校验
+
+use crate::chain::{Address, Network, Protocol};
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// 地址工具命令参数
+#[derive(Args)]
+pub struct AddressCmd {
+    #[command(subcommand)]
+    pub command: AddressSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum AddressSubCmd {
+    /// 转换地址的网络前缀（f ↔ t），目标网络由 --network 指定，未指定时切换为另一网络
+    Convert { address: String },
+    /// 校验地址语法、校验和与载荷长度；地址无效时以非零状态退出
+    Validate {
+        address: String,
+        /// 同时通过 StateLookupID 确认地址在链上存在
+        #[arg(long)]
+        on_chain: bool,
+    },
+}
+
+pub async fn run(cmd: AddressCmd, cfg: &Config, _store: &Store) -> Result<()> {
+    match cmd.command {
+        AddressSubCmd::Convert { address } => {
+            let parsed = Address::from_string(&address)?;
+            let network = cfg.lotus.network.unwrap_or(if address.starts_with('t') {
+                Network::Mainnet
+            } else {
+                Network::Testnet
+            });
+            println!("{}", parsed.to_string_with_network(network));
+        }
+        AddressSubCmd::Validate { address, on_chain } => {
+            let parsed = match Address::from_string(&address) {
+                Ok(parsed) => parsed,
+                Err(e) => return fail("syntax", &e.to_string()),
+            };
+            println!("syntax:   ok");
+
+            // 解析时不校验 checksum，重新编码后与输入比较；不打印重新编码的结果，
+            // 载荷有误时它是另一个合法地址，不能当作"修正后"的地址使用
+            let network = if address.starts_with('t') { Network::Testnet } else { Network::Mainnet };
+            if parsed.to_string_with_network(network) != address {
+                return fail("checksum", "checksum does not match the payload (typo in the address?)");
+            }
+            println!("checksum: ok");
+
+            let expected_len = match parsed.protocol {
+                Protocol::Secp256k1 | Protocol::Actor => Some(20),
+                Protocol::BLS => Some(48),
+                _ => None,
+            };
+            if let Some(expected) = expected_len {
+                if parsed.payload.len() != expected {
+                    return fail("payload", &format!("expected {} bytes, got {}", expected, parsed.payload.len()));
+                }
+                println!("payload:  ok ({} bytes)", expected);
+            }
+
+            if on_chain {
+                let api = crate::rpc::LotusApi::from_config(cfg);
+                match api.state_lookup_id(&address).await {
+                    Ok(id) => println!("on-chain: ok ({})", id),
+                    Err(e) => return fail("on-chain", &format!("{:#}", e)),
+                }
+            }
+            println!("Valid: {}", address);
+        }
+    }
+    Ok(())
+}
+
+/// 打印失败的检查项并返回错误，使命令以非零状态退出
+fn fail(check: &str, reason: &str) -> Result<()> {
+    println!("{:<9} FAILED - {}", format!("{}:", check), reason);
+    anyhow::bail!("invalid address: {} check failed", check)
+}
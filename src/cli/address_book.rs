@@ -0,0 +1,102 @@
+//! 地址簿命令：为常用目标地址（交易所、矿池、财库等）起名
+
+use crate::chain::Address;
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// 地址簿命令参数
+#[derive(Args)]
+pub struct AddressBookCmd {
+    #[command(subcommand)]
+    pub command: AddressBookSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum AddressBookSubCmd {
+    /// 添加条目；名称必须唯一
+    Add {
+        name: String,
+        address: String,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// 删除条目
+    Remove { name: String },
+    /// 列出所有条目
+    List,
+    /// 按名称查询地址
+    Lookup { name: String },
+}
+
+pub async fn run(cmd: AddressBookCmd, _cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    match cmd.command {
+        AddressBookSubCmd::Add { name, address, note } => {
+            // 具有地址形式的名称在 send 等命令中会被当作地址，无法通过名称引用
+            if super::looks_like_address(&name) {
+                anyhow::bail!("name '{}' looks like an address; choose a different name", name);
+            }
+            validate_address(&address)?;
+            if store.lookup_address_book_entry(&name)?.is_some() {
+                anyhow::bail!("address book entry '{}' already exists; remove it first", name);
+            }
+            store.insert_address_book_entry(&name, &address, note.as_deref())?;
+            println!("Added {} -> {}", name, address);
+        }
+        AddressBookSubCmd::Remove { name } => {
+            if !store.delete_address_book_entry(&name)? {
+                anyhow::bail!("address book entry '{}' not found", name);
+            }
+            println!("Removed {}", name);
+        }
+        AddressBookSubCmd::List => {
+            let entries = store.list_address_book()?;
+            out.print_list(&entries, |entries| {
+                if entries.is_empty() {
+                    println!("Address book is empty");
+                    return;
+                }
+                println!("{:<20} {:<44} {:<20} Note", "Name", "Address", "Created");
+                println!("{}", "-".repeat(90));
+                for e in entries {
+                    println!(
+                        "{:<20} {:<44} {:<20} {}",
+                        e.name,
+                        e.address,
+                        e.created_at.format("%Y-%m-%d %H:%M:%S"),
+                        e.note.as_deref().unwrap_or("")
+                    );
+                }
+            })?;
+        }
+        AddressBookSubCmd::Lookup { name } => {
+            let entry = store.lookup_address_book_entry(&name)?
+                .ok_or_else(|| anyhow::anyhow!("address book entry '{}' not found", name))?;
+            out.print_record(&entry, |e| {
+                println!("Name:    {}", e.name);
+                println!("Address: {}", e.address);
+                if let Some(note) = &e.note {
+                    println!("Note:    {}", note);
+                }
+                println!("Created: {}", e.created_at.format("%Y-%m-%d %H:%M:%S"));
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// ID 地址（f0/t0）只需是十进制数字，其余协议按完整格式解析
+fn validate_address(address: &str) -> Result<()> {
+    let valid = match address.strip_prefix('f').or_else(|| address.strip_prefix('t')) {
+        Some(rest) => match rest.strip_prefix('0') {
+            Some(id) => !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()),
+            None => Address::from_string(address).is_ok(),
+        },
+        None => false,
+    };
+    if !valid {
+        anyhow::bail!("invalid address: {}", address);
+    }
+    Ok(())
+}
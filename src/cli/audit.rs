@@ -0,0 +1,88 @@
+//! 私钥使用审计日志查询命令
+
+use crate::chain::format_fil;
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueHint};
+use num_bigint::BigInt;
+
+/// 签名审计日志命令参数
+#[derive(Args)]
+pub struct AuditCmd {
+    #[command(subcommand)]
+    pub command: AuditSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum AuditSubCmd {
+    /// 列出签名审计记录，最新的在前
+    List {
+        #[arg(long, value_hint = ValueHint::Other)]
+        address: Option<String>,
+        /// RFC3339 时间或相对时长（如 "7d" 表示最近 7 天）
+        #[arg(long)]
+        since: Option<String>,
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// 删除指定日期之前的审计记录（不可恢复，需确认）
+    Purge {
+        /// RFC3339 时间或 YYYY-MM-DD 日期
+        #[arg(long, required = true)]
+        force_purge_before: String,
+    },
+}
+
+pub async fn run(cmd: AuditCmd, _cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    match cmd.command {
+        AuditSubCmd::List { address, since, limit } => {
+            let address = address.map(|a| super::resolve_key_address(store, &a)).transpose()?;
+            let since = since.as_deref().map(super::parse_since).transpose()?;
+            let entries = store.query_signing_audit(address.as_deref(), since, limit)?;
+            out.print_list(&entries, |entries| {
+                println!(
+                    "{:<20} {:<44} {:<10} {:<44} {:<24} {:<7} {:<8} {:<8}",
+                    "Timestamp", "Address", "Type", "To", "Value", "Method", "Nonce", "PID"
+                );
+                println!("{}", "-".repeat(172));
+                for e in entries {
+                    let value = format_fil(&e.value_attofil.parse::<BigInt>().unwrap_or_default());
+                    println!(
+                        "{:<20} {:<44} {:<10} {:<44} {:<24} {:<7} {:<8} {:<8}",
+                        e.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                        e.address,
+                        e.key_type,
+                        e.to_addr,
+                        value,
+                        e.method,
+                        e.nonce,
+                        e.signed_by_pid
+                    );
+                    println!("  cid: {}", e.cid_hex);
+                }
+            })?;
+        }
+        AuditSubCmd::Purge { force_purge_before } => {
+            let before = parse_date(&force_purge_before)?;
+            let prompt = format!("Permanently delete audit records before {}?", before.to_rfc3339());
+            if !super::confirm(&prompt)? {
+                println!("Aborted");
+                return Ok(());
+            }
+            let deleted = store.purge_signing_audit_before(before)?;
+            println!("Deleted {} audit records", deleted);
+        }
+    }
+    Ok(())
+}
+
+/// 解析 RFC3339 时间或 YYYY-MM-DD 日期（UTC 零点）
+fn parse_date(s: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(t) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(t.with_timezone(&chrono::Utc));
+    }
+    let date = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("invalid date: {} (expected RFC3339 or YYYY-MM-DD)", s))?;
+    Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+}
@@ -0,0 +1,91 @@
+//! 批量签名与批量广播命令
+
+use crate::chain::{Message, SignedMessage};
+use crate::config::Config;
+use crate::db::Store;
+use crate::rpc::LotusApi;
+use crate::wallet::Wallet;
+use anyhow::Result;
+use clap::{Args, ValueHint};
+use std::fs;
+use std::path::PathBuf;
+
+/// 批量签名：输入为 Message JSON 数组，输出为 SignedMessage JSON 数组
+#[derive(Args)]
+pub struct BatchSignCmd {
+    #[arg(value_hint = ValueHint::FilePath)]
+    pub file: PathBuf,
+    /// 输出文件（默认：标准输出）
+    #[arg(long, value_hint = ValueHint::FilePath)]
+    pub out: Option<PathBuf>,
+    /// 有消息的发送地址不在钱包中时直接失败，而不是跳过
+    #[arg(long)]
+    pub strict: bool,
+}
+
+/// 批量广播：输入为 SignedMessage JSON 数组
+#[derive(Args)]
+pub struct BatchPushCmd {
+    #[arg(value_hint = ValueHint::FilePath)]
+    pub file: PathBuf,
+}
+
+pub async fn run_sign(cmd: BatchSignCmd, cfg: &Config, store: &Store) -> Result<()> {
+    let messages: Vec<Message> = serde_json::from_str(&fs::read_to_string(&cmd.file)?)
+        .map_err(|e| anyhow::anyhow!("invalid message array in {}: {}", cmd.file.display(), e))?;
+
+    let mut to_sign = Vec::new();
+    for (i, msg) in messages.iter().enumerate() {
+        let from = msg.from.to_string();
+        if store.has_key(&from)? {
+            to_sign.push((msg, from));
+        } else if cmd.strict {
+            anyhow::bail!("message {}: no local key for {}", i, from);
+        } else {
+            eprintln!("Warning: skipping message {}: no local key for {}", i, from);
+        }
+    }
+
+    let wallet = Wallet::with_enc_key(store, cfg.enc_key()?);
+    let refs: Vec<(&Message, &str)> = to_sign.iter().map(|(m, f)| (*m, f.as_str())).collect();
+    let mut signed = Vec::new();
+    for ((msg, from), result) in refs.iter().zip(wallet.batch_sign(&refs)) {
+        let signature = result.map_err(|e| anyhow::anyhow!("signing message from {} failed: {}", from, e))?;
+        signed.push(SignedMessage { message: (*msg).clone(), signature });
+    }
+
+    eprintln!("Signed {} of {} messages", signed.len(), messages.len());
+    super::tx::write_output(cmd.out.as_ref(), &serde_json::to_string_pretty(&signed)?)
+}
+
+pub async fn run_push(cmd: BatchPushCmd, cfg: &Config, _store: &Store) -> Result<()> {
+    let messages: Vec<SignedMessage> = serde_json::from_str(&fs::read_to_string(&cmd.file)?)
+        .map_err(|e| anyhow::anyhow!("invalid signed message array in {}: {}", cmd.file.display(), e))?;
+    if cfg.dry_run {
+        println!("{}", serde_json::to_string_pretty(&messages)?);
+        println!("Dry run: {} messages were not pushed", messages.len());
+        return Ok(());
+    }
+    let api = LotusApi::from_config(cfg);
+
+    let mut cids = Vec::new();
+    let mut failed = 0;
+    for (i, msg) in messages.iter().enumerate() {
+        match api.mpool_push(msg).await {
+            Ok(cid) => {
+                eprintln!("[{}] {} nonce {}: {}", i, msg.message.from, msg.message.nonce, cid.root);
+                cids.push(cid.root);
+            }
+            Err(e) => {
+                eprintln!("[{}] {} nonce {}: failed: {}", i, msg.message.from, msg.message.nonce, e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("{}", serde_json::to_string_pretty(&cids)?);
+    if failed > 0 {
+        anyhow::bail!("{} of {} messages failed to push", failed, messages.len());
+    }
+    Ok(())
+}
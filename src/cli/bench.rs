@@ -0,0 +1,228 @@
+//! 数据库、RPC 与签名性能基准测试命令
+
+use crate::chain::{Address, BigInt, Message};
+use crate::config::Config;
+use crate::crypto;
+use crate::db::Store;
+use crate::rpc::LotusApi;
+use crate::wallet::{sign_message_with_key, KeyType, PrivateKey, Wallet};
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueHint};
+use serde::Serialize;
+use std::time::Instant;
+
+/// 基准测试命令参数
+#[derive(Args)]
+pub struct BenchCmd {
+    #[command(subcommand)]
+    pub command: BenchSubCmd,
+    /// 以 JSON 数组输出结果
+    #[arg(long, global = true)]
+    pub json: bool,
+}
+
+#[derive(Subcommand)]
+pub enum BenchSubCmd {
+    /// 测试 store.get_key / store.list_keys 吞吐量，并与阻塞线程池中执行的异步变体对比
+    Db {
+        #[arg(long, default_value = "1000")]
+        iterations: usize,
+    },
+    /// 测试 ChainHead 调用延迟
+    Rpc {
+        #[arg(long, default_value = "100")]
+        iterations: usize,
+    },
+    /// 分别测试 secp256k1 与 BLS 消息签名吞吐量
+    Sign {
+        /// 钱包中的地址，按其密钥类型测试（含解密私钥）；另一类型及省略时使用临时生成、不保存的密钥
+        #[arg(long, value_hint = ValueHint::Other)]
+        address: Option<String>,
+        #[arg(long, default_value = "1000")]
+        iterations: usize,
+    },
+}
+
+/// 单项基准测试结果
+#[derive(Debug, Serialize)]
+struct BenchResult {
+    operation: String,
+    iterations: usize,
+    mean_ms: f64,
+    p50_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+    ops_per_sec: f64,
+}
+
+impl BenchResult {
+    fn from_samples(operation: &str, mut samples: Vec<f64>) -> Self {
+        samples.sort_by(|a, b| a.total_cmp(b));
+        let iterations = samples.len();
+        let total: f64 = samples.iter().sum();
+        let mean_ms = if iterations > 0 { total / iterations as f64 } else { 0.0 };
+        Self {
+            operation: operation.to_string(),
+            iterations,
+            mean_ms,
+            p50_ms: percentile(&samples, 50.0),
+            p95_ms: percentile(&samples, 95.0),
+            p99_ms: percentile(&samples, 99.0),
+            ops_per_sec: if total > 0.0 { iterations as f64 * 1000.0 / total } else { 0.0 },
+        }
+    }
+}
+
+pub async fn run(cmd: BenchCmd, cfg: &Config, store: &Store) -> Result<()> {
+    let results = match cmd.command {
+        BenchSubCmd::Db { iterations } => {
+            let address = store.list_keys()?
+                .first()
+                .map(|k| k.address.clone())
+                .unwrap_or_else(|| "f1nonexistent".to_string());
+            vec![
+                measure("store.get_key", iterations, || store.get_key(&address).map(|_| ()))?,
+                measure_async("store.get_key_async", iterations, || async {
+                    store.get_key_async(&address).await.map(|_| ())
+                }).await?,
+                measure("store.list_keys", iterations, || store.list_keys().map(|_| ()))?,
+                measure_async("store.list_keys_async", iterations, || async {
+                    store.list_keys_async().await.map(|_| ())
+                }).await?,
+            ]
+        }
+        BenchSubCmd::Rpc { iterations } => {
+            let api = LotusApi::from_config(cfg);
+            vec![measure_async("rpc.chain_head", iterations, || async { api.chain_head().await.map(|_| ()) }).await?]
+        }
+        BenchSubCmd::Sign { address, iterations } => {
+            let stored = match &address {
+                Some(address) => {
+                    let key = store.get_key(address)?
+                        .ok_or_else(|| anyhow::anyhow!("key not found: {}", address))?;
+                    Some((address.as_str(), key.key_type))
+                }
+                None => None,
+            };
+            let password = if stored.is_some() { cfg.get_password_or_prompt()? } else { String::new() };
+            let wallet = Wallet::new(store, &password);
+
+            let mut results = Vec::new();
+            for key_type in [KeyType::Secp256k1, KeyType::BLS] {
+                let op = format!("sign.{}", key_type.as_str());
+                match &stored {
+                    Some((address, stored_type)) if stored_type == key_type.as_str() => {
+                        let msg = bench_message(Address::from_string(address)?);
+                        results.push(measure(&op, iterations, || wallet.sign(&msg, address).map(|_| ()))?);
+                    }
+                    _ => {
+                        eprintln!("Using a temporary {} key for {} (not stored)", key_type.as_str(), op);
+                        let key = PrivateKey::generate(key_type)?;
+                        let msg = bench_message(key.address()?);
+                        results.push(measure(&op, iterations, || sign_message_with_key(msg.clone(), &key).map(|_| ()))?);
+                    }
+                }
+            }
+            results
+        }
+    };
+
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        println!(
+            "{:<20} {:>10} {:>10} {:>10} {:>10} {:>10} {:>12}",
+            "Operation", "Iterations", "Mean(ms)", "P50(ms)", "P95(ms)", "P99(ms)", "Ops/sec"
+        );
+        println!("{}", "-".repeat(88));
+        for r in &results {
+            println!(
+                "{:<20} {:>10} {:>10.3} {:>10.3} {:>10.3} {:>10.3} {:>12.1}",
+                r.operation, r.iterations, r.mean_ms, r.p50_ms, r.p95_ms, r.p99_ms, r.ops_per_sec
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 发送给自己的零值转账，用于签名基准
+fn bench_message(addr: Address) -> Message {
+    Message {
+        version: 0,
+        to: addr.clone(),
+        from: addr,
+        nonce: 0,
+        value: BigInt::zero(),
+        gas_limit: 0,
+        gas_fee_cap: BigInt::zero(),
+        gas_premium: BigInt::zero(),
+        method: 0,
+        params: vec![],
+    }
+}
+
+/// 预热 10% 的迭代次数后再逐次计时
+fn measure<F>(operation: &str, iterations: usize, mut f: F) -> Result<BenchResult>
+where
+    F: FnMut() -> Result<()>,
+{
+    for _ in 0..warmup_count(iterations) {
+        f()?;
+    }
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed().as_secs_f64() * 1000.0);
+    }
+    Ok(BenchResult::from_samples(operation, samples))
+}
+
+/// 与 measure 相同，用于异步操作
+async fn measure_async<F, Fut>(operation: &str, iterations: usize, mut f: F) -> Result<BenchResult>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    for _ in 0..warmup_count(iterations) {
+        f().await?;
+    }
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        f().await?;
+        samples.push(start.elapsed().as_secs_f64() * 1000.0);
+    }
+    Ok(BenchResult::from_samples(operation, samples))
+}
+
+fn warmup_count(iterations: usize) -> usize {
+    iterations / 10
+}
+
+/// 已排序样本的百分位值
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let idx = ((p / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[idx.min(sorted.len() - 1)]
+}
+
+/// 搜索 KDF 参数并输出可直接粘贴到配置文件的建议
+pub async fn run_kdf(target_ms: u64) -> Result<()> {
+    println!("Benchmarking Argon2id (target ~{}ms)...", target_ms);
+    println!("{}", kdf_suggestion(target_ms).await?);
+    Ok(())
+}
+
+/// 运行 KDF 基准，返回配置文件注释形式的参数建议；`config init --benchmark-kdf` 写入生成的配置
+pub async fn kdf_suggestion(target_ms: u64) -> Result<String> {
+    let (m, t, p) = tokio::task::spawn_blocking(move || crypto::benchmark_kdf(target_ms)).await?;
+    let actual = tokio::task::spawn_blocking(move || crypto::kdf_time_ms(m, t, p)).await?;
+
+    if actual > target_ms {
+        println!("Warning: even the minimum memory setting exceeds the target on this machine");
+    }
+    Ok(format!("# KDF benchmark result: m={}, t={}, p={} (~{}ms on this machine)", m, t, p, actual))
+}
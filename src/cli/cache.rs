@@ -0,0 +1,29 @@
+//! 本地缓存维护命令
+
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// 缓存命令参数
+#[derive(Args)]
+pub struct CacheCmd {
+    #[command(subcommand)]
+    pub command: CacheSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum CacheSubCmd {
+    /// 删除所有缓存的矿工信息，下次使用时重新向节点查询
+    Clear,
+}
+
+pub async fn run(cmd: CacheCmd, _cfg: &Config, store: &Store) -> Result<()> {
+    match cmd.command {
+        CacheSubCmd::Clear => {
+            let removed = store.clear_miner_info_cache()?;
+            println!("Removed {} cached miner info entries", removed);
+        }
+    }
+    Ok(())
+}
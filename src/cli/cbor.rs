@@ -0,0 +1,180 @@
+//! CBOR 调试命令：解码链上消息参数，或从 JSON 构造自定义方法调用的参数
+
+use crate::chain::actors::{
+    ChangeBeneficiaryParams, ChangeOwnerParams, ChangeWorkerParams, MarketWithdrawParams, WithdrawBalanceParams,
+    METHOD_CHANGE_BENEFICIARY, METHOD_CHANGE_OWNER, METHOD_CHANGE_WORKER, METHOD_MARKET_WITHDRAW,
+    METHOD_WITHDRAW_BALANCE, STORAGE_MARKET_ACTOR,
+};
+use crate::chain::Address;
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use ciborium::Value;
+use clap::{Args, Subcommand, ValueEnum};
+
+/// CBOR 调试命令参数
+#[derive(Args)]
+pub struct CborCmd {
+    #[command(subcommand)]
+    pub command: CborSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum CborSubCmd {
+    /// 解码 base64 或 hex 编码的 CBOR（如消息的 Params 字段）
+    Decode {
+        data: String,
+        #[arg(long = "type", value_enum, default_value = "raw")]
+        params_type: ParamsType,
+    },
+    /// 将 JSON 编码为 CBOR 并输出 hex；以 "0x" 开头的字符串编码为字节串
+    Encode { json: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ParamsType {
+    Withdraw,
+    ChangeOwner,
+    ChangeWorker,
+    ChangeBeneficiary,
+    MarketWithdraw,
+    /// 输出 CBOR 诊断格式
+    Raw,
+}
+
+pub async fn run(cmd: CborCmd, _cfg: &Config, _store: &Store) -> Result<()> {
+    match cmd.command {
+        CborSubCmd::Decode { data, params_type } => {
+            let bytes = decode_input(&data)?;
+            println!("{}", describe_params(&bytes, params_type)?);
+        }
+        CborSubCmd::Encode { json } => {
+            let json: serde_json::Value = serde_json::from_str(&json)
+                .map_err(|e| anyhow::anyhow!("invalid JSON: {}", e))?;
+            let mut buf = Vec::new();
+            ciborium::into_writer(&json_to_cbor(&json)?, &mut buf)?;
+            println!("{}", hex::encode(buf));
+        }
+    }
+    Ok(())
+}
+
+impl ParamsType {
+    /// 按接收方与方法号推断参数类型；无法确定或不是已知方法时返回 None
+    pub(super) fn for_method(to: &Address, method: u64) -> Option<Self> {
+        match method {
+            METHOD_MARKET_WITHDRAW if *to == Address::from_string(STORAGE_MARKET_ACTOR).ok()? => Some(Self::MarketWithdraw),
+            METHOD_WITHDRAW_BALANCE => Some(Self::Withdraw),
+            METHOD_CHANGE_OWNER => Some(Self::ChangeOwner),
+            METHOD_CHANGE_WORKER => Some(Self::ChangeWorker),
+            METHOD_CHANGE_BENEFICIARY => Some(Self::ChangeBeneficiary),
+            _ => None,
+        }
+    }
+}
+
+/// 将参数解码为可读的单行描述
+pub(super) fn describe_params(bytes: &[u8], params_type: ParamsType) -> Result<String> {
+    Ok(match params_type {
+        ParamsType::Withdraw => {
+            let p = WithdrawBalanceParams::from_cbor(bytes)?;
+            format!("WithdrawBalanceParams {{ amount: {} attoFIL }}", p.amount)
+        }
+        ParamsType::ChangeOwner => {
+            let p = ChangeOwnerParams::from_cbor(bytes)?;
+            format!("ChangeOwnerParams {{ new_owner: {} }}", p.new_owner)
+        }
+        ParamsType::ChangeWorker => {
+            let p = ChangeWorkerParams::from_cbor(bytes)?;
+            let controls: Vec<String> = p.new_control_addresses.iter().map(|a| a.to_string()).collect();
+            format!(
+                "ChangeWorkerParams {{ new_worker: {}, new_control_addresses: [{}] }}",
+                p.new_worker,
+                controls.join(", ")
+            )
+        }
+        ParamsType::ChangeBeneficiary => {
+            let p = ChangeBeneficiaryParams::from_cbor(bytes)?;
+            format!(
+                "ChangeBeneficiaryParams {{ new_beneficiary: {}, new_quota: {} attoFIL, new_expiration: {} }}",
+                p.new_beneficiary, p.new_quota, p.new_expiration
+            )
+        }
+        ParamsType::MarketWithdraw => {
+            let p = MarketWithdrawParams::from_cbor(bytes)?;
+            format!(
+                "MarketWithdrawParams {{ provider_or_client: {}, amount: {} attoFIL }}",
+                p.provider_or_client, p.amount
+            )
+        }
+        ParamsType::Raw => {
+            let value: Value = ciborium::from_reader(bytes)
+                .map_err(|e| anyhow::anyhow!("invalid CBOR: {}", e))?;
+            diagnostic(&value)
+        }
+    })
+}
+
+/// 输入可以是 hex（可带 0x 前缀）或标准 base64
+fn decode_input(data: &str) -> Result<Vec<u8>> {
+    use base64::Engine;
+
+    let data = data.trim();
+    let hex_data = data.strip_prefix("0x").unwrap_or(data);
+    if hex_data.len().is_multiple_of(2) && hex_data.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Ok(hex::decode(hex_data)?);
+    }
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|_| anyhow::anyhow!("input is neither hex nor base64"))
+}
+
+fn json_to_cbor(json: &serde_json::Value) -> Result<Value> {
+    use serde_json::Value as Json;
+
+    Ok(match json {
+        Json::Null => Value::Null,
+        Json::Bool(b) => Value::Bool(*b),
+        Json::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+            (Some(u), _, _) => Value::Integer(u.into()),
+            (_, Some(i), _) => Value::Integer(i.into()),
+            (_, _, Some(f)) => Value::Float(f),
+            _ => anyhow::bail!("unsupported number: {}", n),
+        },
+        Json::String(s) => match s.strip_prefix("0x") {
+            Some(hex_str) => Value::Bytes(hex::decode(hex_str)
+                .map_err(|e| anyhow::anyhow!("invalid hex bytes {}: {}", s, e))?),
+            None => Value::Text(s.clone()),
+        },
+        Json::Array(items) => Value::Array(items.iter().map(json_to_cbor).collect::<Result<_>>()?),
+        Json::Object(map) => Value::Map(
+            map.iter()
+                .map(|(k, v)| Ok((Value::Text(k.clone()), json_to_cbor(v)?)))
+                .collect::<Result<_>>()?,
+        ),
+    })
+}
+
+/// CBOR 诊断格式（RFC 8949 §8），字节串显示为 h'..'
+fn diagnostic(value: &Value) -> String {
+    match value {
+        Value::Integer(i) => i128::from(*i).to_string(),
+        Value::Bytes(b) => format!("h'{}'", hex::encode(b)),
+        Value::Text(s) => format!("{:?}", s),
+        Value::Float(f) => f.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => "null".to_string(),
+        Value::Tag(tag, inner) => format!("{}({})", tag, diagnostic(inner)),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(diagnostic).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Map(entries) => {
+            let entries: Vec<String> = entries.iter()
+                .map(|(k, v)| format!("{}: {}", diagnostic(k), diagnostic(v)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        _ => "undefined".to_string(),
+    }
+}
@@ -0,0 +1,179 @@
+//! 链上数据查询命令
+
+use super::cbor::{describe_params, ParamsType};
+use crate::chain::{format_fil, Message};
+use crate::config::Config;
+use crate::db::Store;
+use crate::rpc::{Cid, LotusApi};
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueHint};
+use serde_json::Value;
+
+/// 链上数据查询命令参数
+#[derive(Args)]
+pub struct ChainCmd {
+    #[command(subcommand)]
+    pub command: ChainSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum ChainSubCmd {
+    /// 查询 EVM 交易回执（以太坊兼容接口）
+    EthReceipt {
+        /// 0x 开头的交易哈希
+        tx_hash: String,
+    },
+    /// 列出地址发送的历史消息
+    ListMessages {
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: String,
+        /// 只显示指定方法号的消息
+        #[arg(long)]
+        method: Option<u64>,
+        /// 最多显示的消息条数
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// 搜索的最早纪元（默认：链头前 2880 个纪元，约 1 天）
+        #[arg(long)]
+        since_epoch: Option<i64>,
+    },
+    /// 按 CID 查询消息并解码已知方法的参数
+    GetMessage { cid: String },
+    /// 按 CID 查询已上链消息的回执
+    GetReceipt { cid: String },
+    /// 阻塞等待消息上链，打印上链高度与退出码
+    WaitMsg {
+        cid: String,
+        /// 上链后等待的确认纪元数
+        #[arg(long, default_value = "3")]
+        confidence: u64,
+    },
+}
+
+pub async fn run(cmd: ChainCmd, cfg: &Config, _store: &Store) -> Result<()> {
+    let api = LotusApi::from_config(cfg);
+
+    match cmd.command {
+        ChainSubCmd::EthReceipt { tx_hash } => {
+            let receipt = api.eth_get_transaction_receipt(&tx_hash).await?;
+            if receipt.is_null() {
+                anyhow::bail!("receipt not found: {}", tx_hash);
+            }
+
+            let status = match receipt["status"].as_str() {
+                Some("0x1") => "success",
+                Some(_) => "failed",
+                None => "unknown",
+            };
+            println!("Transaction: {}", tx_hash);
+            println!("Status: {}", status);
+            println!("Block Number: {}", hex_quantity(&receipt["blockNumber"]));
+            println!("Gas Used: {}", hex_quantity(&receipt["gasUsed"]));
+
+            let logs = receipt["logs"].as_array().cloned().unwrap_or_default();
+            println!("Logs: {}", logs.len());
+            for (i, log) in logs.iter().enumerate() {
+                println!("  [{}] address: {}", i, log["address"].as_str().unwrap_or(""));
+                for topic in log["topics"].as_array().into_iter().flatten() {
+                    println!("      topic: {}", topic.as_str().unwrap_or(""));
+                }
+                println!("      data: {}", log["data"].as_str().unwrap_or(""));
+            }
+        }
+        ChainSubCmd::ListMessages { from, method, limit, since_epoch } => {
+            let since = match since_epoch {
+                Some(e) => e,
+                None => {
+                    let head = api.chain_head().await?;
+                    head["Height"].as_i64().unwrap_or(0).saturating_sub(2880).max(0)
+                }
+            };
+            let cids = api.state_list_messages(Some(&from), None, None, Some(since)).await?;
+
+            // 节点不支持按方法号过滤：指定 --method 时获取全部消息后在本地过滤，每条消息只获取一次
+            let (total, messages) = match method {
+                Some(method) => {
+                    let mut matched = Vec::new();
+                    for cid in cids {
+                        let msg = api.chain_get_message(&cid).await?;
+                        if msg.method == method {
+                            matched.push((cid, msg));
+                        }
+                    }
+                    let total = matched.len();
+                    matched.truncate(limit);
+                    (total, matched)
+                }
+                None => {
+                    let mut messages = Vec::new();
+                    for cid in cids.iter().take(limit) {
+                        messages.push((cid.clone(), api.chain_get_message(cid).await?));
+                    }
+                    (cids.len(), messages)
+                }
+            };
+
+            println!("Found {} messages since epoch {}", total, since);
+            println!("{:<64} {:<10} {:<44} {:<8} {:<24}", "CID", "Nonce", "To", "Method", "Value");
+            println!("{}", "-".repeat(154));
+            for (cid, msg) in &messages {
+                println!(
+                    "{:<64} {:<10} {:<44} {:<8} {:<24}",
+                    cid.root, msg.nonce, msg.to.to_string(), msg.method, format_fil(&msg.value.0)
+                );
+            }
+            if total > limit {
+                println!("... {} more (use --limit to show more)", total - limit);
+            }
+        }
+        ChainSubCmd::GetMessage { cid } => {
+            let msg = api.chain_get_message(&Cid { root: cid.clone() }).await?;
+            println!("CID: {}", cid);
+            println!("From: {}", msg.from);
+            println!("To: {}", msg.to);
+            println!("Nonce: {}", msg.nonce);
+            println!("Value: {}", format_fil(&msg.value.0));
+            println!("Method: {}", msg.method);
+            println!("Gas Limit: {}", msg.gas_limit);
+            println!("Gas Fee Cap: {} attoFIL", msg.gas_fee_cap);
+            println!("Gas Premium: {} attoFIL", msg.gas_premium);
+            println!("Params: {}", describe_message_params(&msg));
+        }
+        ChainSubCmd::GetReceipt { cid } => {
+            let receipt = api.state_get_receipt(&Cid { root: cid.clone() }).await?;
+            println!("CID: {}", cid);
+            println!("Exit code: {}", receipt.exit_code);
+            println!("Gas used: {}", receipt.gas_used);
+            println!("Return: {}", receipt.return_data.as_deref().filter(|r| !r.is_empty()).unwrap_or("-"));
+        }
+        ChainSubCmd::WaitMsg { cid, confidence } => {
+            let cid = Cid { root: cid };
+            println!("Waiting for {} confirmations...", confidence);
+            let lookup = api.state_wait_msg(&cid, confidence).await?;
+            println!("Included at epoch {}", lookup.height);
+            println!("Exit code: {}", lookup.receipt.exit_code);
+            println!("Gas used: {}", lookup.receipt.gas_used);
+            super::check_exit_code(&cid, lookup.receipt.exit_code)?;
+        }
+    }
+    Ok(())
+}
+
+/// 已知方法按参数类型解码，解码失败（如同方法号的其他 Actor）或未知方法时输出 CBOR 诊断格式
+fn describe_message_params(msg: &Message) -> String {
+    if msg.params.is_empty() {
+        return "-".to_string();
+    }
+    ParamsType::for_method(&msg.to, msg.method)
+        .and_then(|t| describe_params(&msg.params, t).ok())
+        .or_else(|| describe_params(&msg.params, ParamsType::Raw).ok())
+        .unwrap_or_else(|| format!("0x{}", hex::encode(&msg.params)))
+}
+
+/// 将以太坊 JSON-RPC 的十六进制数量（如 "0x1a"）转换为十进制字符串
+fn hex_quantity(v: &Value) -> String {
+    v.as_str()
+        .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -0,0 +1,51 @@
+//! CID 调试命令：独立计算或校验消息 CID
+
+use crate::chain::{cbor, Message};
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+
+/// CID 调试命令参数
+#[derive(Args)]
+pub struct CidCmd {
+    #[command(subcommand)]
+    pub command: CidSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum CidSubCmd {
+    /// 计算 JSON 消息的 CID
+    Compute { json_message: String },
+    /// 计算 JSON 消息的 CID 并与给定 CID 比对
+    Verify { cid: String, json_message: String },
+}
+
+pub async fn run(cmd: CidCmd, _cfg: &Config, _store: &Store) -> Result<()> {
+    match cmd.command {
+        CidSubCmd::Compute { json_message } => {
+            let data = serialize_json_message(&json_message)?;
+            println!("CID:     {}", cbor::compute_cid(&data));
+            println!("CID hex: {}", hex::encode(cbor::compute_cid_bytes(&data)));
+        }
+        CidSubCmd::Verify { cid, json_message } => {
+            let expected = cbor::cid_from_string(&cid.to_lowercase())?;
+            let actual = cbor::compute_cid_bytes(&serialize_json_message(&json_message)?);
+            if expected == actual {
+                println!("Match");
+            } else {
+                println!("Mismatch");
+                println!("  expected: {}", cid);
+                println!("  computed: {}", cbor::cid_to_string(&actual));
+                anyhow::bail!("CID mismatch");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 解析 Lotus JSON 格式的消息并按链上格式序列化
+fn serialize_json_message(json_message: &str) -> Result<Vec<u8>> {
+    let msg: Message = serde_json::from_str(json_message).context("invalid message JSON")?;
+    cbor::serialize_message(&msg)
+}
@@ -0,0 +1,197 @@
+//! 配置文件管理命令
+
+use crate::config::Config;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::fs;
+use std::path::Path;
+
+/// `config init --benchmark-kdf` 的 KDF 基准目标耗时，与 `kdf-benchmark` 的默认值相同
+const KDF_TARGET_MS: u64 = 250;
+
+/// 配置管理命令参数
+#[derive(Args)]
+pub struct ConfigCmd {
+    #[command(subcommand)]
+    pub command: ConfigSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigSubCmd {
+    /// 检查配置：节点地址为合法 URL、数据库路径可写、已设置的密码非空
+    Validate,
+    /// 以 TOML 打印当前生效的配置（密码等敏感字段显示为 "***"）
+    Show,
+    /// 在默认路径写入默认 config.toml
+    Init {
+        /// 覆盖已存在的配置文件
+        #[arg(long)]
+        force: bool,
+        /// 先测试本机 Argon2id 耗时，并将建议的 KDF 参数作为注释写入文件（可能需要数十秒）
+        #[arg(long)]
+        benchmark_kdf: bool,
+    },
+    /// 列出 config.toml 中的配置集
+    ListProfiles,
+    /// 向 config.toml 添加（或替换）一个配置集
+    AddProfile {
+        #[arg(long)]
+        name: String,
+        /// Lotus 节点 RPC 地址
+        #[arg(long)]
+        host: String,
+        /// 数据库文件路径
+        #[arg(long)]
+        db: Option<String>,
+        /// Lotus API token
+        #[arg(long)]
+        token: Option<String>,
+    },
+}
+
+/// 不需要数据库：main 在打开数据库之前运行，`validate` 才能如实报告不可写的路径，`init` 也不会创建数据库文件
+pub async fn run(cmd: ConfigCmd, cfg: &Config) -> Result<()> {
+    match cmd.command {
+        ConfigSubCmd::Validate => {
+            let errors = validate(cfg);
+            if !errors.is_empty() {
+                let list: Vec<String> = errors.iter().map(|e| format!("  - {}", e)).collect();
+                anyhow::bail!("config is invalid:\n{}", list.join("\n"));
+            }
+            println!("Config is valid");
+        }
+        ConfigSubCmd::Show => {
+            let mut doc = toml::Table::try_from(cfg)?;
+            redact_secrets(&mut doc);
+            print!("{}", toml::to_string_pretty(&doc)?);
+        }
+        ConfigSubCmd::Init { force, benchmark_kdf } => {
+            let path = Config::config_path();
+            if path.exists() && !force {
+                anyhow::bail!("{} already exists; use --force to overwrite", path.display());
+            }
+            let mut content = String::new();
+            if benchmark_kdf {
+                println!("Benchmarking Argon2id (target ~{}ms)...", KDF_TARGET_MS);
+                content = format!("{}\n", super::bench::kdf_suggestion(KDF_TARGET_MS).await?);
+            }
+            content.push_str(&toml::to_string_pretty(&Config::default())?);
+            fs::write(&path, content)?;
+            println!("Wrote default config to {}", path.display());
+        }
+        ConfigSubCmd::ListProfiles => {
+            let mut profiles: Vec<_> = cfg.profiles.iter().flatten().collect();
+            if profiles.is_empty() {
+                println!("No profiles configured");
+                return Ok(());
+            }
+            profiles.sort_by(|a, b| a.0.cmp(b.0));
+
+            println!("{:<16} {:<48} {:<24}", "Name", "Host", "Database");
+            println!("{}", "-".repeat(90));
+            for (name, p) in profiles {
+                let host = p.lotus.as_ref().map(|l| l.host.as_str()).unwrap_or("-");
+                let db = p.database.as_ref().map(|d| d.path.as_str()).unwrap_or("-");
+                println!("{:<16} {:<48} {:<24}", name, host, db);
+            }
+        }
+        ConfigSubCmd::AddProfile { name, host, db, token } => {
+            let path = Config::config_path();
+            let mut doc: toml::Table = if path.exists() {
+                toml::from_str(&fs::read_to_string(&path)?)?
+            } else {
+                // 新建配置文件时写入当前（默认）基础配置，保证文件可被重新加载
+                let mut base = toml::Table::new();
+                let mut lotus = toml::Table::new();
+                lotus.insert("host".into(), cfg.lotus.host.clone().into());
+                let mut database = toml::Table::new();
+                database.insert("path".into(), cfg.database.path.clone().into());
+                base.insert("lotus".into(), lotus.into());
+                base.insert("database".into(), database.into());
+                base
+            };
+
+            let mut lotus = toml::Table::new();
+            lotus.insert("host".into(), host.into());
+            if let Some(token) = token {
+                lotus.insert("token".into(), token.into());
+            }
+            let mut profile = toml::Table::new();
+            profile.insert("lotus".into(), lotus.into());
+            if let Some(db) = db {
+                let mut database = toml::Table::new();
+                database.insert("path".into(), db.into());
+                profile.insert("database".into(), database.into());
+            }
+
+            let profiles = doc
+                .entry("profiles")
+                .or_insert_with(|| toml::Table::new().into())
+                .as_table_mut()
+                .ok_or_else(|| anyhow::anyhow!("'profiles' in {} is not a table", path.display()))?;
+            profiles.insert(name.clone(), profile.into());
+
+            fs::write(&path, toml::to_string_pretty(&doc)?)?;
+            println!("Added profile '{}' to {}", name, path.display());
+        }
+    }
+    Ok(())
+}
+
+/// show 时替换为 "***" 的字段
+const SECRET_KEYS: &[&str] = &["password", "token", "master_seed_hex", "webhook_secret"];
+
+/// 递归替换敏感字段（包括各配置集中的同名字段）
+fn redact_secrets(table: &mut toml::Table) {
+    for (key, value) in table.iter_mut() {
+        if SECRET_KEYS.contains(&key.as_str()) {
+            *value = "***".into();
+        } else if let Some(inner) = value.as_table_mut() {
+            redact_secrets(inner);
+        }
+    }
+}
+
+/// 逐项检查配置，返回所有问题（为空表示有效）
+fn validate(cfg: &Config) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    let hosts = std::iter::once(("lotus.host", &cfg.lotus.host))
+        .chain(cfg.lotus.fallback_hosts.iter().map(|h| ("lotus.fallback_hosts", h)));
+    for (field, host) in hosts {
+        match reqwest::Url::parse(host) {
+            Ok(url) if matches!(url.scheme(), "http" | "https" | "ws" | "wss") => {}
+            Ok(url) => errors.push(format!("{}: unsupported scheme '{}'", field, url.scheme())),
+            Err(e) => errors.push(format!("{}: invalid URL '{}': {}", field, host, e)),
+        }
+    }
+
+    if let Err(e) = check_writable(Path::new(&cfg.database.path)) {
+        errors.push(format!("database.path: '{}' is not writable: {}", cfg.database.path, e));
+    }
+    if cfg.database.encrypt {
+        if let Err(e) = cfg.database.cipher_key() {
+            errors.push(format!("database.encrypt: {}", e));
+        }
+    }
+
+    if cfg.wallet.as_ref().and_then(|w| w.password.as_deref()) == Some("") {
+        errors.push("wallet.password: must not be empty when set".to_string());
+    }
+    errors
+}
+
+/// 检查路径是否可写且不留下副作用：已存在的文件以追加方式打开，
+/// 不存在时在所在目录创建并删除一个探测文件
+fn check_writable(path: &Path) -> std::io::Result<()> {
+    if path.exists() {
+        return fs::OpenOptions::new().append(true).open(path).map(drop);
+    }
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let probe = dir.join(format!(".lotus-sign-write-test-{}", std::process::id()));
+    fs::OpenOptions::new().write(true).create_new(true).open(&probe)?;
+    fs::remove_file(&probe)
+}
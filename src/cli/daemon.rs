@@ -0,0 +1,31 @@
+//! 守护进程命令：通过 HTTP 提供远程签名
+
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use clap::Args;
+
+/// 以 HTTP 守护进程运行，提供远程签名 REST API
+#[derive(Args)]
+pub struct DaemonCmd {
+    /// 监听地址
+    #[arg(long, default_value = "127.0.0.1:1235")]
+    pub bind: String,
+    /// 客户端需在 Authorization: Bearer 头中提供的令牌
+    #[arg(long, env = "LOTUS_SIGN_DAEMON_TOKEN", hide_env_values = true)]
+    pub token: String,
+    /// 同时在该地址提供 Prometheus `/metrics`
+    #[arg(long)]
+    pub metrics_addr: Option<String>,
+}
+
+pub async fn run(cmd: DaemonCmd, cfg: &Config, store: &Store) -> Result<()> {
+    if let Some(addr) = cmd.metrics_addr {
+        tokio::spawn(async move {
+            if let Err(e) = crate::metrics::serve(&addr).await {
+                tracing::error!(error = %e, "metrics server stopped");
+            }
+        });
+    }
+    crate::daemon::serve(cfg, store, &cmd.bind, cmd.token).await
+}
@@ -0,0 +1,60 @@
+//! Filecoin Plus DataCap 查询与分配命令
+
+use crate::chain::{format_bytes_power, parse_bytes_power};
+use crate::config::Config;
+use crate::db::Store;
+use crate::rpc::LotusApi;
+use crate::service::Executor;
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueHint};
+
+/// DataCap 命令参数
+#[derive(Args)]
+pub struct DatacapCmd {
+    #[command(subcommand)]
+    pub command: DatacapSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum DatacapSubCmd {
+    /// 查看地址的 DataCap 余额
+    Status {
+        address: String,
+    },
+    /// 公证人向客户分配 DataCap
+    Transfer {
+        /// 客户地址
+        #[arg(long, value_hint = ValueHint::Other)]
+        to: String,
+        /// DataCap 数量（字节，可带单位，如 "10TiB"）
+        #[arg(long)]
+        amount: String,
+        /// 公证人地址（钱包中必须有对应私钥；省略时使用默认地址）
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+    },
+}
+
+pub async fn run(cmd: DatacapCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    match cmd.command {
+        DatacapSubCmd::Status { address } => {
+            let api = LotusApi::from_config(cfg);
+            match api.state_verified_client_status(&address).await? {
+                Some(datacap) => {
+                    println!("{}: {} ({} bytes)", address, format_bytes_power(&datacap.0), datacap);
+                }
+                None => println!("{} is not a verified client", address),
+            }
+            let root_key = api.state_verified_registry_root_key().await?;
+            println!("Verified Registry Root Key: {}", root_key);
+        }
+        DatacapSubCmd::Transfer { to, amount, from } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            let bytes = parse_bytes_power(&amount)?;
+            let executor = Executor::new_async(cfg, store).await?;
+            let Some(cid) = executor.datacap_transfer(&to, &bytes.to_string(), &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+    }
+    Ok(())
+}
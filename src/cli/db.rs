@@ -0,0 +1,74 @@
+//! 数据库维护命令：在普通 SQLite 与 SQLCipher 加密数据库之间迁移
+//!
+//! 迁移会替换 database.path 指向的文件，执行前应停止使用该数据库的其他进程（如 daemon）
+
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::fs;
+use std::path::Path;
+
+/// 数据库维护命令参数
+#[derive(Args)]
+pub struct DbCmd {
+    #[command(subcommand)]
+    pub command: DbSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum DbSubCmd {
+    /// 将未加密的数据库复制为 SQLCipher 加密数据库并替换原文件，密钥取自 database.cipher_key_env
+    MigrateEncrypt,
+    /// 将加密数据库解密为普通 SQLite 数据库并替换原文件
+    MigrateDecrypt,
+}
+
+/// 不经过 main 中打开的 Store：迁移前后数据库的加密状态与配置不一致
+pub async fn run(cmd: DbCmd, cfg: &Config) -> Result<()> {
+    let key = cfg.database.cipher_key()?;
+    let path = &cfg.database.path;
+    match cmd.command {
+        DbSubCmd::MigrateEncrypt => {
+            let backup = migrate(path, None, Some(&key))?;
+            println!("Encrypted {}", path);
+            eprintln!("WARNING: the unencrypted original was kept at {}; delete it once the encrypted database works", backup);
+            println!("Set database.encrypt = true in config.toml");
+        }
+        DbSubCmd::MigrateDecrypt => {
+            let backup = migrate(path, Some(&key), None)?;
+            println!("Decrypted {} (encrypted original kept at {})", path, backup);
+            println!("Set database.encrypt = false in config.toml");
+        }
+    }
+    Ok(())
+}
+
+/// 导出到临时文件并确认密钥数量一致后，原文件改名为 `.bak`，临时文件替换原文件；返回备份路径
+fn migrate(path: &str, from_key: Option<&str>, to_key: Option<&str>) -> Result<String> {
+    if !Path::new(path).exists() {
+        anyhow::bail!("database not found: {}", path);
+    }
+    let tmp = format!("{}.migrating", path);
+    let backup = format!("{}.bak", path);
+    if Path::new(&backup).exists() {
+        anyhow::bail!("{} already exists; move it away first", backup);
+    }
+    let _ = fs::remove_file(&tmp);
+
+    let source = Store::open(path, from_key)?;
+    let expected = source.list_all_keys()?.len();
+    source.export_to(&tmp, to_key)?;
+    drop(source);
+
+    let copied = Store::open(&tmp, to_key)?.list_all_keys()?.len();
+    if copied != expected {
+        let _ = fs::remove_file(&tmp);
+        anyhow::bail!("copy has {} keys but {} has {}; nothing was changed", copied, path, expected);
+    }
+
+    fs::rename(path, &backup)?;
+    fs::rename(&tmp, path)?;
+    tracing::info!(path = %path, backup = %backup, encrypted = to_key.is_some(), "database migrated");
+    Ok(backup)
+}
@@ -0,0 +1,119 @@
+//! 以太坊兼容签名命令（FVM/EVM 交易）
+
+use crate::chain::rlp;
+use crate::config::Config;
+use crate::db::Store;
+use crate::wallet::Wallet;
+use anyhow::Result;
+use clap::{Args, ValueHint};
+
+/// 按 EIP-155 签名 legacy 交易，输出已签名的 RLP 交易（hex）
+#[derive(Args)]
+pub struct EthSignCmd {
+    /// 待签交易的 RLP 编码（hex，可带 0x 前缀）
+    pub tx: String,
+    /// 签名地址（secp256k1 密钥），默认使用 wallet set-default 配置的地址
+    #[arg(long, value_hint = ValueHint::Other)]
+    pub from: Option<String>,
+    #[arg(long)]
+    pub chain_id: u64,
+}
+
+pub async fn run(cmd: EthSignCmd, cfg: &Config, store: &Store) -> Result<()> {
+    let from = super::resolve_from_arg(store, cmd.from, None)?;
+    let unsigned = hex::decode(cmd.tx.trim().trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("invalid transaction hex: {}", e))?;
+
+    let (fields, signing_payload) = signing_payload(&unsigned, cmd.chain_id)?;
+    let wallet = Wallet::with_enc_key(store, cfg.enc_key()?);
+    let sig = wallet.sign_eth(&signing_payload, cmd.chain_id, &from)?;
+
+    println!("0x{}", hex::encode(assemble_signed(fields, cmd.chain_id, &sig)?));
+    Ok(())
+}
+
+/// 返回交易的前 6 项与 EIP-155 待签格式 [..6 项.., chainId, 0, 0]；
+/// 只有 6 项时补齐后再签名，使 v 与签名哈希一致
+fn signing_payload(unsigned: &[u8], chain_id: u64) -> Result<(Vec<Vec<u8>>, Vec<u8>)> {
+    let mut fields = rlp::decode_list(unsigned)?;
+    match fields.len() {
+        9 => {
+            fields.truncate(6);
+            Ok((fields, unsigned.to_vec()))
+        }
+        6 => {
+            let mut eip155 = fields.clone();
+            eip155.extend([rlp::encode_uint(chain_id), rlp::encode_uint(0), rlp::encode_uint(0)]);
+            Ok((fields, rlp::encode_list(&eip155)))
+        }
+        n => anyhow::bail!("expected a legacy transaction with 6 or 9 fields, got {}", n),
+    }
+}
+
+/// 在前 6 项后追加 v = chainId * 2 + 35 + recid、r、s，返回已签名交易的 RLP 编码
+fn assemble_signed(mut fields: Vec<Vec<u8>>, chain_id: u64, sig: &[u8; 65]) -> Result<Vec<u8>> {
+    let v = chain_id
+        .checked_mul(2)
+        .and_then(|v| v.checked_add(35 + sig[64] as u64))
+        .ok_or_else(|| anyhow::anyhow!("chain id {} is too large for EIP-155 signing", chain_id))?;
+    fields.push(rlp::encode_uint(v));
+    fields.push(rlp::trim_leading_zeros(&sig[..32]));
+    fields.push(rlp::trim_leading_zeros(&sig[32..64]));
+    Ok(rlp::encode_list(&fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{self, CipherAlgorithm};
+    use crate::db::WalletKey;
+    use crate::wallet::{KeyType, PrivateKey};
+
+    const PASSWORD: &str = "eth-test";
+    /// EIP-155 示例：nonce 9、gasPrice 20 gwei、gas 21000、to 0x3535…35、value 1 ETH、data 为空，chainId 1
+    const EIP155_PAYLOAD: &str = "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080";
+    const EIP155_SIGNED: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    /// 存入 EIP-155 示例私钥 0x4646…46，返回其 f1 地址
+    fn store_with_example_key(dir: &tempfile::TempDir) -> (Store, String) {
+        let store = Store::open(&dir.path().join("eth.db").to_string_lossy(), None).unwrap();
+        let key = PrivateKey::from_bytes(KeyType::Secp256k1, &[0x46; 32]).unwrap();
+        let address = key.address().unwrap().to_string();
+        let encrypted = crypto::encrypt(&key.private_key, &crypto::derive_key(PASSWORD), CipherAlgorithm::default()).unwrap();
+        store.insert_key(&WalletKey::new(address.clone(), "secp256k1".to_string(), encrypted)).unwrap();
+        (store, address)
+    }
+
+    fn sign(wallet: &Wallet, unsigned: &[u8], from: &str) -> Result<Vec<u8>> {
+        let (fields, payload) = signing_payload(unsigned, 1)?;
+        let sig = wallet.sign_eth(&payload, 1, from)?;
+        assemble_signed(fields, 1, &sig)
+    }
+
+    #[test]
+    fn signs_eip155_example() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, from) = store_with_example_key(&dir);
+        let wallet = Wallet::new(&store, PASSWORD);
+
+        let payload = hex::decode(EIP155_PAYLOAD).unwrap();
+        assert_eq!(hex::encode(sign(&wallet, &payload, &from).unwrap()), EIP155_SIGNED);
+
+        // 只给出 6 项时补齐 [chainId, 0, 0] 后签名，结果相同
+        let mut fields = rlp::decode_list(&payload).unwrap();
+        fields.truncate(6);
+        assert_eq!(hex::encode(sign(&wallet, &rlp::encode_list(&fields), &from).unwrap()), EIP155_SIGNED);
+    }
+
+    #[test]
+    fn rejects_payload_with_non_empty_r_s() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, from) = store_with_example_key(&dir);
+        let wallet = Wallet::new(&store, PASSWORD);
+
+        let mut fields = rlp::decode_list(&hex::decode(EIP155_PAYLOAD).unwrap()).unwrap();
+        fields[7] = vec![1];
+        let err = sign(&wallet, &rlp::encode_list(&fields), &from).unwrap_err();
+        assert!(err.to_string().contains("[chainId, 0, 0]"));
+    }
+}
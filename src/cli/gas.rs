@@ -0,0 +1,69 @@
+//! Gas 费用查询命令
+
+use crate::chain::{format_fil, format_nanofil};
+use crate::config::Config;
+use crate::db::Store;
+use crate::rpc::LotusApi;
+use crate::service::Executor;
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueHint};
+
+/// Gas 命令参数
+#[derive(Args)]
+pub struct GasCmd {
+    #[command(subcommand)]
+    pub command: GasSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum GasSubCmd {
+    /// 查看当前基础费用
+    BaseFee {
+        /// 同时估算下一纪元的基础费用
+        #[arg(long)]
+        next: bool,
+    },
+    /// 估算一笔消息的 gas 费用（不签名）
+    Estimate {
+        /// 目标地址
+        #[arg(long, value_hint = ValueHint::Other)]
+        to: String,
+        /// 金额（attoFIL）
+        #[arg(long, default_value = "0")]
+        amount: String,
+        /// 发送地址（省略时使用默认地址）
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        /// 方法号（默认：0 = 转账）
+        #[arg(long, default_value = "0")]
+        method: u64,
+    },
+}
+
+pub async fn run(cmd: GasCmd, cfg: &Config, store: &Store) -> Result<()> {
+    match cmd.command {
+        GasSubCmd::BaseFee { next } => {
+            let api = LotusApi::from_config(cfg);
+            let base_fee = api.chain_base_fee().await?;
+            println!("Base Fee: {} attoFIL ({})", base_fee, format_nanofil(&base_fee.0));
+
+            if next {
+                let next_fee = api.estimate_next_base_fee().await?;
+                println!("Next Base Fee (estimated): {} attoFIL ({})", next_fee, format_nanofil(&next_fee.0));
+            }
+        }
+        GasSubCmd::Estimate { to, amount, from, method } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            let executor = Executor::new_async(cfg, store).await?;
+            let est = executor.estimate_fee_only(&from, &to, method, &amount).await?;
+
+            println!("GasLimit: {}", est.message.gas_limit);
+            println!("GasFeeCap: {} attoFIL", est.message.gas_fee_cap);
+            println!("GasPremium: {} attoFIL", est.message.gas_premium);
+            println!("Base Fee: {} attoFIL ({})", est.base_fee, format_nanofil(&est.base_fee.0));
+            println!("Min Fee Cap: {} attoFIL", est.min_fee_cap);
+            println!("Max Fee: {} attoFIL ({})", est.max_fee, format_fil(&est.max_fee.0));
+        }
+    }
+    Ok(())
+}
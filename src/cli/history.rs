@@ -0,0 +1,103 @@
+//! 消息历史与签名日志查询命令
+
+use crate::chain::format_fil;
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueHint};
+use num_bigint::BigInt;
+use super::{Formatter, OutputFormat};
+
+/// 不带子命令时列出已推送的消息（messages 表）；子命令查询签名日志（含失败记录）
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct HistoryCmd {
+    #[command(subcommand)]
+    pub command: Option<HistorySubCmd>,
+    #[arg(long, value_hint = ValueHint::Other)]
+    pub from: Option<String>,
+    #[arg(long, default_value = "50")]
+    pub limit: usize,
+    /// 以 JSON 输出（等同于 --output json）
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Subcommand)]
+pub enum HistorySubCmd {
+    /// 列出签名过的消息，最新的在前
+    List {
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        /// RFC3339 时间或相对时长（如 "7d" 表示最近 7 天）
+        #[arg(long)]
+        since: Option<String>,
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// 汇总签名日志统计
+    Stats {
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+    },
+}
+
+pub async fn run(cmd: HistoryCmd, _cfg: &Config, store: &Store, out: &Formatter) -> Result<()> {
+    let Some(command) = cmd.command else {
+        let messages = store.get_messages_by_address(cmd.from.as_deref(), cmd.limit)?;
+        let json = Formatter::new(OutputFormat::Json);
+        let out = if cmd.json { &json } else { out };
+        return out.print_list(&messages, |messages| {
+            println!(
+                "{:<20} {:<64} {:<44} {:<44} {:<24} {:<8}",
+                "Pushed At", "CID", "From", "To", "Value", "Nonce"
+            );
+            println!("{}", "-".repeat(209));
+            for m in messages {
+                let value = format_fil(&m.value.parse::<BigInt>().unwrap_or_default());
+                println!(
+                    "{:<20} {:<64} {:<44} {:<44} {:<24} {:<8}",
+                    m.pushed_at.format("%Y-%m-%d %H:%M:%S"), m.cid, m.from_addr, m.to_addr, value, m.nonce
+                );
+            }
+        });
+    };
+
+    match command {
+        HistorySubCmd::List { from, since, limit } => {
+            let since = since.as_deref().map(super::parse_since).transpose()?;
+            let entries = store.query_signing_log(from.as_deref(), since, limit)?;
+
+            println!(
+                "{:<20} {:<44} {:<44} {:<8} {:<24} {:<8}",
+                "Time", "From", "To", "Method", "Value", "Status"
+            );
+            println!("{}", "-".repeat(153));
+            for e in entries {
+                let value = format_fil(&e.value.parse::<BigInt>().unwrap_or_default());
+                let status = if e.success { "ok" } else { "failed" };
+                println!(
+                    "{:<20} {:<44} {:<44} {:<8} {:<24} {:<8}",
+                    e.timestamp.format("%Y-%m-%d %H:%M:%S"), e.from_address, e.to_address, e.method, value, status
+                );
+                match (&e.cid, &e.error) {
+                    (Some(cid), _) => println!("  CID: {}", cid),
+                    (None, Some(err)) => println!("  Error: {}", err),
+                    (None, None) => {}
+                }
+            }
+        }
+        HistorySubCmd::Stats { from } => {
+            let agg = store.signing_log_aggregate(from.as_deref())?;
+            println!("Total messages: {}", agg.total_messages);
+            println!("Succeeded:      {}", agg.success_count);
+            println!("Failed:         {}", agg.failure_count);
+            println!("Total value:    {}", format_fil(&agg.total_value_attofil));
+            match agg.last_message_at {
+                Some(t) => println!("Last message:   {}", t.to_rfc3339()),
+                None => println!("Last message:   -"),
+            }
+        }
+    }
+    Ok(())
+}
@@ -2,21 +2,28 @@ use crate::config::Config;
 use crate::db::Store;
 use crate::service::Executor;
 use anyhow::Result;
-use clap::Args;
+use clap::{Args, ValueHint};
 
 #[derive(Args)]
 pub struct MarketWithdrawCmd {
-    #[arg(long)]
+    /// 市场托管账户地址或地址簿名称
+    #[arg(long, value_hint = ValueHint::Other)]
     pub address: String,
     #[arg(long)]
     pub amount: String,
-    #[arg(long)]
-    pub from: String,
+    #[arg(long, value_hint = ValueHint::Other)]
+    pub from: Option<String>,
+    #[arg(long, conflicts_with = "from")]
+    pub from_label: Option<String>,
+    #[command(flatten)]
+    pub wait: super::WaitArgs,
 }
 
-pub async fn run(cmd: MarketWithdrawCmd, cfg: &Config, store: &Store) -> Result<()> {
-    let executor = Executor::new(cfg, store);
-    let cid = executor.market_withdraw(&cmd.address, &cmd.from, &cmd.amount).await?;
-    println!("Market Withdraw CID: {}", cid.root);
-    Ok(())
+pub async fn run(cmd: MarketWithdrawCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    let from = super::resolve_from_arg(store, cmd.from, cmd.from_label)?;
+    let address = super::resolve_address_or_name(store, &cmd.address)?;
+
+    let executor = Executor::new_async(cfg, store).await?;
+    let Some(cid) = executor.market_withdraw(&address, &from, &cmd.amount).await? else { return Ok(()) };
+    super::report_sent(cfg, &executor.api, &cid, &cmd.wait, out, || println!("Market Withdraw CID: {}", cid.root)).await
 }
@@ -0,0 +1,292 @@
+//! 矿工状态查询命令
+
+use crate::chain::{format_bytes_power, BitfieldRle};
+use crate::config::Config;
+use crate::db::Store;
+use crate::notify::{self, FaultAlertPayload};
+use crate::rpc::{is_rate_limited, LotusApi, MinerInfo, MinerPower};
+use anyhow::Result;
+use chrono::Utc;
+use clap::{Args, Subcommand, ValueHint};
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// `list --with-info` 同时进行的 StateMinerInfo 请求数
+const INFO_CONCURRENCY: usize = 10;
+/// 被限流时单个请求的最大重试次数，退避从 1 秒起逐次翻倍
+const RATE_LIMIT_RETRIES: u32 = 6;
+
+/// 矿工查询命令参数
+#[derive(Args)]
+pub struct MinerCmd {
+    #[command(subcommand)]
+    pub command: MinerSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum MinerSubCmd {
+    /// 查看矿工原始算力与有效算力
+    Power {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: String,
+    },
+    /// 列出全网矿工，可附带 owner / worker / 扇区大小
+    List {
+        /// 逐个查询 StateMinerInfo（每批 10 个并发请求）
+        #[arg(long)]
+        with_info: bool,
+        /// 只显示该 owner 的矿工（隐含 --with-info）
+        #[arg(long, value_hint = ValueHint::Other)]
+        owner: Option<String>,
+    },
+    /// 列出处于故障状态的扇区（StateMinerFaults）
+    Faults {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: String,
+    },
+    /// 定期检查故障扇区，出现新故障时发送 webhook；Ctrl-C 退出
+    MonitorFaults {
+        #[arg(long, value_hint = ValueHint::Other)]
+        miner: String,
+        /// 检查间隔（秒）
+        #[arg(long, default_value = "600")]
+        interval: u64,
+        /// 告警地址，省略时使用 notifications.webhook_url
+        #[arg(long)]
+        webhook: Option<String>,
+    },
+}
+
+/// `faults` 的 JSON 输出
+#[derive(Serialize)]
+struct MinerFaults {
+    miner: String,
+    count: u64,
+    sectors: Vec<u64>,
+}
+
+/// 矿工列表行；未查询 StateMinerInfo 时只有 miner
+#[derive(Serialize)]
+struct MinerListRow {
+    miner: String,
+    owner: Option<String>,
+    worker: Option<String>,
+    sector_size: Option<u64>,
+}
+
+pub async fn run(cmd: MinerCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    let api = LotusApi::from_config(cfg).with_miner_info_cache(store, cfg.miner_info_ttl_secs());
+
+    match cmd.command {
+        MinerSubCmd::Power { miner } => {
+            let power = api.state_miner_power(&miner).await?;
+
+            println!("Miner: {}", miner);
+            println!("{}", format_power_line(&power));
+            println!(
+                "Network: {} raw / {} QA",
+                format_bytes_power(&power.total_power.raw_byte_power.0),
+                format_bytes_power(&power.total_power.quality_adj_power.0),
+            );
+            println!("Has Min Power: {}", power.has_min_power);
+        }
+
+        MinerSubCmd::List { with_info, owner } => {
+            let miners = api.state_list_miners().await?;
+            // MinerInfo 中的 owner 为 ID 地址，先把过滤条件解析为 ID 地址再比较
+            let owner = match owner {
+                Some(owner) => {
+                    let owner = super::resolve_address_or_name(store, &owner)?;
+                    Some(api.state_lookup_id(&owner).await?)
+                }
+                None => None,
+            };
+
+            let rows: Vec<MinerListRow> = if with_info || owner.is_some() {
+                fetch_miner_infos(&api, &miners)
+                    .await
+                    .into_iter()
+                    .filter(|(_, info)| owner.as_ref().is_none_or(|owner| info.owner == *owner))
+                    .map(|(miner, info)| MinerListRow {
+                        miner,
+                        owner: Some(info.owner),
+                        worker: Some(info.worker),
+                        sector_size: Some(info.sector_size),
+                    })
+                    .collect()
+            } else {
+                miners
+                    .into_iter()
+                    .map(|miner| MinerListRow { miner, owner: None, worker: None, sector_size: None })
+                    .collect()
+            };
+
+            out.print_list(&rows, |rows| {
+                if !with_info && owner.is_none() {
+                    for row in rows {
+                        println!("{}", row.miner);
+                    }
+                } else {
+                    println!("{:<12} {:<12} {:<12} {:<10}", "Miner", "Owner", "Worker", "Sector Size");
+                    println!("{}", "-".repeat(50));
+                    for row in rows {
+                        let sector_size = row.sector_size
+                            .map(|size| format_bytes_power(&num_bigint::BigInt::from(size)))
+                            .unwrap_or_default();
+                        println!(
+                            "{:<12} {:<12} {:<12} {:<10}",
+                            row.miner,
+                            row.owner.as_deref().unwrap_or("-"),
+                            row.worker.as_deref().unwrap_or("-"),
+                            sector_size,
+                        );
+                    }
+                }
+                println!("Miners: {}", rows.len());
+            })?;
+        }
+
+        MinerSubCmd::Faults { miner } => {
+            let faults = api.state_miner_faults(&miner).await?;
+            let record = MinerFaults { miner, count: faults.count(), sectors: faults.iter().collect() };
+            out.print_record(&record, |r| {
+                if r.count == 0 {
+                    println!("{}: no faulty sectors", r.miner);
+                    return;
+                }
+                println!("{}: {} faulty sectors", r.miner, r.count);
+                println!("{}", format_sector_ranges(&faults));
+            })?;
+        }
+
+        MinerSubCmd::MonitorFaults { miner, interval, webhook } => {
+            monitor_faults(cfg, &api, &miner, interval, webhook).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 只与本进程上次检查的结果比较：启动时已有的故障只打印，不告警
+async fn monitor_faults(cfg: &Config, api: &LotusApi, miner: &str, interval: u64, webhook: Option<String>) -> Result<()> {
+    if interval == 0 {
+        anyhow::bail!("--interval must be greater than 0");
+    }
+    let notifications = cfg.notifications.as_ref();
+    let url = webhook.or_else(|| notifications.and_then(|n| n.webhook_url.clone()));
+    if url.is_none() {
+        eprintln!("WARNING: no webhook configured; new faults will only be printed (set --webhook or notifications.webhook_url)");
+    }
+    let secret = notifications.and_then(|n| n.webhook_secret.as_deref());
+
+    let mut known: BTreeSet<u64> = api.state_miner_faults(miner).await?.iter().collect();
+    println!("Monitoring {} every {}s ({} faulty sectors); Ctrl-C to stop", miner, interval, known.len());
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+    ticker.tick().await;
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+        let current: BTreeSet<u64> = match api.state_miner_faults(miner).await {
+            Ok(faults) => faults.iter().collect(),
+            Err(e) => {
+                eprintln!("WARNING: cannot fetch faults of {}: {:#}", miner, e);
+                continue;
+            }
+        };
+        let new_faults: Vec<u64> = current.difference(&known).copied().collect();
+        let recovered = known.difference(&current).count();
+        if recovered > 0 {
+            println!("{}: {} sectors recovered ({} faulty)", miner, recovered, current.len());
+        }
+        if new_faults.is_empty() {
+            known = current;
+            continue;
+        }
+
+        eprintln!("WARNING: {}: {} new faulty sectors ({} faulty)", miner, new_faults.len(), current.len());
+        if let Some(url) = &url {
+            let payload = FaultAlertPayload {
+                event: "sector_faults",
+                miner: miner.to_string(),
+                new_faults,
+                total_faults: current.len() as u64,
+                timestamp: Utc::now(),
+            };
+            // 发送失败时保留上次的故障集合，下一轮重试
+            if let Err(e) = notify::send_webhook(url, &payload, secret).await {
+                eprintln!("WARNING: fault alert for {} failed: {:#}", miner, e);
+                continue;
+            }
+        }
+        known = current;
+    }
+    println!("Monitor stopped");
+    Ok(())
+}
+
+/// 连续编号合并为区间，如 "1-3, 7, 10-12"
+fn format_sector_ranges(faults: &BitfieldRle) -> String {
+    faults
+        .ranges()
+        .map(|(start, end)| if end - start == 1 { start.to_string() } else { format!("{}-{}", start, end - 1) })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 以 INFO_CONCURRENCY 个并发请求查询矿工信息，结果保持 miners 的顺序；查询失败的矿工打印警告后跳过
+async fn fetch_miner_infos(api: &LotusApi, miners: &[String]) -> Vec<(String, MinerInfo)> {
+    async fn fetch(api: &LotusApi, i: usize, miner: &str) -> (usize, Result<MinerInfo>) {
+        (i, miner_info_with_backoff(api, miner).await)
+    }
+
+    let mut pending = miners.iter().enumerate();
+    let mut in_flight: FuturesUnordered<_> = pending
+        .by_ref()
+        .take(INFO_CONCURRENCY)
+        .map(|(i, miner)| fetch(api, i, miner))
+        .collect();
+    let mut infos = Vec::with_capacity(miners.len());
+
+    while let Some((i, result)) = in_flight.next().await {
+        match result {
+            Ok(info) => infos.push((i, info)),
+            Err(e) => eprintln!("WARNING: cannot get info for {}: {:#}", miners[i], e),
+        }
+        if let Some((i, miner)) = pending.next() {
+            in_flight.push(fetch(api, i, miner));
+        }
+    }
+
+    infos.sort_by_key(|(i, _)| *i);
+    infos.into_iter().map(|(i, info)| (miners[i].clone(), info)).collect()
+}
+
+/// 被节点限流时按指数退避重试，其他错误直接返回
+async fn miner_info_with_backoff(api: &LotusApi, miner: &str) -> Result<MinerInfo> {
+    let mut attempt = 0;
+    loop {
+        match api.state_miner_info(miner).await {
+            Err(e) if attempt < RATE_LIMIT_RETRIES && is_rate_limited(&e) => {
+                let delay = Duration::from_secs(1 << attempt);
+                attempt += 1;
+                eprintln!("WARNING: rate limited by lotus node, retrying {} in {}s", miner, delay.as_secs());
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// 格式化算力摘要，如 "Power: 1.5 PiB raw / 2 PiB QA (0.02% of network)"
+pub fn format_power_line(power: &MinerPower) -> String {
+    format!(
+        "Power: {} raw / {} QA ({}% of network)",
+        format_bytes_power(&power.miner_power.raw_byte_power.0),
+        format_bytes_power(&power.miner_power.quality_adj_power.0),
+        power.network_share_percent(),
+    )
+}
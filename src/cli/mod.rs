@@ -4,11 +4,41 @@ mod actor;
 mod withdraw;
 mod market;
 mod push;
+mod miner;
+mod datacap;
+mod gas;
+mod bench;
+mod chain;
+mod history;
+pub mod config;
+mod tx;
+mod batch;
+mod multisig;
+mod eth;
+mod paych;
+mod mpool;
+mod address;
+mod address_book;
+mod audit;
+mod cbor;
+mod cid;
+mod repl;
+mod daemon;
+mod multi_send;
+mod simulate;
+mod schedule;
+mod monitor;
+mod cache;
+pub mod db;
+pub(crate) mod password;
 
 use crate::config::Config;
 use crate::db::Store;
+use crate::rpc::{Cid, LotusApi, MsgLookup};
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum, ValueHint};
+use serde::Serialize;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "lotus-sign")]
@@ -16,25 +46,498 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+    /// 使用 config.toml 中 [profiles.<name>] 覆盖基础配置
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    /// 输出格式（目前 wallet list 与发送消息的命令支持 json/csv）
+    #[arg(long, global = true, value_enum, default_value = "table")]
+    pub output: OutputFormat,
+    /// 日志格式；日志级别由 RUST_LOG 控制（默认：warn）
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    pub log_format: LogFormat,
+    /// 将日志追加写入文件而不是标准错误
+    #[arg(long, global = true, value_hint = ValueHint::FilePath)]
+    pub log_file: Option<PathBuf>,
+    /// 构建消息（含 gas 估算）并打印，不签名也不广播
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+    /// RPC 传输错误时立即失败，不按 [retry] 策略重试
+    #[arg(long, global = true)]
+    pub no_retry: bool,
+    /// 从 OS 密钥环读取钱包密码（等同于 wallet.use_keyring = true）
+    #[arg(long, global = true)]
+    pub keyring: bool,
+    /// 钱包密码；会出现在进程列表与 shell 历史中，建议改用 --password-file
+    #[arg(long, global = true, conflicts_with = "password_file")]
+    pub password: Option<String>,
+    /// 从文件第一行读取钱包密码（如 Docker secret /run/secrets/lotus_sign_password）
+    #[arg(long, global = true, value_hint = ValueHint::FilePath)]
+    pub password_file: Option<PathBuf>,
+    /// 显示地址使用的网络前缀（覆盖配置中的 lotus.network）
+    #[arg(long, global = true, value_enum)]
+    pub network: Option<crate::chain::Network>,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     Wallet(wallet::WalletCmd),
     Send(send::SendCmd),
+    Simulate(simulate::SimulateCmd),
     Actor(actor::ActorCmd),
     Withdraw(withdraw::WithdrawCmd),
     MarketWithdraw(market::MarketWithdrawCmd),
     MpoolPush(push::PushCmd),
+    Mpool(mpool::MpoolCmd),
+    Miner(miner::MinerCmd),
+    Datacap(datacap::DatacapCmd),
+    Gas(gas::GasCmd),
+    Bench(bench::BenchCmd),
+    Chain(chain::ChainCmd),
+    History(history::HistoryCmd),
+    Config(config::ConfigCmd),
+    Tx(tx::TxCmd),
+    BatchSign(batch::BatchSignCmd),
+    BatchPush(batch::BatchPushCmd),
+    MultiSend(multi_send::MultiSendCmd),
+    Multisig(multisig::MultisigCmd),
+    EthSign(eth::EthSignCmd),
+    Paych(paych::PaychCmd),
+    Address(address::AddressCmd),
+    AddressBook(address_book::AddressBookCmd),
+    Audit(audit::AuditCmd),
+    Cbor(cbor::CborCmd),
+    Cid(cid::CidCmd),
+    Repl(repl::ReplCmd),
+    Daemon(daemon::DaemonCmd),
+    Schedule(schedule::ScheduleCmd),
+    Monitor(monitor::MonitorCmd),
+    Cache(cache::CacheCmd),
+    Db(db::DbCmd),
+    /// 测试本机 Argon2id 耗时，给出 KDF 参数建议
+    KdfBenchmark {
+        /// 目标耗时（毫秒），200-500ms 兼顾安全性与使用体验
+        #[arg(long, default_value = "250")]
+        target_ms: u64,
+    },
+    /// 输出 shell 补全脚本，如 `eval "$(lotus-sign completions bash)"`
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
 }
 
 pub async fn run(cli: Cli, cfg: Config, store: Store) -> Result<()> {
+    dispatch(cli, &cfg, &store).await
+}
+
+impl Cli {
+    /// `--password` 或 `--password-file` 提供的密码
+    pub fn password(&self) -> Result<Option<String>> {
+        match (&self.password, &self.password_file) {
+            (Some(password), _) => Ok(Some(password.clone())),
+            (None, Some(path)) => crate::config::read_password_file(path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// REPL 行与定时任务中的全局参数覆盖到已加载的配置上（--profile 除外）；
+    /// 本行另行指定密码来源时不使用会话密钥
+    fn overlay_config(&self, cfg: &Config) -> Result<Config> {
+        let mut line_cfg = cfg.clone();
+        line_cfg.dry_run = cfg.dry_run || self.dry_run;
+        line_cfg.no_retry = cfg.no_retry || self.no_retry;
+        line_cfg.keyring = cfg.keyring || self.keyring;
+        if self.keyring && !cfg.keyring {
+            line_cfg.session_key = None;
+        }
+        if self.network.is_some() {
+            line_cfg.lotus.network = self.network;
+        }
+        if let Some(password) = self.password()? {
+            line_cfg.cli_password = Some(password);
+            line_cfg.session_key = None;
+        }
+        Ok(line_cfg)
+    }
+}
+
+/// repl 与 schedule daemon：启动时取得密码并派生一次加密密钥，会话中的命令复用该密钥
+async fn with_session_key(cfg: &Config) -> Result<Config> {
+    let password = cfg.get_password_or_prompt()?;
+    let key = tokio::task::spawn_blocking(move || crate::crypto::derive_key(&password)).await?;
+    let mut session_cfg = cfg.clone();
+    session_cfg.session_key = Some(key);
+    Ok(session_cfg)
+}
+
+async fn dispatch(cli: Cli, cfg: &Config, store: &Store) -> Result<()> {
+    let out = Formatter::new(cli.output);
     match cli.command {
-        Commands::Wallet(cmd) => wallet::run(cmd, &cfg, &store).await,
-        Commands::Send(cmd) => send::run(cmd, &cfg, &store).await,
-        Commands::Actor(cmd) => actor::run(cmd, &cfg, &store).await,
-        Commands::Withdraw(cmd) => withdraw::run(cmd, &cfg, &store).await,
-        Commands::MarketWithdraw(cmd) => market::run(cmd, &cfg, &store).await,
-        Commands::MpoolPush(cmd) => push::run(cmd, &cfg, &store).await,
+        Commands::Wallet(cmd) => wallet::run(cmd, cfg, store, &out).await,
+        Commands::Send(cmd) => send::run(cmd, cfg, store, &out).await,
+        Commands::Simulate(cmd) => simulate::run(cmd, cfg, store).await,
+        Commands::Actor(cmd) => actor::run(cmd, cfg, store, &out).await,
+        Commands::Withdraw(cmd) => withdraw::run(cmd, cfg, store, &out).await,
+        Commands::MarketWithdraw(cmd) => market::run(cmd, cfg, store, &out).await,
+        Commands::MpoolPush(cmd) => push::run(cmd, cfg, store, &out).await,
+        Commands::Mpool(cmd) => mpool::run(cmd, cfg, store, &out).await,
+        Commands::Miner(cmd) => miner::run(cmd, cfg, store, &out).await,
+        Commands::Datacap(cmd) => datacap::run(cmd, cfg, store, &out).await,
+        Commands::Gas(cmd) => gas::run(cmd, cfg, store).await,
+        Commands::Bench(cmd) => bench::run(cmd, cfg, store).await,
+        Commands::Chain(cmd) => chain::run(cmd, cfg, store).await,
+        Commands::History(cmd) => history::run(cmd, cfg, store, &out).await,
+        // main 在打开数据库之前处理，repl 中仍可使用
+        Commands::Config(cmd) => config::run(cmd, cfg).await,
+        Commands::Tx(cmd) => tx::run(cmd, cfg, store).await,
+        Commands::BatchSign(cmd) => batch::run_sign(cmd, cfg, store).await,
+        Commands::BatchPush(cmd) => batch::run_push(cmd, cfg, store).await,
+        Commands::MultiSend(cmd) => multi_send::run(cmd, cfg, store).await,
+        Commands::Multisig(cmd) => multisig::run(cmd, cfg, store, &out).await,
+        Commands::EthSign(cmd) => eth::run(cmd, cfg, store).await,
+        Commands::Paych(cmd) => paych::run(cmd, cfg, store).await,
+        Commands::Address(cmd) => address::run(cmd, cfg, store).await,
+        Commands::AddressBook(cmd) => address_book::run(cmd, cfg, store, &out).await,
+        Commands::Audit(cmd) => audit::run(cmd, cfg, store, &out).await,
+        Commands::Cbor(cmd) => cbor::run(cmd, cfg, store).await,
+        Commands::Cid(cmd) => cid::run(cmd, cfg, store).await,
+        Commands::Repl(cmd) => repl::run(cmd, cfg, store).await,
+        Commands::Daemon(cmd) => daemon::run(cmd, cfg, store).await,
+        Commands::Schedule(cmd) => schedule::run(cmd, cfg, store, &out).await,
+        Commands::Monitor(cmd) => monitor::run(cmd, cfg, store).await,
+        Commands::Cache(cmd) => cache::run(cmd, cfg, store).await,
+        // main 在打开数据库之前处理；数据库已打开时不能替换文件
+        Commands::Db(_) => anyhow::bail!("db commands cannot run while the database is open (not available in repl)"),
+        Commands::KdfBenchmark { target_ms } => bench::run_kdf(target_ms).await,
+        Commands::Completions { shell } => {
+            print_completions(shell);
+            Ok(())
+        }
+    }
+}
+
+/// 输出补全脚本到标准输出；不需要配置与数据库
+pub fn print_completions(shell: clap_complete::Shell) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+/// 集中处理命令结果的输出：table 由各命令自行渲染，json/csv 由记录的 Serialize 实现生成
+pub struct Formatter {
+    format: OutputFormat,
+}
+
+/// 已广播消息的机器可读结果
+#[derive(Serialize)]
+struct SentMessage {
+    cid: String,
+    from: String,
+    to: String,
+    value: String,
+    gas_limit: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas_used: Option<i64>,
+}
+
+impl Formatter {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn is_table(&self) -> bool {
+        self.format == OutputFormat::Table
+    }
+
+    pub fn is_csv(&self) -> bool {
+        self.format == OutputFormat::Csv
+    }
+
+    /// 输出记录列表：json 为对象数组，csv 以首条记录的字段名为表头
+    pub fn print_list<T: Serialize>(&self, records: &[T], table: impl FnOnce(&[T])) -> Result<()> {
+        match self.format {
+            OutputFormat::Table => table(records),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(records)?),
+            OutputFormat::Csv => {
+                let rows = records.iter()
+                    .map(serde_json::to_value)
+                    .collect::<Result<Vec<_>, _>>()?;
+                if let Some(serde_json::Value::Object(first)) = rows.first() {
+                    let headers: Vec<&String> = first.keys().collect();
+                    println!("{}", headers.iter().map(|h| csv_field(h)).collect::<Vec<_>>().join(","));
+                    for row in &rows {
+                        let fields: Vec<String> = headers.iter()
+                            .map(|h| match &row[h.as_str()] {
+                                serde_json::Value::Null => String::new(),
+                                serde_json::Value::String(s) => csv_field(s),
+                                v => csv_field(&v.to_string()),
+                            })
+                            .collect();
+                        println!("{}", fields.join(","));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 输出单条记录；csv 为一行表头加一行数据
+    pub fn print_record<T: Serialize>(&self, record: &T, table: impl FnOnce(&T)) -> Result<()> {
+        match self.format {
+            OutputFormat::Table => table(record),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(record)?),
+            OutputFormat::Csv => self.print_list(std::slice::from_ref(record), |_| {})?,
+        }
+        Ok(())
+    }
+
+    /// 输出已广播的消息；非 table 格式时从节点读取消息以补全 from/to/value/gas_limit
+    pub async fn print_sent(
+        &self,
+        api: &LotusApi,
+        cid: &Cid,
+        gas_used: Option<i64>,
+        table: impl FnOnce(),
+    ) -> Result<()> {
+        if self.is_table() {
+            table();
+            return Ok(());
+        }
+        let msg = api.chain_get_message(cid).await?;
+        let sent = SentMessage {
+            cid: cid.root.clone(),
+            from: msg.from.to_string(),
+            to: msg.to.to_string(),
+            value: msg.value.to_string(),
+            gas_limit: msg.gas_limit,
+            gas_used,
+        };
+        self.print_record(&sent, |_| {})
+    }
+}
+
+/// CSV 字段转义：包含逗号、引号或换行时加引号
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// 发送类命令共用的 `--wait` / `--confidence` 参数（普通注释：避免 flatten 后覆盖命令的 about）
+#[derive(Args, Debug, Clone)]
+pub struct WaitArgs {
+    /// 广播后通过 StateWaitMsg 等待消息上链并打印回执；退出码非零时命令失败
+    #[arg(long)]
+    pub wait: bool,
+    /// 上链后等待的确认纪元数
+    #[arg(long, default_value = "3", requires = "wait")]
+    pub confidence: u64,
+}
+
+// 签名前显示费用明细的 `--show-fees` / `--yes` 参数（普通注释：同 WaitArgs）
+#[derive(Args, Debug, Clone)]
+pub struct FeeArgs {
+    /// 签名前打印 gas 费用明细（gas limit、基础费用、最大 / 预期 / 优先费用）并询问是否继续
+    #[arg(long, global = true)]
+    pub show_fees: bool,
+    /// 与 --show-fees 一起使用时不询问，直接签名
+    #[arg(long, short = 'y', global = true)]
+    pub yes: bool,
+}
+
+impl FeeArgs {
+    pub(crate) fn apply<'a>(&self, executor: crate::service::Executor<'a>) -> crate::service::Executor<'a> {
+        executor.with_fee_preview(self.show_fees, self.yes)
+    }
+}
+
+/// 输出已广播的消息；指定 --wait 时等待回执，消息执行失败时返回错误以便脚本检测
+pub(crate) async fn report_sent(
+    cfg: &Config,
+    api: &LotusApi,
+    cid: &Cid,
+    wait: &WaitArgs,
+    out: &Formatter,
+    table: impl FnOnce(),
+) -> Result<()> {
+    if !wait.wait {
+        return out.print_sent(api, cid, None, table).await;
+    }
+
+    if out.is_table() {
+        table();
+        println!("Waiting for {} confirmations...", wait.confidence);
+    }
+    let lookup = api.state_wait_msg(cid, wait.confidence).await?;
+    notify_confirmed(cfg, api, cid, &lookup).await;
+    out.print_sent(api, cid, Some(lookup.receipt.gas_used), || {
+        println!("Included at epoch {}", lookup.height);
+        println!("Exit code: {}", lookup.receipt.exit_code);
+        println!("Gas used: {}", lookup.receipt.gas_used);
+    }).await?;
+    check_exit_code(cid, lookup.receipt.exit_code)
+}
+
+/// 配置了 notifications.webhook_url 时发送上链通知（退出码非零同样通知）；失败只打印警告
+pub(crate) async fn notify_confirmed(cfg: &Config, api: &LotusApi, cid: &Cid, lookup: &MsgLookup) {
+    let Some(notifications) = &cfg.notifications else { return };
+    let Some(url) = &notifications.webhook_url else { return };
+    let result = async {
+        let msg = api.chain_get_message(cid).await?;
+        let payload = crate::notify::WebhookPayload {
+            cid: cid.root.clone(),
+            exit_code: lookup.receipt.exit_code,
+            gas_used: lookup.receipt.gas_used,
+            height: lookup.height,
+            from: msg.from.to_string(),
+            to: msg.to.to_string(),
+            value: msg.value.to_string(),
+        };
+        crate::notify::send_webhook(url, &payload, notifications.webhook_secret.as_deref()).await
+    }.await;
+    if let Err(e) = result {
+        tracing::warn!(cid = %cid.root, error = %format!("{:#}", e), "webhook notification failed");
+        eprintln!("WARNING: webhook notification failed: {:#}", e);
+    }
+}
+
+/// 消息已上链但执行失败时返回错误
+pub(crate) fn check_exit_code(cid: &Cid, exit_code: i64) -> Result<()> {
+    if exit_code != 0 {
+        anyhow::bail!("message {} failed with exit code {}", cid.root, exit_code);
+    }
+    Ok(())
+}
+
+/// 按标签查找本地钱包地址，未找到时给出明确提示
+pub(crate) fn resolve_label(store: &Store, label: &str) -> Result<String> {
+    store
+        .get_key_by_label(label)?
+        .map(|k| k.address)
+        .ok_or_else(|| anyhow::anyhow!(
+            "No address found with label '{}'; use 'wallet list' to see available labels",
+            label
+        ))
+}
+
+/// 输入是否具有地址形式（网络前缀 f/t 加协议号 0-4），如 f01234、f1abc…
+pub(crate) fn looks_like_address(input: &str) -> bool {
+    let mut chars = input.chars();
+    matches!(chars.next(), Some('f' | 't')) && matches!(chars.next(), Some('0'..='4'))
+}
+
+/// 按配置的网络前缀显示地址；无法解析的输入原样返回
+pub(crate) fn display_address(cfg: &Config, address: &str) -> String {
+    crate::chain::Address::from_string(address)
+        .map(|a| a.to_string_with_network(cfg.network()))
+        .unwrap_or_else(|_| address.to_string())
+}
+
+/// 解析原始地址、地址簿名称或钱包标签；具有地址形式的输入原样返回，由后续解析校验
+pub(crate) fn resolve_address_or_name(store: &Store, input: &str) -> Result<String> {
+    if looks_like_address(input) {
+        return Ok(input.to_string());
+    }
+    if let Some(address) = store.lookup_address_by_name(input)? {
+        return Ok(address);
+    }
+    store.get_key_by_label(input)?.map(|k| k.address).ok_or_else(|| anyhow::anyhow!(
+        "'{}' is not a valid address, address book name or wallet label; use 'address-book list' or 'wallet list' to see saved names",
+        input
+    ))
+}
+
+/// 解析钱包地址参数：具有地址形式的输入原样返回，否则按钱包标签查找
+pub(crate) fn resolve_key_address(store: &Store, input: &str) -> Result<String> {
+    if looks_like_address(input) {
+        return Ok(input.to_string());
+    }
+    resolve_label(store, input)
+}
+
+/// 解析 `--xxx <地址>` / `--xxx-label <标签>` 成对参数，二者必须提供其一
+pub(crate) fn resolve_address_arg(
+    store: &Store,
+    address: Option<String>,
+    label: Option<String>,
+    flag: &str,
+) -> Result<String> {
+    match (address, label) {
+        (Some(addr), None) => resolve_key_address(store, &addr),
+        (None, Some(label)) => resolve_label(store, &label),
+        (Some(_), Some(_)) => anyhow::bail!("--{0} and --{0}-label cannot be used together", flag),
+        (None, None) => anyhow::bail!("either --{0} or --{0}-label is required", flag),
+    }
+}
+
+/// 数据库 config 表中保存默认签名地址的键
+pub(crate) const DEFAULT_ADDRESS_KEY: &str = "default_address";
+
+/// 解析 `--from` / `--from-label`，均未提供时回退到 `wallet set-default` 设置的默认地址
+pub(crate) fn resolve_from_arg(
+    store: &Store,
+    from: Option<String>,
+    from_label: Option<String>,
+) -> Result<String> {
+    if from.is_none() && from_label.is_none() {
+        return store.get_config(DEFAULT_ADDRESS_KEY)?.ok_or_else(|| anyhow::anyhow!(
+            "No --from address specified and no default address configured; run 'wallet set-default <addr>'"
+        ));
+    }
+    resolve_address_arg(store, from, from_label, "from")
+}
+
+/// 在终端询问是否继续，仅输入 y/yes 时返回 true
+pub(crate) fn confirm(prompt: &str) -> Result<bool> {
+    use std::io::Write;
+    print!("{} [y/N] ", prompt);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// 解析起始时间：RFC3339 时间，或相对当前时间往前的时长
+pub(crate) fn parse_since(s: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(t) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(t.with_timezone(&chrono::Utc));
     }
+    let duration = parse_duration(s)
+        .map_err(|_| anyhow::anyhow!("invalid --since: {} (expected RFC3339 or e.g. \"7d\")", s))?;
+    chrono::Utc::now()
+        .checked_sub_signed(duration)
+        .ok_or_else(|| anyhow::anyhow!("--since is too far in the past: {}", s))
+}
+
+/// 解析相对时长，如 "30s"、"15m"、"12h"、"30d"、"2w"
+pub(crate) fn parse_duration(s: &str) -> Result<chrono::Duration> {
+    use chrono::Duration;
+    let s = s.trim();
+    let (num, unit) = s.split_at(s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len());
+    let n: i64 = num.parse()
+        .map_err(|_| anyhow::anyhow!("invalid duration: {}", s))?;
+    let duration = match unit {
+        "s" => Duration::try_seconds(n),
+        "m" => Duration::try_minutes(n),
+        "h" => Duration::try_hours(n),
+        "d" => Duration::try_days(n),
+        "w" => Duration::try_weeks(n),
+        _ => anyhow::bail!("invalid duration unit: {} (expected s/m/h/d/w)", unit),
+    };
+    duration.ok_or_else(|| anyhow::anyhow!("duration out of range: {}", s))
 }
@@ -0,0 +1,101 @@
+//! 余额监控：定期查询钱包地址余额，低于 `wallet set-threshold` 设置的阈值时发送 webhook 告警
+//!
+//! 每个地址低于阈值后只告警一次（记录在 balance_thresholds.below_since），余额恢复后重新计算
+
+use crate::chain::format_fil;
+use crate::config::Config;
+use crate::db::{BalanceThreshold, Store};
+use crate::notify::{self, BalanceAlertPayload};
+use crate::rpc::LotusApi;
+use anyhow::Result;
+use chrono::Utc;
+use clap::Args;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 余额监控命令参数
+#[derive(Args)]
+pub struct MonitorCmd {
+    /// 检查间隔（秒）
+    #[arg(long, default_value = "300")]
+    pub interval: u64,
+}
+
+pub async fn run(cmd: MonitorCmd, cfg: &Config, store: &Store) -> Result<()> {
+    if cmd.interval == 0 {
+        anyhow::bail!("--interval must be greater than 0");
+    }
+    let api = LotusApi::from_config(cfg);
+    let thresholds = store.list_balance_thresholds()?.len();
+    println!("Monitoring balances every {}s ({} thresholds); Ctrl-C to stop", cmd.interval, thresholds);
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(cmd.interval));
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+        // 节点或数据库暂时不可用时等待下一轮，不退出
+        if let Err(e) = check_balances(cfg, store, &api).await {
+            eprintln!("Error: {:#}", e);
+        }
+    }
+    println!("Monitor stopped");
+    Ok(())
+}
+
+/// 每轮重新读取阈值，monitor 运行期间的 set-threshold / remove-threshold 在下一轮生效
+async fn check_balances(cfg: &Config, store: &Store, api: &LotusApi) -> Result<()> {
+    let thresholds: HashMap<String, BalanceThreshold> = store.list_balance_thresholds()?
+        .into_iter()
+        .map(|t| (t.address.clone(), t))
+        .collect();
+
+    for key in store.list_keys_async().await? {
+        let balance = match api.wallet_balance(&key.address).await {
+            Ok(balance) => balance.0,
+            Err(e) => {
+                eprintln!("WARNING: cannot fetch balance of {}: {:#}", key.address, e);
+                continue;
+            }
+        };
+        tracing::info!(address = %key.address, balance = %balance, "balance checked");
+
+        let Some(threshold) = thresholds.get(&key.address) else { continue };
+        let limit: num_bigint::BigInt = threshold.threshold_attofil.parse()
+            .map_err(|_| anyhow::anyhow!("invalid threshold for {}: {}", key.address, threshold.threshold_attofil))?;
+        if balance >= limit {
+            if threshold.below_since.is_some() {
+                println!("{} recovered: {} (threshold {})", key.address, format_fil(&balance), format_fil(&limit));
+                store.set_threshold_below_since(&key.address, None)?;
+            }
+            continue;
+        }
+        if threshold.below_since.is_some() {
+            continue;
+        }
+
+        let now = Utc::now();
+        eprintln!("WARNING: {} balance {} is below threshold {}", key.address, format_fil(&balance), format_fil(&limit));
+        let notifications = cfg.notifications.as_ref();
+        let url = threshold.alert_webhook_url.as_deref()
+            .or_else(|| notifications.and_then(|n| n.webhook_url.as_deref()));
+        if let Some(url) = url {
+            let payload = BalanceAlertPayload {
+                event: "balance_below_threshold",
+                address: key.address.clone(),
+                balance: balance.to_string(),
+                threshold: limit.to_string(),
+                timestamp: now,
+            };
+            let secret = notifications.and_then(|n| n.webhook_secret.as_deref());
+            // 发送失败时不记录告警状态，下一轮重试
+            if let Err(e) = notify::send_webhook(url, &payload, secret).await {
+                eprintln!("WARNING: balance alert for {} failed: {:#}", key.address, e);
+                continue;
+            }
+        }
+        store.set_threshold_below_since(&key.address, Some(now))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! 消息池命令：替换卡住的待处理消息（replace-by-fee）
+
+use crate::chain::BigInt;
+use crate::config::Config;
+use crate::db::Store;
+use crate::service::Executor;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use num_bigint::BigInt as NumBigInt;
+
+/// Lotus 消息池要求替换消息的费用至少为原消息的 1.25 倍（以百分比表示）
+const REPLACE_BY_FEE_PERCENT: u32 = 125;
+
+/// 消息池命令参数
+#[derive(Args)]
+pub struct MpoolCmd {
+    #[command(subcommand)]
+    pub command: MpoolSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum MpoolSubCmd {
+    /// 以更高的 gas 费用重新签名并广播待处理消息（nonce 不变）
+    Replace {
+        #[arg(long)]
+        cid: String,
+        /// 新的 Gas 费用上限（attoFIL）
+        #[arg(long)]
+        gas_feecap: String,
+        /// 新的 Gas 优先费（attoFIL）
+        #[arg(long)]
+        gas_premium: String,
+        /// 新的 Gas 限制（默认：沿用原消息）
+        #[arg(long)]
+        gas_limit: Option<i64>,
+    },
+}
+
+pub async fn run(cmd: MpoolCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    match cmd.command {
+        MpoolSubCmd::Replace { cid, gas_feecap, gas_premium, gas_limit } => {
+            let executor = Executor::new_async(cfg, store).await?;
+            let mut msg = executor.api.mpool_pending_by_cid(&cid).await?
+                .ok_or_else(|| anyhow::anyhow!("message {} not found in mpool", cid))?;
+
+            let fee_cap = parse_attofil(&gas_feecap, "--gas-feecap")?;
+            let premium = parse_attofil(&gas_premium, "--gas-premium")?;
+            // 消息池按 gas premium 判断是否允许替换，fee cap 也需同比提高才能覆盖新的 premium
+            check_replace_ratio("gas fee cap", &msg.gas_fee_cap, &fee_cap)?;
+            check_replace_ratio("gas premium", &msg.gas_premium, &premium)?;
+
+            println!(
+                "Replacing {} (nonce {}): fee cap {} -> {}, premium {} -> {}",
+                cid, msg.nonce, msg.gas_fee_cap, fee_cap, msg.gas_premium, premium
+            );
+            msg.gas_fee_cap = fee_cap;
+            msg.gas_premium = premium;
+            if let Some(limit) = gas_limit {
+                msg.gas_limit = limit;
+            }
+
+            let Some(new_cid) = executor.replace_message(msg).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &new_cid, None, || println!("Message CID: {}", new_cid.root)).await?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_attofil(s: &str, flag: &str) -> Result<BigInt> {
+    s.parse::<NumBigInt>()
+        .map(BigInt)
+        .map_err(|_| anyhow::anyhow!("invalid {}: {} (expected attoFIL)", flag, s))
+}
+
+/// new 必须 >= old * 1.25
+fn check_replace_ratio(field: &str, old: &BigInt, new: &BigInt) -> Result<()> {
+    let min = &old.0 * REPLACE_BY_FEE_PERCENT / 100u32;
+    if new.0 < min {
+        anyhow::bail!(
+            "new {} {} is below the minimum replacement value {} (1.25x of {})",
+            field, new, min, old
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! 多目标转账：从同一地址向多个接收方分别发送不同金额（每个接收方一条消息）
+
+use crate::chain::{format_fil, parse_fil, BigInt};
+use crate::config::Config;
+use crate::db::Store;
+use crate::service::Executor;
+use anyhow::Result;
+use clap::{Args, ValueHint};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 多目标转账命令参数
+#[derive(Args)]
+pub struct MultiSendCmd {
+    /// 发送地址（省略时使用默认地址）
+    #[arg(long, value_hint = ValueHint::Other)]
+    pub from: Option<String>,
+    #[arg(long, conflicts_with = "from")]
+    pub from_label: Option<String>,
+    /// 接收方列表：JSON 数组 [{"to": "f1...", "amount": "1.5"}]，或 .csv 文件（to,amount 两列，表头可选）
+    #[arg(long, value_hint = ValueHint::FilePath)]
+    pub recipients: PathBuf,
+    /// 同时广播的消息数
+    #[arg(long, default_value = "4")]
+    pub concurrency: usize,
+    /// 将每条消息的结果（to,amount,cid,status,error）写入 CSV 文件
+    #[arg(long, value_hint = ValueHint::FilePath)]
+    pub out_csv: Option<PathBuf>,
+}
+
+/// 接收方；amount 单位为 FIL
+#[derive(Deserialize)]
+struct Recipient {
+    to: String,
+    amount: String,
+}
+
+pub async fn run(cmd: MultiSendCmd, cfg: &Config, store: &Store) -> Result<()> {
+    let from = super::resolve_from_arg(store, cmd.from, cmd.from_label)?;
+    let recipients = read_recipients(&cmd.recipients)?;
+    if recipients.is_empty() {
+        anyhow::bail!("no recipients in {}", cmd.recipients.display());
+    }
+
+    let mut transfers = Vec::with_capacity(recipients.len());
+    for (i, r) in recipients.iter().enumerate() {
+        let to = super::resolve_address_or_name(store, &r.to)?;
+        let amount = parse_fil(&r.amount).map_err(|e| anyhow::anyhow!("recipient {} ({}): {}", i + 1, r.to, e))?;
+        transfers.push((to, BigInt(amount)));
+    }
+    let total: num_bigint::BigInt = transfers.iter().map(|(_, amount)| &amount.0).sum();
+    println!("Sending {} to {} recipients from {}", format_fil(&total), transfers.len(), from);
+
+    let executor = Executor::new_async(cfg, store).await?;
+    let Some(results) = executor.multi_send(&from, &transfers, cmd.concurrency, |i, result| {
+        let (to, amount) = &transfers[i];
+        match result {
+            Ok(cid) => println!("[{}] {} {}: {}", i + 1, to, format_fil(&amount.0), cid.root),
+            Err(e) => eprintln!("[{}] {} {}: failed: {:#}", i + 1, to, format_fil(&amount.0), e),
+        }
+    }).await? else {
+        return Ok(());
+    };
+
+    if let Some(path) = &cmd.out_csv {
+        write_results_csv(path, &transfers, &results)?;
+    }
+
+    let failed = results.iter().filter(|r| r.is_err()).count();
+    if failed > 0 {
+        // 失败消息占用的 nonce 空缺会阻塞后续 nonce 的消息上链
+        eprintln!("WARNING: messages with nonces after a failed push will not be included until the gap is filled");
+        anyhow::bail!("{} of {} messages failed to push", failed, results.len());
+    }
+    println!("Pushed {} messages", results.len());
+    Ok(())
+}
+
+/// 按扩展名读取 .csv，其余按 JSON 数组解析
+fn read_recipients(path: &Path) -> Result<Vec<Recipient>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("cannot read {}: {}", path.display(), e))?;
+    let is_csv = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    if !is_csv {
+        return serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("invalid recipients JSON in {}: {}", path.display(), e));
+    }
+
+    let mut recipients = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if i == 0 && fields.first().is_some_and(|f| f.eq_ignore_ascii_case("to")) {
+            continue;
+        }
+        match fields.as_slice() {
+            [to, amount] => recipients.push(Recipient { to: to.to_string(), amount: amount.to_string() }),
+            _ => anyhow::bail!("{} line {}: expected 'to,amount'", path.display(), i + 1),
+        }
+    }
+    Ok(recipients)
+}
+
+fn write_results_csv(path: &Path, transfers: &[(String, BigInt)], results: &[Result<crate::rpc::Cid>]) -> Result<()> {
+    let mut csv = String::from("to,amount,cid,status,error\n");
+    for ((to, amount), result) in transfers.iter().zip(results) {
+        let (cid, status, error) = match result {
+            Ok(cid) => (cid.root.clone(), "pushed", String::new()),
+            Err(e) => (String::new(), "failed", format!("{:#}", e)),
+        };
+        let fields = [to.clone(), format_fil(&amount.0), cid, status.to_string(), error];
+        csv.push_str(&fields.iter().map(|f| super::csv_field(f)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    fs::write(path, csv).map_err(|e| anyhow::anyhow!("cannot write {}: {}", path.display(), e))
+}
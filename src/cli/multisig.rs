@@ -0,0 +1,169 @@
+//! 多签钱包命令：创建、提案、批准、取消
+
+use crate::chain::actors::multisig::Transaction;
+use crate::chain::format_fil;
+use crate::config::Config;
+use crate::db::Store;
+use crate::rpc::LotusApi;
+use crate::service::{multisig_proposal_hash, Executor};
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueHint};
+
+/// 多签命令参数
+#[derive(Args)]
+pub struct MultisigCmd {
+    #[command(subcommand)]
+    pub command: MultisigSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum MultisigSubCmd {
+    /// 创建多签钱包
+    Create {
+        /// 逗号分隔的签名人地址
+        #[arg(long, value_delimiter = ',', required = true)]
+        signers: Vec<String>,
+        /// 执行交易所需的批准数（默认：全部签名人）
+        #[arg(long)]
+        threshold: Option<u64>,
+        /// 锁仓释放时长（纪元数，0 表示不锁仓）
+        #[arg(long, default_value = "0")]
+        unlock_duration: i64,
+        /// 锁仓开始纪元
+        #[arg(long, default_value = "0")]
+        start_epoch: i64,
+        /// 创建时转入的金额（attoFIL）
+        #[arg(long, default_value = "0")]
+        value: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+    },
+    /// 发起提案
+    Propose {
+        msig: String,
+        to: String,
+        /// 金额（attoFIL）
+        value: String,
+        #[arg(default_value = "0")]
+        method: u64,
+        /// 十六进制编码的 CBOR 参数
+        #[arg(default_value = "")]
+        params: String,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+    },
+    /// 批准提案
+    Approve {
+        msig: String,
+        txid: i64,
+        /// 提案人地址；与 --to 等一起提供时计算提案哈希，防止批准被替换的提案
+        proposer: Option<String>,
+        #[command(flatten)]
+        proposal: ProposalArgs,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+    },
+    /// 列出待处理的提案
+    Pending {
+        msig: String,
+        /// 查询指定高度的状态（默认：链头）
+        #[arg(long)]
+        epoch: Option<i64>,
+    },
+    /// 取消自己发起的提案
+    Cancel {
+        msig: String,
+        txid: i64,
+        #[command(flatten)]
+        proposal: ProposalArgs,
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+    },
+}
+
+/// 提案内容，用于计算提案哈希（全部省略时不校验）
+#[derive(Args)]
+pub struct ProposalArgs {
+    #[arg(long, value_hint = ValueHint::Other)]
+    to: Option<String>,
+    /// 金额（attoFIL）
+    #[arg(long, default_value = "0")]
+    value: String,
+    #[arg(long, default_value = "0")]
+    method: u64,
+    /// 十六进制编码的 CBOR 参数
+    #[arg(long, default_value = "")]
+    params: String,
+}
+
+impl ProposalArgs {
+    fn hash(&self, proposer: &str) -> Result<Vec<u8>> {
+        match &self.to {
+            Some(to) => multisig_proposal_hash(proposer, to, &self.value, self.method, decode_params(&self.params)?),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+pub async fn run(cmd: MultisigCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    match cmd.command {
+        MultisigSubCmd::Create { signers, threshold, unlock_duration, start_epoch, value, from } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            let threshold = threshold.unwrap_or(signers.len() as u64);
+            let executor = Executor::new_async(cfg, store).await?;
+            let Some(cid) = executor.multisig_create(&signers, threshold, unlock_duration, start_epoch, &value, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+        MultisigSubCmd::Propose { msig, to, value, method, params, from } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            let executor = Executor::new_async(cfg, store).await?;
+            let Some(cid) = executor.multisig_propose(&msig, &to, &value, method, decode_params(&params)?, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+        MultisigSubCmd::Approve { msig, txid, proposer, proposal, from } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            let hash = match &proposer {
+                Some(p) => proposal.hash(p)?,
+                None if proposal.to.is_some() => anyhow::bail!("PROPOSER is required to compute the proposal hash"),
+                None => vec![],
+            };
+            let executor = Executor::new_async(cfg, store).await?;
+            let Some(cid) = executor.multisig_approve(&msig, txid, hash, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+        MultisigSubCmd::Pending { msig, epoch } => {
+            let api = LotusApi::from_config(cfg);
+            let state = api.state_read_state(&msig, epoch).await?;
+            let root = state["State"]["PendingTxns"]["/"].as_str()
+                .ok_or_else(|| anyhow::anyhow!("{} is not a multisig actor (no PendingTxns)", msig))?;
+
+            let mut txns = api.hamt_entries(root).await?
+                .iter()
+                .map(|(k, v)| Transaction::from_hamt_entry(k, v))
+                .collect::<Result<Vec<_>>>()?;
+            txns.sort_by_key(|t| t.id);
+
+            println!("{:<8} {:<44} {:<24} {:<8} Approved", "TxnID", "To", "Value", "Method");
+            println!("{}", "-".repeat(116));
+            for t in txns {
+                let approved: Vec<String> = t.approved.iter().map(|a| a.to_string()).collect();
+                println!(
+                    "{:<8} {:<44} {:<24} {:<8} {}",
+                    t.id, t.to.to_string(), format_fil(&t.value.0), t.method, approved.join(",")
+                );
+            }
+        }
+        MultisigSubCmd::Cancel { msig, txid, proposal, from } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            let hash = proposal.hash(&from)?;
+            let executor = Executor::new_async(cfg, store).await?;
+            let Some(cid) = executor.multisig_cancel(&msig, txid, hash, &from).await? else { return Ok(()) };
+            out.print_sent(&executor.api, &cid, None, || println!("Message CID: {}", cid.root)).await?;
+        }
+    }
+    Ok(())
+}
+
+fn decode_params(params: &str) -> Result<Vec<u8>> {
+    Ok(hex::decode(params.trim_start_matches("0x"))?)
+}
@@ -0,0 +1,22 @@
+//! 终端密码输入
+//!
+//! rpassword 在 Unix 上直接读取 /dev/tty（Windows 上为控制台）而不是标准输入，
+//! 管道输入不会被误当作密码；没有终端时返回错误而不是空密码
+
+use anyhow::Result;
+
+/// 在终端中输入密码（不回显）
+pub fn prompt_password(prompt: &str) -> Result<String> {
+    rpassword::prompt_password(prompt)
+        .map_err(|e| anyhow::anyhow!("cannot prompt for password, no terminal available ({}); set wallet.password or LOTUS_SIGN_WALLET_PASSWORD", e))
+}
+
+/// 输入两次密码，不一致时报错；用于设置新密码，避免输错后无法解密
+pub fn prompt_password_confirm(prompt: &str) -> Result<String> {
+    let password = prompt_password(prompt)?;
+    let confirm = prompt_password("Confirm password: ")?;
+    if password != confirm {
+        anyhow::bail!("passwords do not match");
+    }
+    Ok(password)
+}
@@ -0,0 +1,100 @@
+//! 支付通道命令：凭证创建与验证
+
+use crate::chain::actors::paych::SignedVoucher;
+use crate::chain::{format_fil, Address, BigInt};
+use crate::config::Config;
+use crate::db::Store;
+use crate::rpc::LotusApi;
+use crate::wallet::{verify_signature, Wallet};
+use anyhow::Result;
+use base64::Engine;
+use clap::{Args, Subcommand, ValueHint};
+
+/// 支付通道命令参数
+#[derive(Args)]
+pub struct PaychCmd {
+    #[command(subcommand)]
+    pub command: PaychSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum PaychSubCmd {
+    /// 创建并签名凭证，输出 base64 编码的 SignedVoucher
+    VoucherCreate {
+        #[arg(long)]
+        paych: String,
+        /// 凭证金额（attoFIL，lane 上的累计值）
+        #[arg(long)]
+        amount: String,
+        #[arg(long, default_value = "0")]
+        lane: u64,
+        #[arg(long)]
+        nonce: u64,
+        /// 通道付款方地址
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+    },
+    /// 验证凭证签名
+    VoucherVerify {
+        voucher: String,
+        #[arg(long)]
+        paych: String,
+        /// 预期签名地址（默认：从链上读取通道的 From 并解析为公钥地址）
+        #[arg(long)]
+        signer: Option<String>,
+    },
+}
+
+pub async fn run(cmd: PaychCmd, cfg: &Config, store: &Store) -> Result<()> {
+    match cmd.command {
+        PaychSubCmd::VoucherCreate { paych, amount, lane, nonce, from } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            let mut voucher = SignedVoucher::new(
+                Address::from_string(&paych)?,
+                lane,
+                nonce,
+                BigInt(amount.parse().map_err(|_| anyhow::anyhow!("invalid amount: {}", amount))?),
+            );
+
+            let wallet = Wallet::with_enc_key(store, cfg.enc_key()?);
+            voucher.signature = Some(wallet.sign_raw(&voucher.signing_bytes(), &from)?);
+
+            println!("{}", base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(voucher.to_bytes()));
+        }
+        PaychSubCmd::VoucherVerify { voucher, paych, signer } => {
+            let voucher = SignedVoucher::from_bytes(&decode_voucher(&voucher)?)?;
+            if voucher.channel_addr != Address::from_string(&paych)? {
+                anyhow::bail!("voucher is for channel {}, not {}", voucher.channel_addr, paych);
+            }
+            let sig = voucher.signature.as_ref()
+                .ok_or_else(|| anyhow::anyhow!("voucher is not signed"))?;
+
+            let signer = match signer {
+                Some(s) => s,
+                None => {
+                    let api = LotusApi::from_config(cfg);
+                    let state = api.state_read_state(&paych, None).await?;
+                    let from = state["State"]["From"].as_str()
+                        .ok_or_else(|| anyhow::anyhow!("{} is not a payment channel actor", paych))?;
+                    api.state_account_key(from).await?
+                }
+            };
+
+            if !verify_signature(&Address::from_string(&signer)?, &voucher.signing_bytes(), sig)? {
+                anyhow::bail!("voucher signature is invalid");
+            }
+            println!("valid: lane {} nonce {} amount {}, signed by {}",
+                voucher.lane, voucher.nonce, format_fil(&voucher.amount.0), signer);
+        }
+    }
+    Ok(())
+}
+
+/// Lotus 使用无填充的 URL 安全 base64 编码凭证，同时兼容标准 base64
+fn decode_voucher(s: &str) -> Result<Vec<u8>> {
+    use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+    let s = s.trim();
+    URL_SAFE_NO_PAD.decode(s)
+        .or_else(|_| STANDARD.decode(s))
+        .map_err(|e| anyhow::anyhow!("invalid voucher encoding: {}", e))
+}
@@ -8,12 +8,73 @@ use clap::Args;
 #[derive(Args)]
 pub struct PushCmd {
     pub signed_message: String,
+    /// 存在校验警告时不提示确认，直接推送
+    #[arg(long, short)]
+    pub yes: bool,
+    /// 存在任何校验警告时拒绝推送并以非零状态退出
+    #[arg(long)]
+    pub fail_on_warning: bool,
 }
 
-pub async fn run(cmd: PushCmd, cfg: &Config, _store: &Store) -> Result<()> {
-    let api = LotusApi::new(&cfg.lotus.host, cfg.lotus.token.clone());
-    let msg: SignedMessage = serde_json::from_str(&cmd.signed_message)?;
+pub async fn run(cmd: PushCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    let api = LotusApi::from_config(cfg);
+    let msg: SignedMessage = serde_json::from_str(&cmd.signed_message)
+        .map_err(|e| anyhow::anyhow!("invalid signed message JSON: {}", e))?;
+
+    let warnings = validate(&api, store, &msg).await?;
+    if !warnings.is_empty() {
+        println!("Validation warnings:");
+        for w in &warnings {
+            println!("  - {}", w);
+        }
+        if cmd.fail_on_warning {
+            anyhow::bail!("{} validation warning(s); not pushing", warnings.len());
+        }
+        if !cmd.yes && !cfg.dry_run && !super::confirm("Push anyway?")? {
+            anyhow::bail!("aborted");
+        }
+    }
+
+    if cfg.dry_run {
+        println!("{}", serde_json::to_string_pretty(&msg)?);
+        println!("Dry run: message was not pushed");
+        return Ok(());
+    }
+
     let cid = api.mpool_push(&msg).await?;
-    println!("Message CID: {}", cid.root);
+    out.print_sent(&api, &cid, None, || println!("Pushed: {}", cid.root)).await?;
     Ok(())
 }
+
+/// 推送前的本地校验：nonce、余额，以及钱包内地址的签名
+async fn validate(api: &LotusApi, store: &Store, signed: &SignedMessage) -> Result<Vec<String>> {
+    let msg = &signed.message;
+    let from = msg.from.to_string();
+    let mut warnings = Vec::new();
+
+    let current_nonce = api.mpool_get_nonce(&from).await?;
+    if msg.nonce < current_nonce {
+        warnings.push(format!("nonce {} is below the current nonce {} and will be rejected", msg.nonce, current_nonce));
+    } else if msg.nonce > current_nonce {
+        warnings.push(format!(
+            "nonce gap: message nonce {} but next nonce is {}; it will wait in the mempool",
+            msg.nonce, current_nonce
+        ));
+    }
+
+    let balance = api.wallet_balance(&from).await?;
+    let required = &msg.value.0 + &msg.gas_fee_cap.0 * msg.gas_limit;
+    if balance.0 < required {
+        warnings.push(format!("balance {} attoFIL does not cover value + gas_limit * gas_fee_cap = {} attoFIL", balance, required));
+    }
+
+    if store.has_key(&from)? {
+        match signed.verify() {
+            Ok(true) => {}
+            Ok(false) => warnings.push(format!("signature does not match {}", from)),
+            Err(e) => warnings.push(format!("signature could not be verified: {}", e)),
+        }
+    }
+
+    Ok(warnings)
+}
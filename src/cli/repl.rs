@@ -0,0 +1,84 @@
+//! 交互式会话：配置、数据库与钱包密码只加载一次，逐行执行子命令
+
+use super::Cli;
+use crate::config::Config;
+use crate::db::Store;
+use anyhow::Result;
+use clap::{Args, CommandFactory, Parser};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+/// 交互式会话：密码只输入一次，逐行执行子命令
+#[derive(Args)]
+pub struct ReplCmd {}
+
+pub async fn run(_cmd: ReplCmd, cfg: &Config, store: &Store) -> Result<()> {
+    // 未配置密码时在此询问一次，之后的命令复用会话中派生的密钥
+    let cfg = &super::with_session_key(cfg).await?;
+
+    // 历史只保存在内存中，避免私钥等参数写入磁盘
+    let mut editor = DefaultEditor::new()?;
+    println!("lotus-sign interactive session. Type `help` for commands, `exit` or Ctrl-D to quit.");
+
+    loop {
+        let line = match editor.readline("lotus-sign> ") {
+            Ok(line) => line,
+            // Ctrl-C 只取消当前输入
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+
+        match line {
+            "exit" | "quit" => break,
+            "help" => {
+                Cli::command().print_help()?;
+                println!("\nREPL commands:\n  help      Show this help\n  history   Show commands entered in this session\n  exit      End the session (or Ctrl-D)");
+                continue;
+            }
+            "history" => {
+                for (i, entry) in editor.history().iter().enumerate() {
+                    println!("{:>4}  {}", i + 1, entry);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some(words) = shlex::split(line) else {
+            eprintln!("Error: unbalanced quotes");
+            continue;
+        };
+        let cli = match Cli::try_parse_from(std::iter::once("lotus-sign".to_string()).chain(words)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                let _ = e.print();
+                continue;
+            }
+        };
+        if matches!(cli.command, super::Commands::Repl(_)) {
+            eprintln!("Error: already in an interactive session");
+            continue;
+        }
+        if cli.profile.is_some() {
+            eprintln!("WARNING: --profile is ignored inside the REPL; restart with `lotus-sign --profile <name> repl`");
+        }
+
+        let line_cfg = match cli.overlay_config(cfg) {
+            Ok(line_cfg) => line_cfg,
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                continue;
+            }
+        };
+        if let Err(e) = Box::pin(super::dispatch(cli, &line_cfg, store)).await {
+            eprintln!("Error: {:#}", e);
+        }
+    }
+    Ok(())
+}
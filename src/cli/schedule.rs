@@ -0,0 +1,211 @@
+//! 定时任务：按 cron 表达式定期执行 lotus-sign 命令（如每日提现、定期归集）
+//!
+//! 任务保存在数据库中，由 `schedule daemon` 常驻进程执行；时间均为 UTC
+
+use super::{Cli, Commands};
+use crate::config::Config;
+use crate::db::{ScheduledJob, Store};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clap::{Args, Parser, Subcommand};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// daemon 重新读取任务列表的最长间隔，新增或停用的任务在此时间内生效
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 定时任务命令参数
+#[derive(Args)]
+pub struct ScheduleCmd {
+    #[command(subcommand)]
+    pub command: ScheduleSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum ScheduleSubCmd {
+    /// 添加任务，如 --cron "0 2 * * *" --command "withdraw --miner f01234 --amount 10 --from f1..."
+    Add {
+        /// cron 表达式（UTC）：5 个字段（分 时 日 月 周）或带秒的 6-7 个字段；星期建议写作 MON-FRI
+        #[arg(long)]
+        cron: String,
+        /// 要执行的命令（不含 lotus-sign），按 shell 规则拆分参数
+        #[arg(long, allow_hyphen_values = true)]
+        command: String,
+    },
+    /// 列出任务
+    List,
+    /// 停用任务
+    Disable { id: i64 },
+    /// 重新启用任务，从当前时间起计算下次执行
+    Enable { id: i64 },
+    /// 删除任务
+    Remove { id: i64 },
+    /// 常驻执行到期的任务，Ctrl-C 退出
+    Daemon,
+}
+
+pub async fn run(cmd: ScheduleCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    match cmd.command {
+        ScheduleSubCmd::Add { cron, command } => {
+            let schedule = parse_cron(&cron)?;
+            let args = shlex::split(&command).ok_or_else(|| anyhow::anyhow!("unbalanced quotes in --command"))?;
+            validate_command(&args)?;
+            let next_run = schedule.after(&Utc::now()).next();
+            let id = store.insert_scheduled_job(&cron, &serde_json::to_string(&args)?, next_run)?;
+            println!("Added job {}: next run {}", id, format_time(next_run));
+            println!("Run `lotus-sign schedule daemon` to execute scheduled jobs");
+        }
+        ScheduleSubCmd::List => {
+            let jobs = store.list_scheduled_jobs()?;
+            out.print_list(&jobs, |jobs| {
+                if jobs.is_empty() {
+                    println!("No scheduled jobs");
+                    return;
+                }
+                println!("{:<5} {:<8} {:<20} {:<20} {:<20} Command", "ID", "Enabled", "Cron", "Next run", "Last run");
+                println!("{}", "-".repeat(100));
+                for job in jobs {
+                    let next_run = if job.enabled { format_time(job.next_run) } else { "-".to_string() };
+                    println!(
+                        "{:<5} {:<8} {:<20} {:<20} {:<20} {}",
+                        job.id,
+                        if job.enabled { "yes" } else { "no" },
+                        job.cron_expr,
+                        next_run,
+                        format_time(job.last_run),
+                        display_command(job)
+                    );
+                }
+            })?;
+        }
+        ScheduleSubCmd::Disable { id } => {
+            if !store.set_scheduled_job_enabled(id, false, None)? {
+                anyhow::bail!("scheduled job {} not found", id);
+            }
+            println!("Disabled job {}", id);
+        }
+        ScheduleSubCmd::Enable { id } => {
+            let job = find_job(store, id)?;
+            let next_run = parse_cron(&job.cron_expr)?.after(&Utc::now()).next();
+            store.set_scheduled_job_enabled(id, true, next_run)?;
+            println!("Enabled job {}: next run {}", id, format_time(next_run));
+        }
+        ScheduleSubCmd::Remove { id } => {
+            if !store.delete_scheduled_job(id)? {
+                anyhow::bail!("scheduled job {} not found", id);
+            }
+            println!("Removed job {}", id);
+        }
+        ScheduleSubCmd::Daemon => run_daemon(cfg, store).await?,
+    }
+    Ok(())
+}
+
+/// 标准 cron 的 5 个字段补上秒字段 0；cron crate 的星期数字为 1-7（周日为 1）
+fn parse_cron(expr: &str) -> Result<cron::Schedule> {
+    let full = if expr.split_whitespace().count() == 5 {
+        format!("0 {}", expr)
+    } else {
+        expr.to_string()
+    };
+    cron::Schedule::from_str(&full).map_err(|e| anyhow::anyhow!("invalid cron expression '{}': {}", expr, e))
+}
+
+/// 添加时即按完整命令行解析，避免到执行时才发现参数错误
+fn validate_command(args: &[String]) -> Result<()> {
+    let cli = Cli::try_parse_from(std::iter::once("lotus-sign".to_string()).chain(args.iter().cloned()))
+        .map_err(|e| anyhow::anyhow!("invalid command: {}", e.render().to_string().trim_end()))?;
+    if matches!(cli.command, Commands::Schedule(_) | Commands::Repl(_) | Commands::Daemon(_) | Commands::Monitor(_) | Commands::Db(_)) {
+        anyhow::bail!("schedule, repl, daemon, monitor and db commands cannot be scheduled");
+    }
+    if cli.profile.is_some() {
+        anyhow::bail!("--profile cannot be scheduled; start the daemon with `lotus-sign --profile <name> schedule daemon`");
+    }
+    // 任务以明文保存在数据库中
+    if cli.password.is_some() {
+        anyhow::bail!("do not store --password in a scheduled job; pass it to `schedule daemon` or use --password-file");
+    }
+    Ok(())
+}
+
+fn find_job(store: &Store, id: i64) -> Result<ScheduledJob> {
+    store.list_scheduled_jobs()?
+        .into_iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| anyhow::anyhow!("scheduled job {} not found", id))
+}
+
+async fn run_daemon(cfg: &Config, store: &Store) -> Result<()> {
+    // 与 repl 相同：未配置密码时在启动时询问一次，之后的任务复用会话中派生的密钥
+    let cfg = &super::with_session_key(cfg).await?;
+
+    // 守护进程未运行期间错过的执行不补做，避免停机恢复后集中发送多笔交易
+    let started = Utc::now();
+    for job in store.list_scheduled_jobs()?.into_iter().filter(|job| job.enabled) {
+        if job.next_run.is_none_or(|t| t < started) {
+            let next_run = parse_cron(&job.cron_expr)?.after(&started).next();
+            store.record_scheduled_run(job.id, None, next_run)?;
+            if let Some(missed) = job.next_run {
+                eprintln!("WARNING: job {} missed its run at {}; next run {}", job.id, format_time(Some(missed)), format_time(next_run));
+            }
+        }
+    }
+    println!("Schedule daemon started; Ctrl-C to stop");
+
+    loop {
+        let now = Utc::now();
+        let jobs: Vec<ScheduledJob> = store.list_scheduled_jobs()?.into_iter().filter(|job| job.enabled).collect();
+        for job in jobs.iter().filter(|job| job.next_run.is_some_and(|t| t <= now)) {
+            run_job(job, cfg, store).await;
+        }
+
+        let next_due = store.list_scheduled_jobs()?
+            .into_iter()
+            .filter(|job| job.enabled)
+            .filter_map(|job| job.next_run)
+            .min();
+        let wait = next_due
+            .and_then(|t| (t - Utc::now()).to_std().ok())
+            .map_or(POLL_INTERVAL, |wait| wait.min(POLL_INTERVAL));
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    println!("Schedule daemon stopped");
+    Ok(())
+}
+
+/// 执行一个到期任务并记录时间；失败只打印错误，不影响其他任务与下次执行
+async fn run_job(job: &ScheduledJob, cfg: &Config, store: &Store) {
+    let started = Utc::now();
+    println!("[{}] job {}: {}", started.format("%Y-%m-%d %H:%M:%S"), job.id, display_command(job));
+    if let Err(e) = execute(job, cfg, store).await {
+        eprintln!("Error: job {} failed: {:#}", job.id, e);
+        tracing::warn!(job = job.id, error = %format!("{:#}", e), "scheduled job failed");
+    }
+
+    let next_run = parse_cron(&job.cron_expr)
+        .map(|schedule| schedule.after(&Utc::now()).next())
+        .unwrap_or(None);
+    if let Err(e) = store.record_scheduled_run(job.id, Some(started), next_run) {
+        eprintln!("Error: cannot record run of job {}: {:#}", job.id, e);
+    }
+}
+
+async fn execute(job: &ScheduledJob, cfg: &Config, store: &Store) -> Result<()> {
+    let args: Vec<String> = serde_json::from_str(&job.command_json)?;
+    let cli = Cli::try_parse_from(std::iter::once("lotus-sign".to_string()).chain(args))
+        .map_err(|e| anyhow::anyhow!("invalid command: {}", e.render().to_string().trim_end()))?;
+    let job_cfg = cli.overlay_config(cfg)?;
+    Box::pin(super::dispatch(cli, &job_cfg, store)).await
+}
+
+fn display_command(job: &ScheduledJob) -> String {
+    let args: Vec<String> = serde_json::from_str(&job.command_json).unwrap_or_default();
+    shlex::try_join(args.iter().map(String::as_str)).unwrap_or_else(|_| args.join(" "))
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map_or_else(|| "-".to_string(), |t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+}
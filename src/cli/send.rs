@@ -1,21 +1,30 @@
 //! 发送 FIL 代币的命令
 
-use crate::config::Config;
+use crate::config::{Config, GasStrategy};
 use crate::db::Store;
 use crate::service::Executor;
 use anyhow::Result;
-use clap::Args;
+use clap::{ArgGroup, Args, ValueHint};
+use std::time::Duration;
 
 /// 发送 FIL 代币的命令参数
 #[derive(Args)]
+// 只给出一个位置参数时视为 AMOUNT，以支持 `--to-label <LABEL> <AMOUNT>`
+#[command(allow_missing_positional = true, group = ArgGroup::new("target").args(["to", "to_label"]).required(true))]
 pub struct SendCmd {
-    /// 目标地址（f1/f3 格式）
-    pub to: String,
+    /// 目标地址（f1/f3 格式）或地址簿名称；与 --to-label 二选一
+    pub to: Option<String>,
     /// 发送金额（单位：FIL，如 "0.1"）
     pub amount: String,
-    /// 发送地址（钱包中必须有对应私钥）
+    /// 发送地址（钱包中必须有对应私钥；省略时使用默认地址）
+    #[arg(long, value_hint = ValueHint::Other)]
+    pub from: Option<String>,
+    /// 按钱包标签指定发送地址（替代 --from）
+    #[arg(long, conflicts_with = "from")]
+    pub from_label: Option<String>,
+    /// 按钱包标签指定目标地址（替代位置参数 TO）
     #[arg(long)]
-    pub from: String,
+    pub to_label: Option<String>,
     /// Gas 优先费（默认：0，自动估算）
     #[arg(long, default_value = "0")]
     pub gas_premium: String,
@@ -25,27 +34,80 @@ pub struct SendCmd {
     /// Gas 限制（默认：0，自动估算）
     #[arg(long, default_value = "0")]
     pub gas_limit: i64,
+    /// 自动估算时使用的费用策略（默认：配置中的 gas.strategy，未配置时由节点估算）
+    #[arg(long, value_enum)]
+    pub gas_strategy: Option<GasStrategy>,
     /// 方法号（默认：0 = 转账）
     #[arg(long, default_value = "0")]
     pub method: u64,
     /// Nonce 覆盖（默认：从链上获取）
     #[arg(long)]
     pub nonce: Option<u64>,
+    #[command(flatten)]
+    pub wait: super::WaitArgs,
+    #[command(flatten)]
+    pub fees: super::FeeArgs,
+    /// 推送后轮询消息池直到消息上链，无需与节点保持长连接
+    #[arg(long, conflicts_with = "wait")]
+    pub wait_for_inclusion: bool,
+    /// 等待上链的超时时间（秒）
+    #[arg(long, default_value = "3600", requires = "wait_for_inclusion")]
+    pub inclusion_timeout: u64,
+}
+
+impl SendCmd {
+    /// 解析目标地址：位置参数 TO 或 --to-label
+    fn resolve_to(&self, store: &Store) -> Result<String> {
+        match (&self.to, &self.to_label) {
+            (Some(to), _) => super::resolve_address_or_name(store, to),
+            (None, Some(label)) => super::resolve_label(store, label),
+            (None, None) => unreachable!("clap requires TO or --to-label"),
+        }
+    }
 }
 
 /// 执行发送命令：签名并广播转账消息
-pub async fn run(cmd: SendCmd, cfg: &Config, store: &Store) -> Result<()> {
-    let executor = Executor::new(cfg, store);
-    let cid = executor.transfer_with_options(
-        &cmd.from,
-        &cmd.to,
+pub async fn run(cmd: SendCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    let from = super::resolve_from_arg(store, cmd.from.clone(), cmd.from_label.clone())?;
+    let to = cmd.resolve_to(store)?;
+
+    let executor = cmd.fees.apply(Executor::new_async(cfg, store).await?);
+    let Some(cid) = executor.transfer_with_options(
+        &from,
+        &to,
         &cmd.amount,
         &cmd.gas_premium,
         &cmd.gas_feecap,
         cmd.gas_limit,
         cmd.method,
         cmd.nonce,
+        cmd.gas_strategy,
+    ).await? else {
+        return Ok(());
+    };
+    if !cmd.wait_for_inclusion {
+        return super::report_sent(cfg, &executor.api, &cid, &cmd.wait, out, || println!("Message CID: {}", cid.root)).await;
+    }
+
+    if out.is_table() {
+        println!("Message CID: {}", cid.root);
+    }
+    let lookup = executor.api.wait_for_inclusion(
+        &cid,
+        cfg.wait_poll_interval(),
+        Duration::from_secs(cmd.inclusion_timeout),
+        // 进度写到标准错误，不混入 --output json 的结果
+        |epoch| match epoch {
+            Some(epoch) => eprintln!("Waiting for inclusion (epoch {})...", epoch),
+            None => eprintln!("Waiting for inclusion..."),
+        },
     ).await?;
-    println!("Message CID: {}", cid.root);
+    super::notify_confirmed(cfg, &executor.api, &cid, &lookup).await;
+    out.print_sent(&executor.api, &cid, Some(lookup.receipt.gas_used), || {
+        println!("Included at epoch {}", lookup.height);
+        println!("Exit code: {}", lookup.receipt.exit_code);
+        println!("Gas used: {}", lookup.receipt.gas_used);
+    }).await?;
+    super::check_exit_code(&cid, lookup.receipt.exit_code)?;
     Ok(())
 }
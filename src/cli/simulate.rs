@@ -0,0 +1,104 @@
+//! 模拟执行命令：通过 StateCall 预览消息的执行结果，不签名也不广播
+
+use crate::chain::{parse_fil, Address, BigInt, Message};
+use crate::config::Config;
+use crate::db::Store;
+use crate::rpc::LotusApi;
+use anyhow::Result;
+use clap::{ArgGroup, Args, ValueHint};
+
+/// 模拟执行命令参数
+#[derive(Args)]
+// 只给出一个位置参数时视为 AMOUNT，以支持 `--to-label <LABEL> <AMOUNT>`
+#[command(allow_missing_positional = true, group = ArgGroup::new("target").args(["to", "to_label"]).required(true))]
+pub struct SimulateCmd {
+    /// 目标地址或地址簿名称；与 --to-label 二选一
+    pub to: Option<String>,
+    /// 发送金额（单位：FIL，如 "0.1"）
+    pub amount: String,
+    /// 发送地址（不需要钱包中有私钥；省略时使用默认地址）
+    #[arg(long, value_hint = ValueHint::Other)]
+    pub from: Option<String>,
+    /// 按钱包标签指定发送地址（替代 --from）
+    #[arg(long, conflicts_with = "from")]
+    pub from_label: Option<String>,
+    /// 按钱包标签指定目标地址（替代位置参数 TO）
+    #[arg(long)]
+    pub to_label: Option<String>,
+    /// Gas 优先费（默认：0）
+    #[arg(long, default_value = "0")]
+    pub gas_premium: String,
+    /// Gas 费用上限（默认：0）
+    #[arg(long, default_value = "0")]
+    pub gas_feecap: String,
+    /// Gas 限制（默认：0，由节点使用区块 gas 上限执行）
+    #[arg(long, default_value = "0")]
+    pub gas_limit: i64,
+    /// 方法号（默认：0 = 转账）
+    #[arg(long, default_value = "0")]
+    pub method: u64,
+    /// hex 编码的方法参数
+    #[arg(long)]
+    pub params: Option<String>,
+    /// Nonce 覆盖（默认：从链上获取）
+    #[arg(long)]
+    pub nonce: Option<u64>,
+}
+
+impl SimulateCmd {
+    /// 解析目标地址：位置参数 TO 或 --to-label
+    fn resolve_to(&self, store: &Store) -> Result<String> {
+        match (&self.to, &self.to_label) {
+            (Some(to), _) => super::resolve_address_or_name(store, to),
+            (None, Some(label)) => super::resolve_label(store, label),
+            (None, None) => unreachable!("clap requires TO or --to-label"),
+        }
+    }
+}
+
+/// 模拟执行并打印退出码、gas 消耗与错误信息；退出码非零时命令失败以便脚本检测
+pub async fn run(cmd: SimulateCmd, cfg: &Config, store: &Store) -> Result<()> {
+    let from = super::resolve_from_arg(store, cmd.from.clone(), cmd.from_label.clone())?;
+    let to = cmd.resolve_to(store)?;
+    let params = match &cmd.params {
+        Some(p) => hex::decode(p.trim_start_matches("0x"))?,
+        None => vec![],
+    };
+
+    let api = LotusApi::from_config(cfg);
+    let nonce = match cmd.nonce {
+        Some(n) => n,
+        None => api.mpool_get_nonce(&from).await?,
+    };
+    let msg = Message {
+        version: 0,
+        to: Address::from_string(&to)?,
+        from: Address::from_string(&from)?,
+        nonce,
+        value: BigInt(parse_fil(&cmd.amount)?),
+        gas_limit: cmd.gas_limit,
+        gas_fee_cap: BigInt::from_str(&cmd.gas_feecap),
+        gas_premium: BigInt::from_str(&cmd.gas_premium),
+        method: cmd.method,
+        params,
+    };
+
+    let result = api.state_call(&msg).await?;
+    if let Some(cid) = &result.msg_cid {
+        println!("Message CID: {}", cid.root);
+    }
+    println!("Exit code: {}", result.msg_rct.exit_code);
+    println!("Gas used: {}", result.msg_rct.gas_used);
+    if let Some(ret) = result.msg_rct.return_data.as_deref().filter(|r| !r.is_empty()) {
+        println!("Return: {}", ret);
+    }
+    if let Some(error) = result.error.as_deref().filter(|e| !e.is_empty()) {
+        println!("Error: {}", error);
+    }
+    println!("Duration: {} ms", result.duration / 1_000_000);
+
+    if result.msg_rct.exit_code != 0 {
+        anyhow::bail!("simulated execution failed with exit code {}", result.msg_rct.exit_code);
+    }
+    Ok(())
+}
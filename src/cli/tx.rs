@@ -0,0 +1,111 @@
+//! 离线签名流程：联网机器导出未签名消息，离线机器签名，再通过 mpool-push 广播
+
+use crate::chain::{format_fil, Message, SignedMessage};
+use crate::config::Config;
+use crate::db::Store;
+use crate::service::Executor;
+use crate::wallet::Wallet;
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueHint};
+use std::fs;
+use std::path::PathBuf;
+
+/// 离线签名命令参数
+#[derive(Args)]
+pub struct TxCmd {
+    #[command(subcommand)]
+    pub command: TxSubCmd,
+}
+
+#[derive(Subcommand)]
+pub enum TxSubCmd {
+    /// 构建未签名消息（获取 nonce 并估算 gas）并输出为 JSON
+    Export {
+        #[arg(long, value_hint = ValueHint::Other)]
+        to: String,
+        /// 金额（attoFIL）
+        #[arg(long, default_value = "0")]
+        amount: String,
+        /// 发送地址（省略时使用默认地址）
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: Option<String>,
+        /// 方法号（默认：0 = 转账）
+        #[arg(long, default_value = "0")]
+        method: u64,
+        /// 十六进制编码的 CBOR 参数
+        #[arg(long, default_value = "")]
+        params: String,
+        /// 输出文件（默认：标准输出）
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        out: Option<PathBuf>,
+    },
+    /// 使用本地钱包签名导出的消息，输出 SignedMessage JSON
+    Sign {
+        #[arg(value_hint = ValueHint::FilePath)]
+        file: PathBuf,
+        /// 输出文件（默认：标准输出）
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        out: Option<PathBuf>,
+        /// 跳过确认提示
+        #[arg(long, short)]
+        yes: bool,
+    },
+}
+
+pub async fn run(cmd: TxCmd, cfg: &Config, store: &Store) -> Result<()> {
+    match cmd.command {
+        TxSubCmd::Export { to, amount, from, method, params, out } => {
+            let from = super::resolve_from_arg(store, from, None)?;
+            let params = hex::decode(params.trim_start_matches("0x"))?;
+
+            let executor = Executor::new_async(cfg, store).await?;
+            let msg = executor.build_message(&from, &to, method, &amount, params).await?;
+
+            print_preview(&msg);
+            write_output(out.as_ref(), &serde_json::to_string_pretty(&msg)?)?;
+        }
+        TxSubCmd::Sign { file, out, yes } => {
+            let msg: Message = serde_json::from_str(&fs::read_to_string(&file)?)
+                .map_err(|e| anyhow::anyhow!("invalid message JSON in {}: {}", file.display(), e))?;
+
+            print_preview(&msg);
+            if !yes && !super::confirm("Sign this message?")? {
+                anyhow::bail!("aborted");
+            }
+
+            // 签名只需要本地密钥，不访问网络
+            let from = msg.from.to_string();
+            let wallet = Wallet::with_enc_key(store, cfg.enc_key()?);
+            let signature = wallet.sign(&msg, &from)?;
+            let signed = SignedMessage { message: msg, signature };
+            write_output(out.as_ref(), &serde_json::to_string(&signed)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// 在标准错误输出消息摘要，供操作者核对（标准输出只保留 JSON）
+fn print_preview(msg: &Message) {
+    eprintln!("From:       {}", msg.from);
+    eprintln!("To:         {}", msg.to);
+    eprintln!("Value:      {}", format_fil(&msg.value.0));
+    eprintln!("Method:     {}", msg.method);
+    eprintln!("Nonce:      {}", msg.nonce);
+    eprintln!("GasLimit:   {}", msg.gas_limit);
+    eprintln!("GasFeeCap:  {} attoFIL", msg.gas_fee_cap);
+    eprintln!("GasPremium: {} attoFIL", msg.gas_premium);
+    if !msg.params.is_empty() {
+        eprintln!("Params:     {}", hex::encode(&msg.params));
+    }
+}
+
+pub(super) fn write_output(out: Option<&PathBuf>, content: &str) -> Result<()> {
+    match out {
+        Some(path) => {
+            fs::write(path, content)?;
+            eprintln!("Written to {}", path.display());
+        }
+        None => println!("{}", content),
+    }
+    Ok(())
+}
@@ -1,9 +1,14 @@
+use crate::chain::BigInt;
 use crate::config::Config;
 use crate::db::{Store, WalletKey};
 use crate::wallet::{KeyType, PrivateKey};
 use crate::crypto;
 use anyhow::Result;
-use clap::{Args, Subcommand};
+use chrono::{DateTime, Duration, Utc};
+use clap::{Args, Subcommand, ValueEnum, ValueHint};
+use serde::Serialize;
+use std::path::PathBuf;
+use zeroize::Zeroizing;
 
 #[derive(Args)]
 pub struct WalletCmd {
@@ -16,14 +21,33 @@ pub enum WalletSubCmd {
     New {
         #[arg(short, long, default_value = "secp256k1")]
         key_type: String,
+        /// 从配置中的 wallet.master_seed_hex 确定性派生密钥
+        #[arg(long, requires = "index")]
+        deterministic: bool,
+        /// 确定性派生的密钥序号
+        #[arg(long, requires = "deterministic")]
+        index: Option<u64>,
+    },
+    List {
+        #[arg(long)]
+        expired: bool,
+        /// 附加显示每个地址的签名日志统计
+        #[arg(long, short)]
+        verbose: bool,
+        /// 同时显示已归档的密钥（标记为 [DELETED]）
+        #[arg(long)]
+        include_deleted: bool,
     },
-    List,
     Balance {
         address: String,
     },
     Export {
         address: String,
     },
+    /// 以 Lotus 密钥格式导出（hex 编码的 {"Type","PrivateKey"} JSON），可直接用于 `lotus wallet import`
+    ExportLotus {
+        address: String,
+    },
     Import {
         private_key: String,
         #[arg(short, long, default_value = "hex")]
@@ -32,63 +56,338 @@ pub enum WalletSubCmd {
     Importnew {
         private_key: String,
     },
+    /// 批量导入目录中的私钥文件：hex 格式读取 *.key（*.bls.key 为 BLS 密钥），json 格式读取 *.json
+    ImportDir {
+        #[arg(value_hint = ValueHint::DirPath)]
+        path: String,
+        #[arg(short, long, default_value = "hex")]
+        format: String,
+    },
+    /// 导入 `lotus wallet export` 的输出
+    ///
+    /// 输入为 hex 编码的 JSON：{"Type":"secp256k1"|"bls","PrivateKey":"<base64>"}
+    ImportLotus {
+        hex_encoded_json: String,
+    },
+    /// 生成 24 词 BIP-39 助记词并存储派生的 secp256k1 密钥
+    MnemonicNew {
+        /// 可选的 BIP-39 密码短语（恢复时必须提供相同的值）
+        #[arg(long)]
+        passphrase: Option<String>,
+    },
+    /// 从 BIP-39 助记词恢复 secp256k1 密钥
+    MnemonicImport {
+        /// 助记词，可整体加引号或以空格分隔
+        #[arg(required = true, num_args = 1..)]
+        words: Vec<String>,
+        #[arg(long)]
+        passphrase: Option<String>,
+    },
+    /// 按 BIP-32 路径从种子或助记词派生 secp256k1 子密钥
+    DeriveChild {
+        /// 十六进制原始种子
+        #[arg(long, conflicts_with = "mnemonic", required_unless_present = "mnemonic")]
+        seed_hex: Option<String>,
+        /// BIP-39 助记词（整体加引号）
+        #[arg(long)]
+        mnemonic: Option<String>,
+        /// BIP-39 密码短语（仅与 --mnemonic 一起使用）
+        #[arg(long, requires = "mnemonic")]
+        passphrase: Option<String>,
+        #[arg(long, default_value = "m/44'/461'/0'/0/0")]
+        path: String,
+        /// 将派生的密钥加密存入钱包
+        #[arg(long)]
+        store: bool,
+    },
+    /// 转出全部余额减去估算的最大 gas 费用
+    Sweep {
+        /// 目标地址或地址簿名称
+        #[arg(value_hint = ValueHint::Other)]
+        to: String,
+        /// 转出地址或钱包标签
+        #[arg(long, value_hint = ValueHint::Other)]
+        from: String,
+        /// 转出金额低于该值时放弃（单位：FIL）
+        #[arg(long)]
+        min_amount: Option<String>,
+        #[command(flatten)]
+        wait: super::WaitArgs,
+    },
+    /// 修改钱包密码并重新加密所有密钥
+    ChangePassword,
+    /// 在终端中输入钱包密码并保存到 OS 密钥环（与 --keyring / wallet.use_keyring 配合使用）
+    KeyringSet,
+    /// 从 OS 密钥环删除保存的钱包密码
+    KeyringDelete,
+    /// 设置默认签名地址，省略 --from 时使用
+    SetDefault {
+        address: String,
+    },
+    /// 查看默认签名地址
+    GetDefault,
+    SetExpiry {
+        address: String,
+        /// RFC3339 时间（如 "2025-12-31T00:00:00Z"）、相对时长（如 "30d"、"12h"）或 "none" 清除
+        expiry: String,
+    },
+    /// 为密钥设置标签；标签必须唯一，可在地址参数处代替地址使用
+    Label {
+        address: String,
+        label: String,
+    },
+    /// 为密钥设置备注
+    Note {
+        address: String,
+        note: String,
+    },
+    /// 归档密钥：不再列出或用于签名，可用 `wallet restore` 恢复；--purge 则从数据库中彻底删除
+    Delete {
+        address: String,
+        #[arg(long, default_value = "false")]
+        really_do_it: bool,
+        /// 彻底删除私钥，不可恢复
+        #[arg(long)]
+        purge: bool,
+    },
+    /// 恢复被 `wallet delete` 归档的密钥（地址或标签）
+    Restore {
+        address: String,
+    },
+    /// 设置余额告警阈值（FIL），由 `monitor` 检查；余额低于阈值时发送 webhook
+    SetThreshold {
+        address: String,
+        amount: String,
+        /// 告警地址，省略时使用 notifications.webhook_url
+        #[arg(long)]
+        webhook: Option<String>,
+    },
+    /// 删除余额告警阈值
+    RemoveThreshold {
+        address: String,
+    },
+    /// 从公钥计算地址（不存储任何内容）
+    Derive {
+        /// secp256k1：33/65 字节；BLS：48 字节
+        public_key_hex: String,
+        #[arg(short, long, default_value = "secp256k1")]
+        key_type: String,
+    },
+    /// 以公钥导入只读地址：可查询余额，但不保存私钥、不能签名
+    ImportPubkey {
+        /// secp256k1：33/65 字节；BLS：48 字节
+        public_key_hex: String,
+        #[arg(short, long, default_value = "secp256k1")]
+        key_type: String,
+    },
+    /// 从私钥计算公钥与地址（不存储任何内容）
+    DeriveFromPrivate {
+        private_key_hex: String,
+        #[arg(short, long, default_value = "secp256k1")]
+        key_type: String,
+    },
+    /// 对 JSON 挑战签名（用于身份认证）
+    ///
+    /// 注意：这与签名链上 Message 不同，生成的签名不能用于链上交易
+    SignJson {
+        address: String,
+        json: String,
+    },
+    /// 验证 sign-json 生成的签名
+    VerifyJson {
+        #[arg(long, value_hint = ValueHint::Other)]
+        address: String,
+        #[arg(long)]
+        json: String,
+        /// base64 编码的签名
+        #[arg(long)]
+        signature: String,
+    },
+    /// 对任意字节签名（如交易所 KYC 的地址所有权证明），输出 base64 签名
+    SignBytes {
+        address: String,
+        /// 十六进制编码的数据
+        hex_data: String,
+    },
+    /// 验证 sign-bytes 生成的签名，有效时退出码为 0，无效时为 1
+    Verify {
+        address: String,
+        hex_data: String,
+        /// base64 编码的签名
+        signature: String,
+    },
+    /// 将钱包列表（含余额与 nonce）导出为 CSV 文件；节点不可用时余额与 nonce 留空
+    ExportCsv {
+        #[arg(value_hint = ValueHint::FilePath)]
+        output_file: PathBuf,
+    },
+    /// 只输出地址指纹（去掉前缀后的前 8 个字符），便于脚本使用
+    Fingerprint {
+        address: String,
+    },
+    /// 以二维码显示地址，便于手机扫码
+    Qr {
+        /// 地址或钱包标签
+        address: String,
+        /// 每个模块的大小：unicode / ascii 为字符数（默认 1），svg 为像素（默认 8）
+        #[arg(long)]
+        size: Option<u32>,
+        #[arg(long, value_enum, default_value = "unicode")]
+        format: QrFormat,
+        /// 输出文件；svg 格式必须指定
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum QrFormat {
+    /// Unicode 半块字符，每行字符显示两行模块
+    Unicode,
+    /// 纯 ASCII（'#' 与空格），适合不支持 Unicode 的终端
+    Ascii,
+    Svg,
 }
 
-pub async fn run(cmd: WalletCmd, cfg: &Config, store: &Store) -> Result<()> {
+pub async fn run(cmd: WalletCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
     match cmd.command {
-        WalletSubCmd::New { key_type } => {
-            use crate::chain::Address;
+        WalletSubCmd::New { key_type, deterministic, index } => {
             let kt = KeyType::from_str(&key_type)?;
-            let key = PrivateKey::generate(kt)?;
-            let password = cfg.get_password();
-            let enc_key = crypto::derive_key(&password);
-            let encrypted = crypto::encrypt(&key.private_key, &enc_key)?;
-
-            let addr = match kt {
-                KeyType::Secp256k1 => {
-                    Address::new_secp256k1(&key.public_key)?.to_string()
-                }
-                KeyType::BLS => {
-                    Address::new_bls(&key.public_key)?.to_string()
+            let key = match (deterministic, index) {
+                (true, Some(index)) => {
+                    let seed_hex = cfg.wallet.as_ref()
+                        .and_then(|w| w.master_seed_hex.as_deref())
+                        .ok_or_else(|| anyhow::anyhow!("wallet.master_seed_hex is not set in config"))?;
+                    let seed = hex::decode(seed_hex.trim())?;
+                    if seed.len() != 32 {
+                        anyhow::bail!("wallet.master_seed_hex must be 32 bytes, got {}", seed.len());
+                    }
+                    eprintln!("WARNING: the master seed can regenerate every deterministic key; back it up securely");
+                    PrivateKey::derive_deterministic(kt, &seed, index)?
                 }
+                _ => PrivateKey::generate(kt)?,
             };
-            let wk = WalletKey::new(addr.clone(), kt.as_str().to_string(), encrypted);
+            let addr = key.address()?.to_string();
+
+            if deterministic && store.has_key(&addr)? {
+                println!("Exists: {}", addr);
+                return Ok(());
+            }
+
+            let password = cfg.get_new_password()?;
+            let enc_key = crypto::derive_key(&password);
+            let wk = new_wallet_key(cfg, addr.clone(), kt.as_str().to_string(), &key.private_key, &enc_key)?;
             store.insert_key(&wk)?;
 
             println!("Created: {}", addr);
         }
-        WalletSubCmd::List => {
+        WalletSubCmd::List { expired, verbose, include_deleted } => {
             use crate::rpc::LotusApi;
             use crate::chain::format_fil;
-            let api = LotusApi::new(&cfg.lotus.host, cfg.lotus.token.clone());
-            let keys = store.list_keys()?;
+            let api = LotusApi::from_config(cfg);
+            let keys = if include_deleted { store.list_all_keys()? } else { store.list_keys()? };
+            let keys: Vec<WalletKey> = keys
+                .into_iter()
+                .filter(|k| !expired || k.is_expired())
+                .collect();
+            let default = store.get_config(super::DEFAULT_ADDRESS_KEY)?;
+
+            let chain_state = fetch_chain_state(&api, &keys).await;
+            if out.is_csv() {
+                return write_wallet_csv(std::io::stdout(), cfg, &keys, &chain_state);
+            }
 
-            println!("{:<50} {:<12} {:<20} {:<10}", "Address", "Type", "Balance", "Nonce");
-            println!("{}", "-".repeat(95));
+            let mut rows = Vec::new();
+            for (k, (balance, nonce)) in keys.iter().zip(&chain_state) {
+                rows.push(WalletListRow {
+                    address: super::display_address(cfg, &k.address),
+                    fingerprint: crate::chain::Address::from_string(&k.address)
+                        .map(|a| a.fingerprint())
+                        .unwrap_or_default(),
+                    label: k.label.clone(),
+                    note: k.note.clone(),
+                    key_type: k.key_type.clone(),
+                    watch_only: k.is_watch_only,
+                    balance_attofil: balance.clone().unwrap_or_default().to_string(),
+                    nonce: nonce.unwrap_or(0),
+                    deleted_at: k.deleted_at.map(|t| t.to_rfc3339()),
+                });
+            }
 
-            for k in keys {
-                let balance = api.wallet_balance(&k.address).await.unwrap_or_default();
-                let nonce = api.mpool_get_nonce(&k.address).await.unwrap_or(0);
-                let bal_str = format_fil(&balance.0);
-                println!("{:<50} {:<12} {:<20} {:<10}", k.address, k.key_type, bal_str, nonce);
+            let mut stats = Vec::new();
+            if verbose && out.is_table() {
+                for k in &keys {
+                    stats.push(store.signing_log_aggregate(Some(&k.address))?);
+                }
             }
+
+            out.print_list(&rows, |rows| {
+                println!("  {:<50} {:<11} {:<20} {:<18} {:<20} {:<10} {:<20}", "Address", "Fingerprint", "Label", "Type", "Balance", "Nonce", "Expires");
+                println!("{}", "-".repeat(157));
+
+                for (i, (row, k)) in rows.iter().zip(&keys).enumerate() {
+                    let bal_str = format_fil(&BigInt::from_str(&row.balance_attofil).0);
+                    let expiry_str = format_expiry(k);
+                    let marker = if default.as_deref() == Some(k.address.as_str()) { "*" } else { " " };
+                    let label: String = row.label.as_deref().unwrap_or("-").chars().take(20).collect();
+                    let key_type = if row.watch_only { format!("{} [watch]", row.key_type) } else { row.key_type.clone() };
+                    let deleted = if row.deleted_at.is_some() { " [DELETED]" } else { "" };
+                    println!("{} {:<50} {:<11} {:<20} {:<18} {:<20} {:<10} {:<20}{}", marker, row.address, row.fingerprint, label, key_type, bal_str, row.nonce, expiry_str, deleted);
+                    if let Some(stats) = stats.get(i) {
+                        let last = stats.last_message_at
+                            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_else(|| "-".to_string());
+                        println!(
+                            "  signed: {} ({} ok, {} failed), sent: {}, last: {}",
+                            stats.total_messages, stats.success_count, stats.failure_count,
+                            format_fil(&stats.total_value_attofil), last
+                        );
+                    }
+                }
+            })?;
         }
         WalletSubCmd::Balance { address } => {
+            let address = super::resolve_key_address(store, &address)?;
             use crate::rpc::LotusApi;
-            let api = LotusApi::new(&cfg.lotus.host, cfg.lotus.token.clone());
+            let api = LotusApi::from_config(cfg);
             let bal = api.wallet_balance(&address).await?;
             println!("{}: {} attoFIL", address, bal);
         }
         WalletSubCmd::Export { address } => {
+            let address = super::resolve_key_address(store, &address)?;
             let key = store.get_key(&address)?
                 .ok_or_else(|| anyhow::anyhow!("key not found"))?;
-            let password = cfg.get_password();
+            if key.is_watch_only {
+                anyhow::bail!("{} is watch-only and has no private key", address);
+            }
+            let password = cfg.get_password_or_prompt()?;
             let enc_key = crypto::derive_key(&password);
-            let pk = crypto::decrypt(&key.encrypted_key, &enc_key)?;
+            let pk = crypto::decrypt(&key.encrypted_key, &enc_key, key.cipher)?;
             println!("{}", hex::encode(&pk));
         }
+        WalletSubCmd::ExportLotus { address } => {
+            use base64::Engine;
+
+            let address = super::resolve_key_address(store, &address)?;
+            let key = store.get_key(&address)?
+                .ok_or_else(|| anyhow::anyhow!("key not found"))?;
+            if key.is_watch_only {
+                anyhow::bail!("{} is watch-only and has no private key", address);
+            }
+            let kt = KeyType::from_str(&key.key_type)?;
+            let password = cfg.get_password_or_prompt()?;
+            let enc_key = crypto::derive_key(&password);
+            let pk = crypto::decrypt(&key.encrypted_key, &enc_key, key.cipher)?;
+
+            // 字段顺序与 Lotus 的 KeyInfo 一致
+            let json = format!(
+                r#"{{"Type":"{}","PrivateKey":"{}"}}"#,
+                kt.as_str(),
+                base64::engine::general_purpose::STANDARD.encode(&pk)
+            );
+            println!("{}", hex::encode(json.as_bytes()));
+        }
         WalletSubCmd::Import { private_key, format } => {
-            use crate::chain::Address;
             use base64::Engine;
 
             // Auto-detect format: hex-encoded JSON starts with "7b22" (which is `{"`)
@@ -100,7 +399,7 @@ pub async fn run(cmd: WalletCmd, cfg: &Config, store: &Store) -> Result<()> {
                 let key_type = v["Type"].as_str().unwrap_or("secp256k1").to_string();
                 let key_str = v["PrivateKey"].as_str()
                     .ok_or_else(|| anyhow::anyhow!("invalid json format"))?;
-                let pk = base64::engine::general_purpose::STANDARD.decode(key_str)?;
+                let pk = Zeroizing::new(base64::engine::general_purpose::STANDARD.decode(key_str)?);
                 (pk, key_type)
             } else if format == "json" {
                 // Plain JSON format
@@ -108,54 +407,651 @@ pub async fn run(cmd: WalletCmd, cfg: &Config, store: &Store) -> Result<()> {
                 let key_type = v["Type"].as_str().unwrap_or("secp256k1").to_string();
                 let key_str = v["PrivateKey"].as_str()
                     .ok_or_else(|| anyhow::anyhow!("invalid json format"))?;
-                let pk = base64::engine::general_purpose::STANDARD.decode(key_str)?;
+                let pk = Zeroizing::new(base64::engine::general_purpose::STANDARD.decode(key_str)?);
                 (pk, key_type)
             } else {
                 // Raw hex format
-                (hex::decode(&private_key)?, "secp256k1".to_string())
+                (Zeroizing::new(hex::decode(&private_key)?), "secp256k1".to_string())
             };
 
             // Derive public key and address based on key type
-            let addr = if key_type == "bls" {
-                use blst::min_pk::SecretKey as BlsSecretKey;
-                let mut key_be = [0u8; 32];
-                for i in 0..32 { key_be[i] = pk[31 - i]; }
-                let sk = BlsSecretKey::from_bytes(&key_be)
-                    .map_err(|e| anyhow::anyhow!("invalid BLS key: {:?}", e))?;
-                let pubkey = sk.sk_to_pk().to_bytes().to_vec();
-                Address::new_bls(&pubkey)?.to_string()
-            } else {
-                // secp256k1
-                let secp = secp256k1::Secp256k1::new();
-                let secret = secp256k1::SecretKey::from_slice(&pk)?;
-                let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret);
-                Address::new_secp256k1(&pubkey.serialize_uncompressed())?.to_string()
-            };
+            let kt = if key_type == "bls" { KeyType::BLS } else { KeyType::Secp256k1 };
+            let addr = PrivateKey::from_bytes(kt, &pk)?.address()?.to_string();
 
-            let password = cfg.get_password();
+            let password = cfg.get_new_password()?;
             let enc_key = crypto::derive_key(&password);
-            let encrypted = crypto::encrypt(&pk, &enc_key)?;
-            let wk = WalletKey::new(addr.clone(), key_type, encrypted);
+            let wk = new_wallet_key(cfg, addr.clone(), key_type, &pk, &enc_key)?;
             store.insert_key(&wk)?;
             println!("Imported: {}", addr);
         }
+        WalletSubCmd::ImportLotus { hex_encoded_json } => {
+            let json_bytes = hex::decode(hex_encoded_json.trim())
+                .map_err(|e| anyhow::anyhow!("invalid hex: {}", e))?;
+            let (kt, pk) = parse_lotus_key_info(&String::from_utf8(json_bytes)?)?;
+            let key = PrivateKey::from_bytes(kt, &pk)?;
+
+            let addr = key.address()?.to_string();
+            if store.has_key(&addr)? {
+                println!("Exists: {}", addr);
+                return Ok(());
+            }
+            store_key(cfg, store, &key)?;
+            println!("Imported: {}", addr);
+        }
+        WalletSubCmd::ImportDir { path, format } => {
+            let extension = match format.as_str() {
+                "hex" => "key",
+                "json" => "json",
+                _ => anyhow::bail!("unsupported format: {} (expected hex or json)", format),
+            };
+            let mut files: Vec<PathBuf> = std::fs::read_dir(&path)
+                .map_err(|e| anyhow::anyhow!("cannot read directory {}: {}", path, e))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == extension))
+                .collect();
+            files.sort();
+
+            let password = cfg.get_new_password()?;
+            let enc_key = crypto::derive_key(&password);
+            let mut keys: Vec<WalletKey> = Vec::new();
+            let (mut failed, mut skipped) = (0, 0);
+            for file in &files {
+                let key = match read_key_file(file) {
+                    Ok(key) => key,
+                    Err(e) => {
+                        tracing::debug!(file = %file.display(), error = %e, "key file rejected");
+                        failed += 1;
+                        continue;
+                    }
+                };
+                let addr = key.address()?.to_string();
+                if store.has_key(&addr)? || keys.iter().any(|k| k.address == addr) {
+                    tracing::debug!(file = %file.display(), address = %addr, "key already stored, skipping");
+                    skipped += 1;
+                    continue;
+                }
+                tracing::debug!(file = %file.display(), address = %addr, "key file parsed");
+                keys.push(new_wallet_key(cfg, addr, key.key_type.as_str().to_string(), &key.private_key, &enc_key)?);
+            }
+
+            // 任一插入失败时整批回滚，不会留下部分导入的结果
+            store.insert_keys(&keys)?;
+            println!("Imported: {} keys, Failed: {} files", keys.len(), failed);
+            if skipped > 0 {
+                println!("Skipped: {} files already in the wallet", skipped);
+            }
+        }
         WalletSubCmd::Importnew { private_key } => {
-            use crate::chain::Address;
-            let pk = hex::decode(&private_key)?;
+            let pk = Zeroizing::new(hex::decode(&private_key)?);
 
             // Derive public key and address (secp256k1)
-            let secp = secp256k1::Secp256k1::new();
-            let secret = secp256k1::SecretKey::from_slice(&pk)?;
-            let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret);
-            let addr = Address::new_secp256k1(&pubkey.serialize_uncompressed())?.to_string();
+            let addr = PrivateKey::from_bytes(KeyType::Secp256k1, &pk)?.address()?.to_string();
 
-            let password = cfg.get_password();
+            let password = cfg.get_new_password()?;
             let enc_key = crypto::derive_key(&password);
-            let encrypted = crypto::encrypt(&pk, &enc_key)?;
-            let wk = WalletKey::new(addr.clone(), "secp256k1".to_string(), encrypted);
+            let wk = new_wallet_key(cfg, addr.clone(), "secp256k1".to_string(), &pk, &enc_key)?;
             store.insert_key(&wk)?;
             println!("{}", addr);
         }
+        WalletSubCmd::MnemonicNew { passphrase } => {
+            let mnemonic = bip39::Mnemonic::generate(24)
+                .map_err(|e| anyhow::anyhow!("mnemonic generation failed: {}", e))?;
+            let seed = mnemonic.to_seed(passphrase.unwrap_or_default());
+            let key = PrivateKey::from_bip39_seed(&seed)?;
+
+            let addr = store_key(cfg, store, &key)?;
+            println!("Created: {}", addr);
+            println!();
+            println!("{}", mnemonic);
+            println!();
+            eprintln!("WARNING: write these 24 words down and keep them offline; anyone with them can spend from this address");
+        }
+        WalletSubCmd::MnemonicImport { words, passphrase } => {
+            let phrase = words.join(" ");
+            let mnemonic = bip39::Mnemonic::parse_normalized(&phrase)
+                .map_err(|e| anyhow::anyhow!("invalid mnemonic: {}", e))?;
+            let seed = mnemonic.to_seed(passphrase.unwrap_or_default());
+            let key = PrivateKey::from_bip39_seed(&seed)?;
+
+            let addr = key.address()?.to_string();
+            if store.has_key(&addr)? {
+                println!("Exists: {}", addr);
+                return Ok(());
+            }
+            store_key(cfg, store, &key)?;
+            println!("Imported: {}", addr);
+        }
+        WalletSubCmd::DeriveChild { seed_hex, mnemonic, passphrase, path, store: save } => {
+            let seed = match (seed_hex, mnemonic) {
+                (Some(hex_seed), _) => hex::decode(hex_seed.trim())?,
+                (None, Some(words)) => bip39::Mnemonic::parse_normalized(&words)
+                    .map_err(|e| anyhow::anyhow!("invalid mnemonic: {}", e))?
+                    .to_seed(passphrase.unwrap_or_default())
+                    .to_vec(),
+                (None, None) => anyhow::bail!("either --seed-hex or --mnemonic is required"),
+            };
+            if !(16..=64).contains(&seed.len()) {
+                anyhow::bail!("seed must be 16-64 bytes, got {}", seed.len());
+            }
+
+            let key = PrivateKey::derive_child(&seed, &path)?;
+            let addr = key.address()?.to_string();
+            println!("Path:    {}", path);
+            println!("Address: {}", addr);
+
+            if save {
+                if store.has_key(&addr)? {
+                    println!("Exists: {}", addr);
+                } else {
+                    store_key(cfg, store, &key)?;
+                    println!("Imported: {}", addr);
+                }
+            }
+        }
+        WalletSubCmd::Sweep { to, from, min_amount, wait } => {
+            use crate::chain::parse_fil;
+            let from = super::resolve_key_address(store, &from)?;
+            let to = super::resolve_address_or_name(store, &to)?;
+            let min_amount = min_amount.as_deref().map(parse_fil).transpose()?.unwrap_or_default();
+
+            let executor = crate::service::Executor::new_async(cfg, store).await?;
+            let Some(cid) = executor.sweep(&from, &to, &min_amount).await? else { return Ok(()) };
+            super::report_sent(cfg, &executor.api, &cid, &wait, out, || {
+                println!("Swept {} -> {}", from, to);
+                println!("Message CID: {}", cid.root);
+            }).await?;
+        }
+        WalletSubCmd::ChangePassword => {
+            let old_password = super::password::prompt_password("Current wallet password: ")?;
+            let new_password = super::password::prompt_password_confirm("New wallet password: ")?;
+            let old_key = crypto::derive_key(&old_password);
+            let new_key = crypto::derive_key(&new_password);
+
+            // 先全部解密并重新加密，确认旧密码对每个密钥都正确后再写入
+            let mut updates = Vec::new();
+            // 已归档的密钥也要重新加密，否则恢复后无法用新密码解密；每个密钥保持原有的加密算法
+            for k in store.list_all_keys()?.into_iter().filter(|k| !k.is_watch_only) {
+                let pk = crypto::decrypt(&k.encrypted_key, &old_key, k.cipher)
+                    .map_err(|_| anyhow::anyhow!("current password does not decrypt {}; nothing was changed", k.address))?;
+                let encrypted = crypto::encrypt(&pk, &new_key, k.cipher);
+                updates.push((k.address, encrypted?));
+            }
+            store.update_keys_encrypted(&updates)?;
+
+            println!("Re-encrypted {} keys", updates.len());
+            if cfg.use_keyring() {
+                match crate::config::keyring_set_password(&new_password) {
+                    Ok(()) => println!("Updated the wallet password in the OS keyring"),
+                    Err(e) => eprintln!("WARNING: cannot update the OS keyring ({:#}); run `wallet keyring-set`", e),
+                }
+            }
+            if cfg.wallet.as_ref().is_some_and(|w| w.password.is_some()) {
+                println!("Remember to update wallet.password in config.toml");
+            }
+        }
+        WalletSubCmd::KeyringSet => {
+            let password = super::password::prompt_password("Wallet password: ")?;
+            // 保存前确认密码能解密现有密钥，避免之后每次签名都失败
+            let enc_key = crypto::derive_key(&password);
+            if let Some(k) = store.list_all_keys()?.into_iter().find(|k| !k.is_watch_only) {
+                if crypto::decrypt(&k.encrypted_key, &enc_key, k.cipher).is_err() {
+                    anyhow::bail!("password does not decrypt {}; nothing was saved", k.address);
+                }
+            }
+            crate::config::keyring_set_password(&password)?;
+            println!("Saved wallet password to the OS keyring");
+        }
+        WalletSubCmd::KeyringDelete => {
+            if crate::config::keyring_delete_password()? {
+                println!("Deleted wallet password from the OS keyring");
+            } else {
+                println!("No wallet password in the OS keyring");
+            }
+        }
+        WalletSubCmd::SetDefault { address } => {
+            let address = super::resolve_key_address(store, &address)?;
+            if store.get_key(&address)?.is_none() {
+                anyhow::bail!("key not found: {}", address);
+            }
+            store.set_config(super::DEFAULT_ADDRESS_KEY, &address)?;
+            println!("Default address set to {}", address);
+        }
+        WalletSubCmd::GetDefault => {
+            match store.get_config(super::DEFAULT_ADDRESS_KEY)? {
+                Some(address) => println!("{}", address),
+                None => println!("No default address configured"),
+            }
+        }
+        WalletSubCmd::SetExpiry { address, expiry } => {
+            let address = super::resolve_key_address(store, &address)?;
+            let expires_at = parse_expiry(&expiry)?;
+            store.set_expiry(&address, expires_at)?;
+            match expires_at {
+                Some(t) => println!("{} expires at {}", address, t.to_rfc3339()),
+                None => println!("{} expiry cleared", address),
+            }
+        }
+        WalletSubCmd::Label { address, label } => {
+            let address = super::resolve_key_address(store, &address)?;
+            if super::looks_like_address(&label) {
+                anyhow::bail!("label '{}' looks like an address; choose a different label", label);
+            }
+            if let Some(other) = store.get_key_by_label(&label)? {
+                if other.address != address {
+                    anyhow::bail!("label '{}' is already used by {}", label, other.address);
+                }
+            }
+            store.update_key_label(&address, Some(&label))?;
+            println!("{} labeled {}", address, label);
+        }
+        WalletSubCmd::Note { address, note } => {
+            let address = super::resolve_key_address(store, &address)?;
+            store.update_key_note(&address, Some(&note))?;
+            println!("Note set for {}", address);
+        }
+        WalletSubCmd::Delete { address, really_do_it, purge } => {
+            let address = super::resolve_key_address(store, &address)?;
+            if !store.has_key(&address)? {
+                anyhow::bail!("key not found: {}", address);
+            }
+            if !really_do_it {
+                let action = if purge { "permanently delete" } else { "archive" };
+                println!("Pass --really-do-it to {} {}", action, address);
+                return Ok(());
+            }
+            if purge {
+                eprintln!("WARNING: the private key for {} is removed from the database and cannot be recovered", address);
+                store.purge_key(&address)?;
+                println!("Purged: {}", address);
+            } else {
+                store.delete_key(&address)?;
+                println!("Archived: {} (undo with `lotus-sign wallet restore {}`)", address, address);
+            }
+            if store.get_config(super::DEFAULT_ADDRESS_KEY)?.as_deref() == Some(address.as_str()) {
+                store.delete_config(super::DEFAULT_ADDRESS_KEY)?;
+                println!("Default address cleared");
+            }
+        }
+        WalletSubCmd::Restore { address } => {
+            // 已归档的密钥不在 get_key_by_label 的结果中，需要包括归档记录查找
+            let address = if super::looks_like_address(&address) {
+                address
+            } else {
+                store.get_key_by_label_with_archived(&address)?
+                    .map(|k| k.address)
+                    .ok_or_else(|| anyhow::anyhow!("No key found with label '{}'", address))?
+            };
+            store.restore_key(&address)?;
+            println!("Restored: {}", address);
+        }
+        WalletSubCmd::SetThreshold { address, amount, webhook } => {
+            use crate::chain::{format_fil, parse_fil};
+            let address = super::resolve_key_address(store, &address)?;
+            if store.get_key(&address)?.is_none() {
+                anyhow::bail!("{} is not in the wallet", address);
+            }
+            let threshold = parse_fil(&amount)?;
+            if let Some(url) = &webhook {
+                reqwest::Url::parse(url).map_err(|e| anyhow::anyhow!("invalid webhook URL '{}': {}", url, e))?;
+            }
+            store.set_balance_threshold(&address, &threshold.to_string(), webhook.as_deref())?;
+            println!("Alert when {} drops below {}", address, format_fil(&threshold));
+            if webhook.is_none() && cfg.notifications.as_ref().and_then(|n| n.webhook_url.as_ref()).is_none() {
+                eprintln!("WARNING: no webhook configured; `monitor` will only print alerts (set --webhook or notifications.webhook_url)");
+            }
+        }
+        WalletSubCmd::RemoveThreshold { address } => {
+            let address = super::resolve_key_address(store, &address)?;
+            if !store.delete_balance_threshold(&address)? {
+                anyhow::bail!("no balance threshold set for {}", address);
+            }
+            println!("Removed balance threshold for {}", address);
+        }
+        WalletSubCmd::Derive { public_key_hex, key_type } => {
+            use crate::wallet::address_from_public_key;
+            let kt = KeyType::from_str(&key_type)?;
+            let pubkey = hex::decode(public_key_hex.trim())?;
+            println!("{}", address_from_public_key(kt, &pubkey)?);
+        }
+        WalletSubCmd::ImportPubkey { public_key_hex, key_type } => {
+            use crate::wallet::address_from_public_key;
+            let kt = KeyType::from_str(&key_type)?;
+            let pubkey = hex::decode(public_key_hex.trim())?;
+            let addr = address_from_public_key(kt, &pubkey)?.to_string();
+            if store.has_key(&addr)? {
+                println!("Exists: {}", addr);
+                return Ok(());
+            }
+            store.insert_key(&WalletKey::watch_only(addr.clone(), kt.as_str().to_string()))?;
+            println!("Imported (watch-only): {}", addr);
+        }
+        WalletSubCmd::DeriveFromPrivate { private_key_hex, key_type } => {
+            let kt = KeyType::from_str(&key_type)?;
+            let pk = hex::decode(private_key_hex.trim())?;
+            let key = PrivateKey::from_bytes(kt, &pk)?;
+            println!("Public Key: {}", hex::encode(&key.public_key));
+            println!("Address: {}", key.address()?);
+        }
+        WalletSubCmd::SignJson { address, json } => {
+            use crate::wallet::Wallet;
+            use base64::Engine;
+
+            let address = super::resolve_key_address(store, &address)?;
+            let canonical = canonicalize_json(&json)?;
+            let message_hash = blake2b_simd::Params::new()
+                .hash_length(32)
+                .hash(canonical.as_bytes());
+
+            let wallet = Wallet::with_enc_key(store, cfg.enc_key()?);
+            let sig = wallet.sign_raw(canonical.as_bytes(), &address)?;
+            let sig_type = if sig.sig_type == 2 { "bls" } else { "secp256k1" };
+
+            let out = serde_json::json!({
+                "address": address,
+                "message": canonical,
+                "message_hash": hex::encode(message_hash.as_bytes()),
+                "signature": base64::engine::general_purpose::STANDARD.encode(&sig.data),
+                "sig_type": sig_type,
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        WalletSubCmd::VerifyJson { address, json, signature } => {
+            use crate::chain::Address;
+            use crate::wallet::verify_signature;
+
+            let addr = Address::from_string(&super::resolve_key_address(store, &address)?)?;
+            let canonical = canonicalize_json(&json)?;
+            let sig = decode_signature(&addr, &signature)?;
+
+            // 与 verify 相同，格式错误的签名也视为无效
+            if !verify_signature(&addr, canonical.as_bytes(), &sig).unwrap_or(false) {
+                println!("invalid");
+                anyhow::bail!("invalid signature");
+            }
+            println!("valid");
+        }
+        WalletSubCmd::SignBytes { address, hex_data } => {
+            use crate::wallet::Wallet;
+            use base64::Engine;
+
+            let address = super::resolve_key_address(store, &address)?;
+            let data = decode_hex_data(&hex_data)?;
+            let wallet = Wallet::with_enc_key(store, cfg.enc_key()?);
+            let sig = wallet.sign_raw(&data, &address)?;
+            println!("{}", base64::engine::general_purpose::STANDARD.encode(&sig.data));
+        }
+        WalletSubCmd::Verify { address, hex_data, signature } => {
+            use crate::chain::Address;
+            use crate::wallet::verify_signature;
+
+            let addr = Address::from_string(&super::resolve_key_address(store, &address)?)?;
+            let data = decode_hex_data(&hex_data)?;
+            let sig = decode_signature(&addr, &signature)?;
+
+            // 格式错误的签名也视为无效
+            if verify_signature(&addr, &data, &sig).unwrap_or(false) {
+                println!("valid");
+            } else {
+                println!("invalid");
+                anyhow::bail!("invalid signature");
+            }
+        }
+        WalletSubCmd::ExportCsv { output_file } => {
+            let api = crate::rpc::LotusApi::from_config(cfg);
+            let keys = store.list_keys()?;
+            let chain_state = fetch_chain_state(&api, &keys).await;
+            let file = std::fs::File::create(&output_file)
+                .map_err(|e| anyhow::anyhow!("cannot create {}: {}", output_file.display(), e))?;
+            write_wallet_csv(file, cfg, &keys, &chain_state)?;
+            println!("Exported {} keys to {}", keys.len(), output_file.display());
+        }
+        WalletSubCmd::Fingerprint { address } => {
+            let address = super::resolve_key_address(store, &address)?;
+            println!("{}", crate::chain::Address::from_string(&address)?.fingerprint());
+        }
+        WalletSubCmd::Qr { address, size, format, out } => {
+            use crate::chain::Address;
+
+            if format == QrFormat::Svg && out.is_none() {
+                anyhow::bail!("--out <FILE> is required for svg output");
+            }
+            let address = super::resolve_key_address(store, &address)?;
+            Address::from_string(&address)?;
+            let rendered = render_qr(&address, format, size)?;
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, rendered)
+                        .map_err(|e| anyhow::anyhow!("cannot write {}: {}", path.display(), e))?;
+                    println!("QR code for {} written to {}", address, path.display());
+                }
+                None => {
+                    println!("{}", rendered);
+                    println!("{}", address);
+                }
+            }
+        }
     }
     Ok(())
 }
+
+/// 并发查询每个地址的余额与 nonce，顺序与 keys 一致；查询失败的项为 None
+async fn fetch_chain_state(api: &crate::rpc::LotusApi, keys: &[WalletKey]) -> Vec<(Option<BigInt>, Option<u64>)> {
+    use futures::stream::{FuturesUnordered, StreamExt};
+
+    let mut pending: FuturesUnordered<_> = keys.iter()
+        .enumerate()
+        .map(|(i, k)| async move {
+            let (balance, nonce) = futures::join!(api.wallet_balance(&k.address), api.mpool_get_nonce(&k.address));
+            (i, balance.ok(), nonce.ok())
+        })
+        .collect();
+
+    let mut state = vec![(None, None); keys.len()];
+    while let Some((i, balance, nonce)) = pending.next().await {
+        state[i] = (balance, nonce);
+    }
+    state
+}
+
+/// wallet list --output csv 与 export-csv 的输出行
+#[derive(Serialize)]
+struct WalletCsvRow {
+    address: String,
+    key_type: String,
+    balance_attofil: Option<String>,
+    balance_fil: Option<String>,
+    nonce: Option<u64>,
+    label: Option<String>,
+    created_at: String,
+}
+
+/// 写出 RFC 4180 CSV；未查询到的余额与 nonce 为空字段
+fn write_wallet_csv<W: std::io::Write>(
+    writer: W,
+    cfg: &Config,
+    keys: &[WalletKey],
+    chain_state: &[(Option<BigInt>, Option<u64>)],
+) -> Result<()> {
+    use crate::chain::format_fil;
+
+    let mut csv = csv::Writer::from_writer(writer);
+    if keys.is_empty() {
+        // serialize 在写第一条记录时才输出表头，空钱包也保留表头
+        csv.write_record(["address", "key_type", "balance_attofil", "balance_fil", "nonce", "label", "created_at"])?;
+    }
+    for (k, (balance, nonce)) in keys.iter().zip(chain_state) {
+        csv.serialize(WalletCsvRow {
+            address: super::display_address(cfg, &k.address),
+            key_type: k.key_type.clone(),
+            balance_attofil: balance.as_ref().map(|b| b.to_string()),
+            // 与 attoFIL 列一样只保留数值，便于表格计算
+            balance_fil: balance.as_ref().map(|b| format_fil(&b.0).trim_end_matches(" FIL").to_string()),
+            nonce: *nonce,
+            label: k.label.clone(),
+            created_at: k.created_at.to_rfc3339(),
+        })?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// 生成只包含地址字符串的二维码
+fn render_qr(address: &str, format: QrFormat, size: Option<u32>) -> Result<String> {
+    use qrcode::render::{svg, unicode};
+    use qrcode::QrCode;
+
+    let code = QrCode::new(address.as_bytes())
+        .map_err(|e| anyhow::anyhow!("cannot encode QR code: {}", e))?;
+    Ok(match format {
+        // 终端多为深色背景：深色模块输出为空白、浅色模块输出为色块，扫码时仍是浅底深码
+        QrFormat::Unicode => {
+            let size = size.unwrap_or(1);
+            code.render::<unicode::Dense1x2>()
+                .dark_color(unicode::Dense1x2::Light)
+                .light_color(unicode::Dense1x2::Dark)
+                .module_dimensions(size, size)
+                .build()
+        }
+        // 字符高约为宽的两倍，横向加倍使模块接近正方形
+        QrFormat::Ascii => {
+            let size = size.unwrap_or(1);
+            code.render::<char>()
+                .dark_color(' ')
+                .light_color('#')
+                .module_dimensions(size * 2, size)
+                .build()
+        }
+        QrFormat::Svg => {
+            let size = size.unwrap_or(8);
+            code.render::<svg::Color>()
+                .module_dimensions(size, size)
+                .build()
+        }
+    })
+}
+
+/// wallet list 的机器可读输出行
+#[derive(Serialize)]
+struct WalletListRow {
+    address: String,
+    fingerprint: String,
+    label: Option<String>,
+    note: Option<String>,
+    key_type: String,
+    watch_only: bool,
+    balance_attofil: String,
+    nonce: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<String>,
+}
+
+/// 按地址协议还原 base64 编码的签名（f1 为 secp256k1，f3 为 BLS）
+fn decode_signature(addr: &crate::chain::Address, signature: &str) -> Result<crate::chain::Signature> {
+    use crate::chain::{Protocol, Signature};
+    use base64::Engine;
+
+    let data = base64::engine::general_purpose::STANDARD.decode(signature.trim())?;
+    let sig_type = match addr.protocol {
+        Protocol::Secp256k1 => 1,
+        Protocol::BLS => 2,
+        _ => anyhow::bail!("only f1/f3 addresses can sign"),
+    };
+    Ok(Signature { sig_type, data })
+}
+
+fn decode_hex_data(hex_data: &str) -> Result<Vec<u8>> {
+    hex::decode(hex_data.trim().trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("invalid hex data: {}", e))
+}
+
+/// 解析 Lotus KeyInfo JSON：{"Type":"secp256k1"|"bls","PrivateKey":"<base64>"}
+fn parse_lotus_key_info(json: &str) -> Result<(KeyType, Vec<u8>)> {
+    use base64::Engine;
+
+    let v: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| anyhow::anyhow!("invalid Lotus key JSON: {}", e))?;
+    let key_type = v["Type"].as_str()
+        .ok_or_else(|| anyhow::anyhow!("Lotus key JSON is missing Type"))?;
+    let key_str = v["PrivateKey"].as_str()
+        .ok_or_else(|| anyhow::anyhow!("Lotus key JSON is missing PrivateKey"))?;
+    let pk = base64::engine::general_purpose::STANDARD.decode(key_str)?;
+    Ok((KeyType::from_str(key_type)?, pk))
+}
+
+/// 读取 import-dir 的单个私钥文件
+///
+/// *.json 为 {"Type","PrivateKey"} 格式；*.key 为 hex 私钥或 hex 编码的 Lotus 密钥 JSON，
+/// 类型由 JSON 的 Type 字段或 .bls.key 后缀决定
+fn read_key_file(path: &std::path::Path) -> Result<PrivateKey> {
+    let content = std::fs::read_to_string(path)?;
+    let content = content.trim();
+    let (kt, pk) = if path.extension().is_some_and(|ext| ext == "json") {
+        parse_lotus_key_info(content)?
+    } else if content.starts_with("7b22") {
+        parse_lotus_key_info(&String::from_utf8(hex::decode(content)?)?)?
+    } else {
+        let is_bls = path.file_name().is_some_and(|name| name.to_string_lossy().ends_with(".bls.key"));
+        let kt = if is_bls { KeyType::BLS } else { KeyType::Secp256k1 };
+        (kt, hex::decode(content)?)
+    };
+    PrivateKey::from_bytes(kt, &pk)
+}
+
+/// 规范化 JSON：按键排序（BTreeMap）并去除空白
+fn canonicalize_json(json: &str) -> Result<String> {
+    use std::collections::BTreeMap;
+    let map: BTreeMap<String, serde_json::Value> = serde_json::from_str(json)
+        .map_err(|e| anyhow::anyhow!("invalid JSON object: {}", e))?;
+    Ok(serde_json::to_string(&map)?)
+}
+
+/// 加密并存储密钥，返回其地址
+fn store_key(cfg: &Config, store: &Store, key: &PrivateKey) -> Result<String> {
+    let addr = key.address()?.to_string();
+    let password = cfg.get_new_password()?;
+    let enc_key = crypto::derive_key(&password);
+    store.insert_key(&new_wallet_key(cfg, addr.clone(), key.key_type.as_str().to_string(), &key.private_key, &enc_key)?)?;
+    Ok(addr)
+}
+
+/// 按 database.cipher_algorithm 加密私钥，生成待存储的密钥记录
+fn new_wallet_key(cfg: &Config, address: String, key_type: String, private_key: &[u8], enc_key: &[u8; 32]) -> Result<WalletKey> {
+    let cipher = cfg.database.cipher_algorithm;
+    let encrypted = crypto::encrypt(private_key, enc_key, cipher)?;
+    Ok(WalletKey { cipher, ..WalletKey::new(address, key_type, encrypted) })
+}
+
+/// 解析过期时间：RFC3339 时间、相对时长（s/m/h/d/w）或 "none"
+fn parse_expiry(s: &str) -> Result<Option<DateTime<Utc>>> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+        return Ok(Some(t.with_timezone(&Utc)));
+    }
+
+    let duration = super::parse_duration(s)
+        .map_err(|_| anyhow::anyhow!("invalid expiry: {} (expected RFC3339 or e.g. \"30d\")", s))?;
+    Utc::now()
+        .checked_add_signed(duration)
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("expiry is too far in the future: {}", s))
+}
+
+/// 格式化过期时间，7 天内过期或已过期时附加警告标记
+fn format_expiry(key: &WalletKey) -> String {
+    match key.expires_at {
+        None => "-".to_string(),
+        Some(t) => {
+            let date = t.format("%Y-%m-%d").to_string();
+            if key.is_expired() {
+                format!("{} ⚠️ expired", date)
+            } else if t - Utc::now() <= Duration::days(7) {
+                format!("{} ⚠️", date)
+            } else {
+                date
+            }
+        }
+    }
+}
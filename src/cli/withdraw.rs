@@ -1,22 +1,111 @@
+use crate::chain::{format_fil, parse_fil, BigInt};
 use crate::config::Config;
 use crate::db::Store;
 use crate::service::Executor;
 use anyhow::Result;
-use clap::Args;
+use clap::{Args, ValueHint};
+use serde::Serialize;
 
 #[derive(Args)]
 pub struct WithdrawCmd {
-    #[arg(long)]
-    pub miner: String,
-    #[arg(long)]
-    pub amount: String,
-    #[arg(long)]
-    pub from: String,
+    /// 矿工地址或地址簿名称
+    #[arg(long, value_hint = ValueHint::Other)]
+    pub miner: Option<String>,
+    #[arg(long, conflicts_with = "miner")]
+    pub miner_label: Option<String>,
+    #[arg(long, required_unless_present = "all_miners")]
+    pub amount: Option<String>,
+    #[arg(long, value_hint = ValueHint::Other)]
+    pub from: Option<String>,
+    #[arg(long, conflicts_with = "from")]
+    pub from_label: Option<String>,
+    /// 逗号分隔的矿工列表，提取每个矿工的全部可用余额
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["miner", "miner_label", "amount", "wait"])]
+    pub all_miners: Option<Vec<String>>,
+    /// 跳过可用余额低于该值的矿工（单位：FIL）
+    #[arg(long, requires = "all_miners")]
+    pub min_amount: Option<String>,
+    #[command(flatten)]
+    pub wait: super::WaitArgs,
 }
 
-pub async fn run(cmd: WithdrawCmd, cfg: &Config, store: &Store) -> Result<()> {
-    let executor = Executor::new(cfg, store);
-    let cid = executor.miner_withdraw(&cmd.miner, &cmd.from, &cmd.amount).await?;
-    println!("Withdraw Message CID: {}", cid.root);
+pub async fn run(cmd: WithdrawCmd, cfg: &Config, store: &Store, out: &super::Formatter) -> Result<()> {
+    let from = super::resolve_from_arg(store, cmd.from, cmd.from_label)?;
+    let executor = Executor::new_async(cfg, store).await?;
+
+    let Some(miners) = cmd.all_miners else {
+        let miner = match cmd.miner {
+            Some(miner) => super::resolve_address_or_name(store, &miner)?,
+            None => super::resolve_address_arg(store, None, cmd.miner_label, "miner")?,
+        };
+        let amount = cmd.amount.unwrap_or_default();
+        let Some(cid) = executor.miner_withdraw(&miner, &from, &amount).await? else { return Ok(()) };
+        return super::report_sent(cfg, &executor.api, &cid, &cmd.wait, out, || println!("Withdraw Message CID: {}", cid.root)).await;
+    };
+
+    let min_amount = cmd.min_amount.as_deref().map(parse_fil).transpose()?.unwrap_or_default();
+    let miners: Vec<&str> = miners.iter().map(|m| m.trim()).filter(|m| !m.is_empty()).collect();
+    // 余额查询失败的矿工直接记为失败行，其余矿工继续提现
+    let mut rows = Vec::new();
+    let mut balances = Vec::new();
+    for (miner, balance) in executor.miner_available_balances(&miners).await {
+        match balance {
+            Ok(balance) if balance.0 >= min_amount && balance.0 > num_bigint::BigInt::from(0) => balances.push((miner, balance)),
+            Ok(balance) => println!("Skipping {}: available balance {}", miner, format_fil(&balance.0)),
+            Err(e) => rows.push(WithdrawRow::failed(miner, None, &e.context("cannot fetch available balance"))),
+        }
+    }
+
+    let amounts: Vec<(String, String)> = balances.iter()
+        .map(|(m, b)| (m.clone(), b.to_string()))
+        .collect();
+    let results = executor.withdraw_all_miners(
+        amounts.iter().map(|(m, b)| (m.as_str(), b.as_str())).collect(),
+        &from,
+    ).await?;
+
+    for (miner, amount, result) in results {
+        rows.push(match result {
+            Ok(Some(cid)) => WithdrawRow { miner, amount_attofil: Some(amount), cid: Some(cid.root), status: "pushed", error: None },
+            Ok(None) => WithdrawRow { miner, amount_attofil: Some(amount), cid: None, status: "dry-run", error: None },
+            Err(e) => WithdrawRow::failed(miner, Some(amount), &e),
+        });
+    }
+
+    out.print_list(&rows, |rows| {
+        println!("{:<12} {:<24} {:<8} {:<64}", "Miner", "Amount", "Status", "CID / Error");
+        println!("{}", "-".repeat(111));
+        for r in rows {
+            let amount = r.amount_attofil.as_deref().map_or_else(|| "-".to_string(), |a| format_fil(&BigInt::from_str(a).0));
+            let detail = r.cid.as_deref().or(r.error.as_deref()).unwrap_or("-");
+            println!("{:<12} {:<24} {:<8} {:<64}", r.miner, amount, r.status, detail);
+        }
+    })?;
+
+    let failed = rows.iter().filter(|r| r.status == "failed").count();
+    if failed > 0 {
+        anyhow::bail!("{} of {} withdrawals failed", failed, rows.len());
+    }
+    if cfg.dry_run {
+        // 每条提现消息已由 Executor 打印
+        println!("Dry run: {} withdrawals were not signed or pushed", rows.len());
+    }
     Ok(())
 }
+
+/// --all-miners 的机器可读输出行，每个矿工一行
+#[derive(Serialize)]
+struct WithdrawRow {
+    miner: String,
+    amount_attofil: Option<String>,
+    cid: Option<String>,
+    /// pushed / dry-run / failed
+    status: &'static str,
+    error: Option<String>,
+}
+
+impl WithdrawRow {
+    fn failed(miner: String, amount_attofil: Option<String>, e: &anyhow::Error) -> Self {
+        Self { miner, amount_attofil, cid: None, status: "failed", error: Some(format!("{:#}", e)) }
+    }
+}
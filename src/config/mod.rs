@@ -1,47 +1,270 @@
+use crate::chain::Network;
+use crate::crypto::CipherAlgorithm;
+use crate::cli::password::{prompt_password, prompt_password_confirm};
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Once, OnceLock};
+use std::time::Duration;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub lotus: LotusConfig,
     pub database: DatabaseConfig,
     pub wallet: Option<WalletConfig>,
+    pub gas: Option<GasConfig>,
+    /// 命名配置集，通过 `--profile <name>` 覆盖到基础配置之上
+    pub profiles: Option<HashMap<String, ProfileConfig>>,
+    /// RPC 传输错误的重试策略；不设置时使用默认值
+    pub retry: Option<RetryPolicy>,
+    /// `--wait` 等待到消息上链后的通知
+    pub notifications: Option<NotificationsConfig>,
+    /// 节点查询结果的本地缓存
+    pub cache: Option<CacheConfig>,
+    /// 运行时选项（`--dry-run`），不从配置文件读取
+    #[serde(skip)]
+    pub dry_run: bool,
+    /// 运行时选项（`--no-retry`），不从配置文件读取
+    #[serde(skip)]
+    pub no_retry: bool,
+    /// 运行时选项（`--keyring`），不从配置文件读取
+    #[serde(skip)]
+    pub keyring: bool,
+    /// 运行时选项（`--password` / `--password-file`），不从配置文件读取
+    #[serde(skip)]
+    pub cli_password: Option<String>,
+    /// repl / schedule daemon 启动时派生的加密密钥，会话中的命令直接复用，不再派生
+    #[serde(skip)]
+    pub session_key: Option<zeroize::Zeroizing<[u8; 32]>>,
 }
 
-#[derive(Debug, Deserialize)]
+/// 配置集：只需填写与基础配置不同的部分
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProfileConfig {
+    pub lotus: Option<LotusConfig>,
+    pub database: Option<DatabaseConfig>,
+    pub wallet: Option<WalletConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LotusConfig {
     pub host: String,
     pub token: Option<String>,
+    /// `send --wait-for-inclusion` 轮询消息池的间隔（秒），默认 30
+    pub wait_poll_interval_secs: Option<u64>,
+    /// 方法 0 转账的最小金额（attoFIL），默认 "0" 表示不限制
+    pub min_send_attofil: Option<String>,
+    /// 备用节点，主节点连接失败或超时时按顺序切换
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_hosts: Vec<String>,
+    /// 切换到备用节点后多久重新尝试主节点（秒），默认 60
+    pub recovery_interval_secs: Option<u64>,
+    /// 显示地址使用的网络前缀（mainnet / testnet），默认 mainnet；可被 `--network` 覆盖
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<Network>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatabaseConfig {
     pub path: String,
+    /// 以 SQLCipher 加密整个数据库文件（需以 `--features sqlcipher` 编译）；切换前先运行 `db migrate-encrypt`
+    #[serde(default)]
+    pub encrypt: bool,
+    /// 保存 SQLCipher 密钥的环境变量名
+    #[serde(default = "default_cipher_key_env")]
+    pub cipher_key_env: String,
+    /// 加密新私钥使用的算法（aes256gcm / chacha20poly1305）；已存储的密钥按各自记录的算法解密
+    #[serde(default)]
+    pub cipher_algorithm: CipherAlgorithm,
+}
+
+fn default_cipher_key_env() -> String {
+    "LOTUS_SIGN_DB_KEY".to_string()
+}
+
+impl DatabaseConfig {
+    /// 从 `cipher_key_env` 指定的环境变量读取 SQLCipher 密钥
+    pub fn cipher_key(&self) -> Result<String> {
+        match std::env::var(&self.cipher_key_env) {
+            Ok(key) if !key.is_empty() => Ok(key),
+            _ => anyhow::bail!("database cipher key is not set: export {}", self.cipher_key_env),
+        }
+    }
+
+    /// 打开数据库使用的密钥；未启用 encrypt 时为 None
+    pub fn open_key(&self) -> Result<Option<String>> {
+        if self.encrypt { self.cipher_key().map(Some) } else { Ok(None) }
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct WalletConfig {
     pub password: Option<String>,
+    /// 32 字节十六进制主种子，用于 `wallet new --deterministic`
+    pub master_seed_hex: Option<String>,
+    /// 从 OS 密钥环（macOS 钥匙串、Linux Secret Service）读取钱包密码，首次使用时询问并保存
+    #[serde(default)]
+    pub use_keyring: bool,
+}
+
+/// Gas 费用策略：预设的 fee cap / premium 倍数，custom 使用 [gas] 中配置的倍数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum GasStrategy {
+    Slow,
+    Medium,
+    Fast,
+    Custom,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GasConfig {
+    /// 未指定 `--gas-strategy` 时使用的策略；不设置时使用节点的 GasEstimateMessageGas
+    pub strategy: Option<GasStrategy>,
+    /// custom 策略的 premium 倍数，默认 1.0
+    pub premium_multiplier: Option<f64>,
+    /// custom 策略的 fee cap 倍数，默认 1.0
+    pub feecap_multiplier: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NotificationsConfig {
+    /// 消息上链后 POST 回执 JSON 的地址
+    pub webhook_url: Option<String>,
+    /// 设置时以 HMAC-SHA-256 签名请求体，放在 X-Lotus-Sign-Signature 请求头中
+    pub webhook_secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CacheConfig {
+    /// StateMinerInfo 缓存有效期（秒），默认 60；0 表示不缓存
+    pub miner_info_ttl_secs: Option<u64>,
+}
+
+/// RPC 重试策略：延迟 = min(initial_delay_ms * 2^attempt + 抖动, max_delay_ms)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// 在延迟上叠加随机抖动，避免多个进程同时重试
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_retries: 3, initial_delay_ms: 500, max_delay_ms: 30000, jitter: true }
+    }
+}
+
+impl RetryPolicy {
+    /// 第 attempt 次重试（从 0 开始）前的等待时间
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let base = self.initial_delay_ms.saturating_mul(1u64 << attempt.min(32));
+        let jitter = if self.jitter && self.initial_delay_ms > 0 {
+            rand::Rng::gen_range(&mut rand::thread_rng(), 0..=self.initial_delay_ms)
+        } else {
+            0
+        };
+        Duration::from_millis(base.saturating_add(jitter).min(self.max_delay_ms))
+    }
 }
 
 impl Config {
-    pub fn load() -> Result<Self> {
+    /// 加载配置，优先级：环境变量 > 配置文件（含 `--profile` 配置集） > 默认值
+    pub fn load(profile: Option<&str>) -> Result<Self> {
         let config_path = Self::config_path();
 
-        if config_path.exists() {
+        let mut config = if config_path.exists() {
             let content = fs::read_to_string(&config_path)?;
-            let config: Config = toml::from_str(&content)?;
-            Ok(config)
+            toml::from_str(&content)?
         } else {
-            Ok(Self::default())
+            Self::default()
+        };
+        if let Some(name) = profile {
+            config.apply_profile(name)?;
+        }
+        config.overlay_from_env();
+        Ok(config)
+    }
+
+    /// 用环境变量覆盖配置字段，便于在容器中注入密钥而无需配置文件：
+    ///
+    /// - `LOTUS_SIGN_LOTUS_HOST` → `lotus.host`
+    /// - `LOTUS_SIGN_LOTUS_TOKEN` → `lotus.token`
+    /// - `LOTUS_SIGN_DB_PATH` → `database.path`
+    /// - `LOTUS_SIGN_WALLET_PASSWORD` → `wallet.password`
+    pub fn overlay_from_env(&mut self) {
+        if let Ok(host) = std::env::var("LOTUS_SIGN_LOTUS_HOST") {
+            self.lotus.host = host;
+        }
+        if let Ok(token) = std::env::var("LOTUS_SIGN_LOTUS_TOKEN") {
+            self.lotus.token = Some(token);
+        }
+        if let Ok(path) = std::env::var("LOTUS_SIGN_DB_PATH") {
+            self.database.path = path;
+        }
+        if let Ok(password) = std::env::var("LOTUS_SIGN_WALLET_PASSWORD") {
+            let wallet = self.wallet.get_or_insert(WalletConfig { password: None, master_seed_hex: None, use_keyring: false });
+            if wallet.password.is_some() {
+                eprintln!("WARNING: both LOTUS_SIGN_WALLET_PASSWORD and wallet.password are set; using LOTUS_SIGN_WALLET_PASSWORD");
+            }
+            wallet.password = Some(password);
         }
     }
 
-    fn config_path() -> PathBuf {
+    pub fn config_path() -> PathBuf {
         PathBuf::from("config.toml")
     }
+
+    /// 将命名配置集覆盖到当前配置：host/path 直接替换，可选字段仅在配置集中设置时替换
+    pub fn apply_profile(&mut self, name: &str) -> Result<()> {
+        let profile = self.profiles
+            .as_ref()
+            .and_then(|p| p.get(name))
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("profile not found: {} (see 'config list-profiles')", name))?;
+
+        if let Some(lotus) = profile.lotus {
+            self.lotus.host = lotus.host;
+            // 备用节点与主节点属于同一网络，随 host 一起替换
+            self.lotus.fallback_hosts = lotus.fallback_hosts;
+            if lotus.recovery_interval_secs.is_some() {
+                self.lotus.recovery_interval_secs = lotus.recovery_interval_secs;
+            }
+            if lotus.token.is_some() {
+                self.lotus.token = lotus.token;
+            }
+            if lotus.wait_poll_interval_secs.is_some() {
+                self.lotus.wait_poll_interval_secs = lotus.wait_poll_interval_secs;
+            }
+            if lotus.min_send_attofil.is_some() {
+                self.lotus.min_send_attofil = lotus.min_send_attofil;
+            }
+            if lotus.network.is_some() {
+                self.lotus.network = lotus.network;
+            }
+        }
+        if let Some(database) = profile.database {
+            self.database = database;
+        }
+        if let Some(wallet) = profile.wallet {
+            let base = self.wallet.get_or_insert(WalletConfig { password: None, master_seed_hex: None, use_keyring: false });
+            if wallet.password.is_some() {
+                base.password = wallet.password;
+            }
+            if wallet.master_seed_hex.is_some() {
+                base.master_seed_hex = wallet.master_seed_hex;
+            }
+            if wallet.use_keyring {
+                base.use_keyring = true;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for Config {
@@ -50,20 +273,222 @@ impl Default for Config {
             lotus: LotusConfig {
                 host: "https://api.node.glif.io/rpc/v0".to_string(),
                 token: None,
+                wait_poll_interval_secs: None,
+                min_send_attofil: None,
+                fallback_hosts: Vec::new(),
+                recovery_interval_secs: None,
+                network: None,
             },
             database: DatabaseConfig {
                 path: "lotus_sign.db".to_string(),
+                encrypt: false,
+                cipher_key_env: default_cipher_key_env(),
+                cipher_algorithm: CipherAlgorithm::default(),
             },
             wallet: None,
+            gas: None,
+            profiles: None,
+            retry: None,
+            notifications: None,
+            cache: None,
+            dry_run: false,
+            no_retry: false,
+            keyring: false,
+            cli_password: None,
+            session_key: None,
         }
     }
 }
 
+/// 未配置密码时交互输入的密码，每个进程只询问一次
+static PROMPTED_PASSWORD: OnceLock<String> = OnceLock::new();
+/// 从密钥环取得（或首次使用时保存到密钥环）的密码；None 表示回退到配置文件，每个进程只访问一次密钥环
+static KEYRING_PASSWORD: OnceLock<Option<String>> = OnceLock::new();
+
+/// 钱包密码环境变量；优先于密钥环与配置文件（包括 LOTUS_SIGN_WALLET_PASSWORD）
+const PASSWORD_ENV: &str = "LOTUS_SIGN_PASSWORD";
+/// Docker secret 的默认挂载路径（`docker secret create lotus_sign_password ...`）
+const DOCKER_SECRET_PASSWORD_FILE: &str = "/run/secrets/lotus_sign_password";
+/// 环境变量密码的警告每个进程只打印一次
+static PASSWORD_ENV_WARNING: Once = Once::new();
+
+/// OS 密钥环中保存钱包密码的服务名与账户名
+const KEYRING_SERVICE: &str = "lotus-sign";
+const KEYRING_ACCOUNT: &str = "wallet-password";
+
 impl Config {
-    pub fn get_password(&self) -> String {
-        self.wallet
-            .as_ref()
-            .and_then(|w| w.password.clone())
-            .unwrap_or_default()
+    fn configured_password(&self) -> Option<String> {
+        self.wallet.as_ref().and_then(|w| w.password.clone())
+    }
+
+    /// 显式提供的密码，优先级：`--password` / `--password-file` > LOTUS_SIGN_PASSWORD > Docker secret
+    fn explicit_password(&self) -> Option<String> {
+        if let Some(password) = &self.cli_password {
+            return Some(password.clone());
+        }
+        if let Some(password) = std::env::var(PASSWORD_ENV).ok().filter(|p| !p.is_empty()) {
+            PASSWORD_ENV_WARNING.call_once(|| {
+                tracing::warn!(
+                    "using the wallet password from {}; storing secrets in environment variables is discouraged in production, prefer --password-file or Docker secrets",
+                    PASSWORD_ENV
+                );
+            });
+            return Some(password);
+        }
+        let secret = Path::new(DOCKER_SECRET_PASSWORD_FILE);
+        if secret.exists() {
+            match read_password_file(secret) {
+                Ok(password) => return Some(password),
+                Err(e) => eprintln!("WARNING: {:#}", e),
+            }
+        }
+        None
+    }
+
+    /// 显示地址使用的网络前缀
+    pub fn network(&self) -> Network {
+        self.lotus.network.unwrap_or_default()
+    }
+
+    /// 是否通过 OS 密钥环获取密码（`--keyring` 或 wallet.use_keyring）
+    pub fn use_keyring(&self) -> bool {
+        self.keyring || self.wallet.as_ref().is_some_and(|w| w.use_keyring)
+    }
+
+    /// 读取密钥环中的密码；条目不存在时在终端中询问并保存（`confirm` 时输入两次）。
+    /// 密钥环不可用或无法询问时返回 None，由调用方回退到配置文件
+    fn keyring_password(&self, confirm: bool) -> Option<String> {
+        KEYRING_PASSWORD
+            .get_or_init(|| match keyring_get_password() {
+                Ok(Some(password)) => Some(password),
+                Ok(None) => {
+                    let prompt = "Wallet password (will be saved to the OS keyring): ";
+                    let prompted = if confirm { prompt_password_confirm(prompt) } else { prompt_password(prompt) };
+                    let password = match prompted {
+                        Ok(password) => password,
+                        Err(e) => {
+                            eprintln!("WARNING: no wallet password in the OS keyring: {:#}; falling back to config", e);
+                            return None;
+                        }
+                    };
+                    match keyring_set_password(&password) {
+                        Ok(()) => eprintln!("Saved wallet password to the OS keyring"),
+                        Err(e) => eprintln!("WARNING: cannot save the wallet password to the OS keyring: {:#}", e),
+                    }
+                    Some(password)
+                }
+                Err(e) => {
+                    eprintln!("WARNING: OS keyring is unavailable ({:#}); falling back to config", e);
+                    None
+                }
+            })
+            .clone()
+    }
+
+    /// 不询问即可得到的解密密码：命令行 / 环境变量 / Docker secret、密钥环（启用时）、配置，
+    /// 或本进程已输入过的密码
+    pub fn get_password(&self) -> Option<String> {
+        if let Some(password) = self.explicit_password() {
+            return Some(password);
+        }
+        if self.use_keyring() {
+            if let Some(password) = self.keyring_password(false) {
+                return Some(password);
+            }
+        }
+        self.configured_password().or_else(|| PROMPTED_PASSWORD.get().cloned())
+    }
+
+    /// 解密密钥使用的密码：`get_password` 没有结果时在终端中询问，每个进程只询问一次
+    pub fn get_password_or_prompt(&self) -> Result<String> {
+        if let Some(password) = self.get_password() {
+            return Ok(password);
+        }
+        let password = prompt_password("Wallet password: ")?;
+        Ok(PROMPTED_PASSWORD.get_or_init(|| password).clone())
+    }
+
+    /// 解密私钥的加密密钥：会话已派生时直接复用，否则由 `get_password_or_prompt` 的密码派生
+    pub fn enc_key(&self) -> Result<zeroize::Zeroizing<[u8; 32]>> {
+        match &self.session_key {
+            Some(key) => Ok(key.clone()),
+            None => Ok(crate::crypto::derive_key(&self.get_password_or_prompt()?)),
+        }
+    }
+
+    /// 加密新密钥使用的密码：未配置时要求在终端中输入两次，避免输错后无法解密
+    pub fn get_new_password(&self) -> Result<String> {
+        if let Some(password) = self.explicit_password() {
+            return Ok(password);
+        }
+        if self.use_keyring() {
+            if let Some(password) = self.keyring_password(true) {
+                return Ok(password);
+            }
+        }
+        if let Some(password) = self.get_password() {
+            return Ok(password);
+        }
+        let password = prompt_password_confirm("New wallet password: ")?;
+        Ok(PROMPTED_PASSWORD.get_or_init(|| password).clone())
+    }
+
+    /// 生效的重试策略；`--no-retry` 时不重试
+    pub fn retry_policy(&self) -> RetryPolicy {
+        let mut policy = self.retry.clone().unwrap_or_default();
+        if self.no_retry {
+            policy.max_retries = 0;
+        }
+        policy
+    }
+
+    pub fn recovery_interval(&self) -> Duration {
+        Duration::from_secs(self.lotus.recovery_interval_secs.unwrap_or(60))
+    }
+
+    pub fn miner_info_ttl_secs(&self) -> u64 {
+        self.cache.as_ref().and_then(|c| c.miner_info_ttl_secs).unwrap_or(60)
+    }
+
+    pub fn wait_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.lotus.wait_poll_interval_secs.unwrap_or(30))
+    }
+}
+
+/// 读取密码文件的第一行（去掉换行符），如 Docker secret
+pub fn read_password_file(path: &Path) -> Result<String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("cannot read password file {}: {}", path.display(), e))?;
+    let password = content.lines().next().unwrap_or_default();
+    if password.is_empty() {
+        anyhow::bail!("password file {} is empty", path.display());
+    }
+    Ok(password.to_string())
+}
+
+fn keyring_entry() -> Result<keyring::Entry> {
+    Ok(keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT)?)
+}
+
+/// 读取 OS 密钥环中的钱包密码；条目不存在时为 None，密钥环不可用时报错
+pub fn keyring_get_password() -> Result<Option<String>> {
+    match keyring_entry()?.get_password() {
+        Ok(password) => Ok(Some(password)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 保存（覆盖）OS 密钥环中的钱包密码
+pub fn keyring_set_password(password: &str) -> Result<()> {
+    Ok(keyring_entry()?.set_password(password)?)
+}
+
+/// 删除 OS 密钥环中的钱包密码；条目不存在时返回 false
+pub fn keyring_delete_password() -> Result<bool> {
+    match keyring_entry()?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
     }
 }
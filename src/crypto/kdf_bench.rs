@@ -0,0 +1,51 @@
+//! Argon2id 耗时基准，用于给出 KDF 参数建议；依赖计时器，仅原生构建提供
+
+use std::time::Instant;
+
+/// 基准测试使用的 Argon2id 迭代次数与并行度，只搜索内存参数
+const KDF_BENCH_ITERATIONS: u32 = 3;
+const KDF_BENCH_PARALLELISM: u32 = 1;
+/// 内存参数搜索范围（MiB）
+const KDF_BENCH_MIN_MEMORY_MIB: u32 = 8;
+const KDF_BENCH_MAX_MEMORY_MIB: u32 = 1024;
+/// 每组参数运行次数，取中位数
+const KDF_BENCH_RUNS: usize = 5;
+
+/// 二分搜索使 Argon2id 耗时约为 `target_ms` 的参数
+///
+/// 返回 `(memory_kib, iterations, parallelism)`。固定 t=3、p=1，只调整内存：
+/// 内存越大，GPU/ASIC 暴力破解的成本越高。200-500ms 是安全性与使用体验之间较合理的取值
+pub fn benchmark_kdf(target_ms: u64) -> (u32, u32, u32) {
+    let (mut lo, mut hi) = (KDF_BENCH_MIN_MEMORY_MIB, KDF_BENCH_MAX_MEMORY_MIB);
+    while lo < hi {
+        let mid = lo + (hi - lo).div_ceil(2);
+        if kdf_time_ms(mid * 1024, KDF_BENCH_ITERATIONS, KDF_BENCH_PARALLELISM) <= target_ms {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    (lo * 1024, KDF_BENCH_ITERATIONS, KDF_BENCH_PARALLELISM)
+}
+
+/// 运行 Argon2id 多次，返回耗时中位数（毫秒）
+pub fn kdf_time_ms(memory_kib: u32, iterations: u32, parallelism: u32) -> u64 {
+    use argon2::{Algorithm, Argon2, Params, Version};
+    let params = Params::new(memory_kib, iterations, parallelism, Some(32))
+        .expect("benchmark parameters are within Argon2 limits");
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+    let salt = [0u8; 16];
+    let mut out = [0u8; 32];
+
+    let mut samples: Vec<u64> = (0..KDF_BENCH_RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            argon2
+                .hash_password_into(b"lotus-sign-kdf-benchmark", &salt, &mut out)
+                .expect("benchmark parameters are within Argon2 limits");
+            start.elapsed().as_millis() as u64
+        })
+        .collect();
+    samples.sort_unstable();
+    samples[samples.len() / 2]
+}
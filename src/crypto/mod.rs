@@ -1,16 +1,80 @@
 use aes_gcm::{
-    aead::{Aead, KeyInit},
-    Aes256Gcm, Nonce,
+    aead::{self, Aead, AeadCore, KeyInit},
+    Aes256Gcm,
 };
 use anyhow::{anyhow, Result};
+use chacha20poly1305::ChaCha20Poly1305;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "native")]
+mod kdf_bench;
+#[cfg(feature = "native")]
+pub use kdf_bench::{benchmark_kdf, kdf_time_ms};
 
 const NONCE_SIZE: usize = 12;
+/// AEAD 认证标签长度；密文至少比明文长 NONCE_SIZE + TAG_SIZE 字节
+const TAG_SIZE: usize = 16;
+
+/// 私钥加密使用的 AEAD 算法；两者均为 256 位密钥、96 位 nonce，密文格式同为 nonce || 密文 || 标签
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CipherAlgorithm {
+    /// 默认；有 AES 硬件加速（AES-NI、ARMv8 Crypto 扩展）时最快
+    #[default]
+    Aes256Gcm,
+    /// 纯软件实现也很快，适合没有 AES 加速的设备（如用作离线签名机的树莓派）
+    ChaCha20Poly1305,
+}
+
+impl CipherAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CipherAlgorithm::Aes256Gcm => "aes256gcm",
+            CipherAlgorithm::ChaCha20Poly1305 => "chacha20poly1305",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "aes256gcm" => Ok(CipherAlgorithm::Aes256Gcm),
+            "chacha20poly1305" => Ok(CipherAlgorithm::ChaCha20Poly1305),
+            _ => Err(anyhow!("unknown cipher algorithm: {}", s)),
+        }
+    }
+}
+
+/// 以指定算法加密
+pub fn encrypt(data: &[u8], key: &[u8; 32], cipher: CipherAlgorithm) -> Result<Vec<u8>> {
+    match cipher {
+        CipherAlgorithm::Aes256Gcm => seal::<Aes256Gcm>(data, key),
+        CipherAlgorithm::ChaCha20Poly1305 => encrypt_chacha(data, key),
+    }
+}
+
+/// 以加密时使用的算法解密；明文（私钥）在释放时自动清零
+pub fn decrypt(data: &[u8], key: &[u8; 32], cipher: CipherAlgorithm) -> Result<Zeroizing<Vec<u8>>> {
+    match cipher {
+        CipherAlgorithm::Aes256Gcm => open::<Aes256Gcm>(data, key),
+        CipherAlgorithm::ChaCha20Poly1305 => decrypt_chacha(data, key),
+    }
+}
+
+pub fn encrypt_chacha(data: &[u8], key: &[u8; 32]) -> Result<Vec<u8>> {
+    seal::<ChaCha20Poly1305>(data, key)
+}
+
+pub fn decrypt_chacha(data: &[u8], key: &[u8; 32]) -> Result<Zeroizing<Vec<u8>>> {
+    open::<ChaCha20Poly1305>(data, key)
+}
 
-pub fn encrypt(data: &[u8], key: &[u8; 32]) -> Result<Vec<u8>> {
-    let cipher = Aes256Gcm::new(key.into());
+/// 随机生成 96 位 nonce 并置于密文之前
+fn seal<C: Aead + KeyInit>(data: &[u8], key: &[u8; 32]) -> Result<Vec<u8>> {
+    let cipher = C::new_from_slice(key).map_err(|e| anyhow!("invalid key: {}", e))?;
     let nonce_bytes: [u8; NONCE_SIZE] = rand::thread_rng().gen();
-    let nonce = Nonce::from_slice(&nonce_bytes);
+    let nonce = aead::Nonce::<C>::from_slice(&nonce_bytes);
 
     let ciphertext = cipher
         .encrypt(nonce, data)
@@ -21,23 +85,80 @@ pub fn encrypt(data: &[u8], key: &[u8; 32]) -> Result<Vec<u8>> {
     Ok(result)
 }
 
-pub fn decrypt(data: &[u8], key: &[u8; 32]) -> Result<Vec<u8>> {
-    if data.len() < NONCE_SIZE {
+fn open<C: Aead + AeadCore + KeyInit>(data: &[u8], key: &[u8; 32]) -> Result<Zeroizing<Vec<u8>>> {
+    if data.len() < NONCE_SIZE + TAG_SIZE {
         return Err(anyhow!("data too short"));
     }
 
     let (nonce_bytes, ciphertext) = data.split_at(NONCE_SIZE);
-    let cipher = Aes256Gcm::new(key.into());
-    let nonce = Nonce::from_slice(nonce_bytes);
+    let cipher = C::new_from_slice(key).map_err(|e| anyhow!("invalid key: {}", e))?;
+    let nonce = aead::Nonce::<C>::from_slice(nonce_bytes);
 
     cipher
         .decrypt(nonce, ciphertext)
+        .map(Zeroizing::new)
         .map_err(|e| anyhow!("decryption failed: {}", e))
 }
 
-pub fn derive_key(password: &str) -> [u8; 32] {
+pub fn derive_key(password: &str) -> Zeroizing<[u8; 32]> {
     use sha2::{Sha256, Digest};
     let mut hasher = Sha256::new();
     hasher.update(password.as_bytes());
-    hasher.finalize().into()
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(&hasher.finalize());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn cipher_strategy() -> impl Strategy<Value = CipherAlgorithm> {
+        prop_oneof![Just(CipherAlgorithm::Aes256Gcm), Just(CipherAlgorithm::ChaCha20Poly1305)]
+    }
+
+    proptest! {
+        #[test]
+        fn encrypt_decrypt_round_trip(data in prop::collection::vec(any::<u8>(), 0..512), key in any::<[u8; 32]>(), cipher in cipher_strategy()) {
+            let encrypted = encrypt(&data, &key, cipher).unwrap();
+            let decrypted = decrypt(&encrypted, &key, cipher).unwrap();
+            prop_assert_eq!(decrypted.as_slice(), data.as_slice());
+        }
+
+        #[test]
+        fn wrong_key_fails(data in prop::collection::vec(any::<u8>(), 0..512), key in any::<[u8; 32]>(), other in any::<[u8; 32]>(), cipher in cipher_strategy()) {
+            prop_assume!(key != other);
+            let encrypted = encrypt(&data, &key, cipher).unwrap();
+            prop_assert!(decrypt(&encrypted, &other, cipher).is_err());
+        }
+
+        #[test]
+        fn flipped_byte_fails(data in prop::collection::vec(any::<u8>(), 0..512), key in any::<[u8; 32]>(), cipher in cipher_strategy(), index in any::<prop::sample::Index>(), mask in 1u8..) {
+            let mut encrypted = encrypt(&data, &key, cipher).unwrap();
+            let i = index.index(encrypted.len());
+            encrypted[i] ^= mask;
+            prop_assert!(decrypt(&encrypted, &key, cipher).is_err());
+        }
+
+        #[test]
+        fn ciphertext_includes_nonce_and_tag(data in prop::collection::vec(any::<u8>(), 0..512), key in any::<[u8; 32]>(), cipher in cipher_strategy()) {
+            let encrypted = encrypt(&data, &key, cipher).unwrap();
+            prop_assert!(encrypted.len() >= data.len() + NONCE_SIZE + TAG_SIZE);
+        }
+
+        #[test]
+        fn derive_key_is_deterministic(password in ".*") {
+            prop_assert_eq!(derive_key(&password), derive_key(&password));
+        }
+    }
+
+    #[test]
+    fn derive_key_edge_inputs() {
+        let long = "a".repeat(1000);
+        for password in ["", "密码🔑пароль", long.as_str()] {
+            assert_eq!(derive_key(password), derive_key(password));
+        }
+        assert_ne!(derive_key(""), derive_key(&long));
+    }
 }
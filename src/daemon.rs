@@ -0,0 +1,257 @@
+//! 远程签名守护进程：以 REST API 提供签名与广播
+//!
+//! 接口（均需 `Authorization: Bearer <token>`）：
+//! - `POST /sign`：Message JSON → SignedMessage JSON
+//! - `POST /push`：SignedMessage JSON → Cid JSON
+//! - `POST /sign-push`：`{from, to, value, method, params}` → 构建、签名并广播，返回 Cid JSON
+//! - `GET /wallet`：钱包地址列表
+//! - `GET /wallet/:address/balance`：地址余额
+//! - `GET /chain/head`：最新链头高度，由 WebSocket 订阅 ChainNotify 实时更新，无需轮询
+
+use crate::chain::{format_fil, Message, SignedMessage};
+use crate::config::Config;
+use crate::db::Store;
+use crate::rpc::{Cid, LotusWsClient};
+use crate::service::Executor;
+use anyhow::Result;
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// ChainNotify 订阅断开后重连前的等待时间
+const HEAD_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+struct DaemonState {
+    executor: Executor<'static>,
+    store: &'static Store,
+    token: String,
+    /// 最新链头高度；尚未收到 ChainNotify 推送时为 None
+    head: watch::Receiver<Option<i64>>,
+}
+
+type SharedState = Arc<DaemonState>;
+
+/// 启动守护进程，直到 Ctrl-C 退出
+pub async fn serve(cfg: &Config, store: &Store, bind: &str, token: String) -> Result<()> {
+    if token.is_empty() {
+        anyhow::bail!("daemon token must not be empty");
+    }
+    if cfg.dry_run {
+        anyhow::bail!("--dry-run is not supported in daemon mode");
+    }
+
+    // 守护进程常驻，Wallet 借用的 Store 需在整个进程生命周期内有效
+    let store: &'static Store = Box::leak(Box::new(store.clone()));
+    let executor = Executor::new_async(cfg, store).await?;
+    let (head_tx, head) = watch::channel(None);
+    let watcher = tokio::spawn(watch_chain_head(cfg.lotus.host.clone(), cfg.lotus.token.clone(), head_tx));
+    let state = Arc::new(DaemonState { executor, store, token, head });
+
+    let app = Router::new()
+        .route("/sign", post(sign))
+        .route("/push", post(push))
+        .route("/sign-push", post(sign_push))
+        .route("/wallet", get(list_wallet))
+        .route("/wallet/:address/balance", get(wallet_balance))
+        .route("/chain/head", get(chain_head))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .layer(middleware::from_fn(log_request))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    tracing::info!(addr = %listener.local_addr()?, "daemon listening");
+    eprintln!("lotus-sign daemon listening on {}", listener.local_addr()?);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    watcher.abort();
+    Ok(())
+}
+
+/// 通过 WebSocket 订阅 ChainNotify 跟踪链头高度，连接断开后重连
+async fn watch_chain_head(host: String, token: Option<String>, head: watch::Sender<Option<i64>>) {
+    loop {
+        if let Err(e) = follow_chain_notify(&host, token.as_deref(), &head).await {
+            tracing::warn!(error = %e, "chain head subscription failed; reconnecting");
+        }
+        tokio::time::sleep(HEAD_RECONNECT_DELAY).await;
+    }
+}
+
+async fn follow_chain_notify(host: &str, token: Option<&str>, head: &watch::Sender<Option<i64>>) -> Result<()> {
+    let client = LotusWsClient::connect(host, token).await?;
+    let mut changes = std::pin::pin!(client.subscribe_chain_notify().await?);
+    tracing::info!("subscribed to ChainNotify");
+    while let Some(batch) = changes.next().await {
+        if let Some(height) = applied_height(&batch?) {
+            head.send_replace(Some(height));
+        }
+    }
+    anyhow::bail!("ChainNotify subscription closed")
+}
+
+/// 一组 HeadChange 中 current / apply 项的最高高度；只有 revert 时返回 None，等待随后的 apply
+fn applied_height(changes: &serde_json::Value) -> Option<i64> {
+    changes
+        .as_array()?
+        .iter()
+        .filter(|c| c["Type"] != "revert")
+        .filter_map(|c| c["Val"]["Height"].as_i64())
+        .max()
+}
+
+/// 接口错误：以 `{"error": "..."}` 返回
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn bad_request(e: impl std::fmt::Display) -> Self {
+        Self(StatusCode::BAD_REQUEST, e.to_string())
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if self.0.is_server_error() {
+            tracing::warn!(error = %self.1, "daemon request failed");
+        }
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+async fn authorize(State(state): State<SharedState>, req: Request, next: Next) -> Response {
+    let provided = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), state.token.as_bytes()) => next.run(req).await,
+        _ => ApiError(StatusCode::UNAUTHORIZED, "missing or invalid bearer token".to_string()).into_response(),
+    }
+}
+
+/// 逐字节比较全部内容，耗时与匹配前缀长度无关
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn log_request(req: Request, next: Next) -> Response {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let started = Instant::now();
+    let response = next.run(req).await;
+    tracing::info!(
+        %method,
+        path,
+        status = response.status().as_u16(),
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "daemon request"
+    );
+    response
+}
+
+async fn sign(State(state): State<SharedState>, Json(msg): Json<Message>) -> Result<Json<SignedMessage>, ApiError> {
+    let from = msg.from.to_string();
+    let signed = state.executor.sign_message(msg, &from).await?;
+    Ok(Json(signed))
+}
+
+async fn push(State(state): State<SharedState>, Json(signed): Json<SignedMessage>) -> Result<Json<Cid>, ApiError> {
+    Ok(Json(state.executor.api.mpool_push(&signed).await?))
+}
+
+#[derive(Deserialize)]
+struct SignPushRequest {
+    from: String,
+    to: String,
+    /// 金额（attoFIL）
+    #[serde(default = "zero")]
+    value: String,
+    #[serde(default)]
+    method: u64,
+    /// base64 编码的参数
+    #[serde(default)]
+    params: Option<String>,
+}
+
+fn zero() -> String {
+    "0".to_string()
+}
+
+async fn sign_push(State(state): State<SharedState>, Json(req): Json<SignPushRequest>) -> Result<Json<Cid>, ApiError> {
+    let params = match req.params.as_deref() {
+        Some(p) => STANDARD.decode(p).map_err(ApiError::bad_request)?,
+        None => vec![],
+    };
+    let msg = state.executor.build_message(&req.from, &req.to, req.method, &req.value, params).await?;
+    // serve 拒绝 --dry-run，因此消息总会被广播
+    let cid = state.executor.sign_and_push_message(msg).await?
+        .ok_or_else(|| anyhow::anyhow!("message was not pushed (dry run)"))?;
+    Ok(Json(cid))
+}
+
+#[derive(Serialize)]
+struct WalletEntry {
+    address: String,
+    key_type: String,
+    label: Option<String>,
+    watch_only: bool,
+}
+
+async fn list_wallet(State(state): State<SharedState>) -> Result<Json<Vec<WalletEntry>>, ApiError> {
+    let keys = state.store.list_keys_async().await?;
+    let entries = keys
+        .into_iter()
+        .map(|k| WalletEntry { address: k.address, key_type: k.key_type, label: k.label, watch_only: k.is_watch_only })
+        .collect();
+    Ok(Json(entries))
+}
+
+#[derive(Serialize)]
+struct BalanceResponse {
+    address: String,
+    /// attoFIL
+    balance: String,
+    balance_fil: String,
+}
+
+async fn wallet_balance(
+    State(state): State<SharedState>,
+    Path(address): Path<String>,
+) -> Result<Json<BalanceResponse>, ApiError> {
+    crate::chain::Address::from_string(&address).map_err(ApiError::bad_request)?;
+    let balance = state.executor.api.wallet_balance(&address).await?;
+    Ok(Json(BalanceResponse {
+        address,
+        balance: balance.to_string(),
+        balance_fil: format_fil(&balance.0),
+    }))
+}
+
+#[derive(Serialize)]
+struct HeadResponse {
+    height: i64,
+}
+
+async fn chain_head(State(state): State<SharedState>) -> Result<Json<HeadResponse>, ApiError> {
+    let height = (*state.head.borrow())
+        .ok_or_else(|| ApiError(StatusCode::SERVICE_UNAVAILABLE, "chain head not received yet".to_string()))?;
+    Ok(Json(HeadResponse { height }))
+}
@@ -2,4 +2,4 @@ mod store;
 mod models;
 
 pub use store::Store;
-pub use models::WalletKey;
+pub use models::{WalletKey, SigningLogEntry, SigningLogAggregate, MessageRecord, AddressBookEntry, SigningAuditEntry, ScheduledJob, BalanceThreshold, WorkerRotation};
@@ -1,4 +1,7 @@
+use crate::crypto::CipherAlgorithm;
 use chrono::{DateTime, Utc};
+use num_bigint::BigInt;
+use serde::Serialize;
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -9,6 +12,15 @@ pub struct WalletKey {
     pub encrypted_key: Vec<u8>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub label: Option<String>,
+    pub note: Option<String>,
+    /// 只读地址：仅由公钥导入，encrypted_key 为空，不能签名
+    pub is_watch_only: bool,
+    /// 归档（软删除）时间；已归档的密钥不再用于签名
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// 加密 encrypted_key 使用的算法
+    pub cipher: CipherAlgorithm,
 }
 
 impl WalletKey {
@@ -21,6 +33,129 @@ impl WalletKey {
             encrypted_key,
             created_at: now,
             updated_at: now,
+            expires_at: None,
+            label: None,
+            note: None,
+            is_watch_only: false,
+            deleted_at: None,
+            cipher: CipherAlgorithm::default(),
         }
     }
+
+    /// 只读地址，不保存任何私钥材料
+    pub fn watch_only(address: String, key_type: String) -> Self {
+        Self { is_watch_only: true, ..Self::new(address, key_type, Vec::new()) }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|t| t <= Utc::now())
+    }
+}
+
+/// 签名日志：每条签名并推送（或推送失败）的消息记录一行
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct SigningLogEntry {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub from_address: String,
+    pub to_address: String,
+    /// attoFIL 十进制字符串
+    pub value: String,
+    pub method: u64,
+    pub nonce: u64,
+    pub cid: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// 已成功推送的消息（messages 表），保留完整的 gas 参数与签名密钥类型
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageRecord {
+    pub id: i64,
+    pub cid: String,
+    pub from_addr: String,
+    pub to_addr: String,
+    /// attoFIL 十进制字符串
+    pub value: String,
+    pub method: u64,
+    pub gas_limit: i64,
+    pub gas_fee_cap: String,
+    pub gas_premium: String,
+    pub nonce: u64,
+    pub key_type: String,
+    pub pushed_at: DateTime<Utc>,
+}
+
+/// 私钥使用审计记录（signing_audit 表），普通命令不会删除
+#[derive(Debug, Clone, Serialize)]
+pub struct SigningAuditEntry {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub address: String,
+    pub key_type: String,
+    /// 被签名的消息 CID 字节（hex）
+    pub cid_hex: String,
+    pub to_addr: String,
+    pub value_attofil: String,
+    pub method: u64,
+    pub nonce: u64,
+    pub signed_by_pid: u32,
+}
+
+/// 地址簿条目：为常用目标地址起的名称
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressBookEntry {
+    pub id: i64,
+    pub name: String,
+    pub address: String,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 签名日志聚合统计
+#[derive(Debug, Clone, Default)]
+pub struct SigningLogAggregate {
+    pub total_messages: u64,
+    pub total_value_attofil: BigInt,
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub last_message_at: Option<DateTime<Utc>>,
+}
+
+/// 定时任务（scheduled_jobs 表）；command_json 为命令行参数的 JSON 数组（不含程序名）
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledJob {
+    pub id: i64,
+    pub cron_expr: String,
+    pub command_json: String,
+    pub last_run: Option<DateTime<Utc>>,
+    pub next_run: Option<DateTime<Utc>>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 余额告警阈值（balance_thresholds 表），由 `monitor` 检查
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceThreshold {
+    pub address: String,
+    pub threshold_attofil: String,
+    /// 为空时使用 notifications.webhook_url
+    pub alert_webhook_url: Option<String>,
+    /// 已告警的低于阈值时间；余额恢复后清空，避免每轮检查重复告警
+    pub below_since: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 进行中的 worker 更换（worker_rotations 表），供 `actor rotate-worker --resume-from-confirm` 继续执行
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerRotation {
+    pub miner: String,
+    pub new_worker: String,
+    /// 发送提议与确认消息的 owner 地址
+    pub owner: String,
+    pub propose_cid: String,
+    /// 可确认的纪元；提议上链并读取 StateMinerInfo 之前为空
+    pub change_epoch: Option<i64>,
+    pub created_at: DateTime<Utc>,
 }
@@ -1,88 +1,346 @@
 use anyhow::Result;
-use rusqlite::{Connection, params};
-use super::WalletKey;
-use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use super::{WalletKey, SigningLogEntry, SigningLogAggregate, MessageRecord, AddressBookEntry, SigningAuditEntry, ScheduledJob, BalanceThreshold, WorkerRotation};
+use crate::crypto::CipherAlgorithm;
+use crate::rpc::MinerInfo;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex, MutexGuard};
 
+const KEY_COLUMNS: &str = "id, address, key_type, encrypted_key, created_at, updated_at, expires_at, label, note, is_watch_only, deleted_at, cipher";
+const LOG_COLUMNS: &str = "id, timestamp, from_address, to_address, value, method, nonce, cid, success, error";
+const MESSAGE_COLUMNS: &str = "id, cid, from_addr, to_addr, value, method, gas_limit, gas_fee_cap, gas_premium, nonce, key_type, pushed_at";
+const ADDRESS_BOOK_COLUMNS: &str = "id, name, address, note, created_at";
+const AUDIT_COLUMNS: &str = "id, timestamp, address, key_type, cid_hex, to_addr, value_attofil, method, nonce, signed_by_pid";
+const SCHEDULE_COLUMNS: &str = "id, cron_expr, command_json, last_run, next_run, enabled, created_at";
+const THRESHOLD_COLUMNS: &str = "address, threshold_attofil, alert_webhook_url, below_since, created_at";
+const ROTATION_COLUMNS: &str = "miner, new_worker, owner, propose_cid, change_epoch, created_at";
+
+/// 数据库迁移，MIGRATIONS[i] 将数据库从版本 i 升级到 i + 1；
+/// 修改表结构时在末尾追加新的迁移，不要修改已发布的迁移
+const MIGRATIONS: &[&str] = &[
+    // 1：基线表结构
+    "CREATE TABLE IF NOT EXISTS wallet_keys (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        address TEXT NOT NULL UNIQUE,
+        key_type TEXT NOT NULL,
+        encrypted_key BLOB NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        expires_at TEXT,
+        label TEXT,
+        note TEXT
+    );
+    CREATE TABLE IF NOT EXISTS signing_log (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        timestamp TEXT NOT NULL,
+        from_address TEXT NOT NULL,
+        to_address TEXT NOT NULL,
+        value TEXT NOT NULL,
+        method INTEGER NOT NULL,
+        nonce INTEGER NOT NULL,
+        cid TEXT,
+        success INTEGER NOT NULL,
+        error TEXT
+    );
+    CREATE INDEX IF NOT EXISTS idx_signing_log_from_timestamp
+        ON signing_log (from_address, timestamp);
+    CREATE TABLE IF NOT EXISTS config (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS messages (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        cid TEXT NOT NULL,
+        from_addr TEXT NOT NULL,
+        to_addr TEXT NOT NULL,
+        value TEXT NOT NULL,
+        method INTEGER NOT NULL,
+        gas_limit INTEGER NOT NULL,
+        gas_fee_cap TEXT NOT NULL,
+        gas_premium TEXT NOT NULL,
+        nonce INTEGER NOT NULL,
+        key_type TEXT NOT NULL,
+        pushed_at TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_messages_from_pushed_at
+        ON messages (from_addr, pushed_at);
+    CREATE TABLE IF NOT EXISTS address_book (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        address TEXT NOT NULL,
+        note TEXT,
+        created_at TEXT NOT NULL
+    );",
+    // 2：私钥使用审计
+    "CREATE TABLE signing_audit (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        timestamp TEXT NOT NULL,
+        address TEXT NOT NULL,
+        key_type TEXT NOT NULL,
+        cid_hex TEXT NOT NULL,
+        to_addr TEXT NOT NULL,
+        value_attofil TEXT NOT NULL,
+        method INTEGER NOT NULL,
+        nonce INTEGER NOT NULL,
+        signed_by_pid INTEGER NOT NULL
+    );
+    CREATE INDEX idx_signing_audit_address_timestamp
+        ON signing_audit (address, timestamp);",
+    // 3：只读（仅公钥）地址
+    "ALTER TABLE wallet_keys ADD COLUMN is_watch_only INTEGER NOT NULL DEFAULT 0;",
+    // 4：软删除（归档）时间
+    "ALTER TABLE wallet_keys ADD COLUMN deleted_at TEXT;",
+    // 5：私钥加密算法（此前均为 AES-256-GCM）
+    "ALTER TABLE wallet_keys ADD COLUMN cipher TEXT NOT NULL DEFAULT 'aes256gcm';",
+    // 6：定时任务
+    "CREATE TABLE scheduled_jobs (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        cron_expr TEXT NOT NULL,
+        command_json TEXT NOT NULL,
+        last_run TEXT,
+        next_run TEXT,
+        enabled INTEGER NOT NULL DEFAULT 1,
+        created_at TEXT NOT NULL
+    );",
+    // 7：余额告警阈值；below_since 为已告警的低于阈值时间，余额恢复后清空
+    "CREATE TABLE balance_thresholds (
+        address TEXT PRIMARY KEY,
+        threshold_attofil TEXT NOT NULL,
+        alert_webhook_url TEXT,
+        below_since TEXT,
+        created_at TEXT NOT NULL
+    );",
+    // 8：进行中的 worker 更换（actor rotate-worker），确认后删除
+    "CREATE TABLE worker_rotations (
+        miner TEXT PRIMARY KEY,
+        new_worker TEXT NOT NULL,
+        owner TEXT NOT NULL,
+        propose_cid TEXT NOT NULL,
+        change_epoch INTEGER,
+        created_at TEXT NOT NULL
+    );",
+    // 9：StateMinerInfo 缓存（Lotus JSON 格式）
+    "CREATE TABLE miner_info_cache (
+        miner_addr TEXT PRIMARY KEY,
+        info_json TEXT NOT NULL,
+        cached_at TEXT NOT NULL
+    );",
+];
+
+/// 当前二进制支持的数据库版本
+const CURRENT_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// SQLite 存储；连接由 Arc<Mutex> 共享，克隆开销很小，
+/// 以便在 `spawn_blocking` 中执行数据库 I/O 而不阻塞 tokio 运行时
+#[derive(Clone)]
 pub struct Store {
-    conn: Connection,
+    conn: Arc<Mutex<Connection>>,
 }
 
 impl Store {
-    pub fn open(path: &str) -> Result<Self> {
+    /// 打开数据库并启用 WAL：允许一个写入者与多个读取者同时访问，
+    /// 多个进程（如定时任务）并发写入时最多等待 busy_timeout 而不是立即报 "database is locked"
+    ///
+    /// `cipher_key` 非空时按 SQLCipher 加密数据库打开，密钥必须在其他语句之前设置
+    pub fn open(path: &str, cipher_key: Option<&str>) -> Result<Self> {
         let conn = Connection::open(path)?;
-        let store = Self { conn };
+        if let Some(key) = cipher_key {
+            ensure_sqlcipher(&conn)?;
+            conn.pragma_update(None, "key", key)?;
+            // 密钥错误时首次读取才会失败
+            conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+                .map_err(|_| anyhow::anyhow!("cannot open {}: wrong database cipher key or the file is not encrypted", path))?;
+        }
+        conn.execute_batch(
+            "PRAGMA journal_mode=WAL;
+             PRAGMA busy_timeout=5000;
+             PRAGMA synchronous=NORMAL;
+             PRAGMA foreign_keys=ON;",
+        )?;
+        let store = Self { conn: Arc::new(Mutex::new(conn)) };
         store.migrate()?;
         Ok(store)
     }
 
+    /// 在阻塞线程池中打开数据库
+    pub async fn open_async(path: &str, cipher_key: Option<String>) -> Result<Self> {
+        let path = path.to_string();
+        tokio::task::spawn_blocking(move || Store::open(&path, cipher_key.as_deref())).await?
+    }
+
+    /// 用 `sqlcipher_export` 将整个数据库复制到新文件；`dest_key` 为 None 时写出未加密的数据库
+    pub fn export_to(&self, dest: &str, dest_key: Option<&str>) -> Result<()> {
+        let conn = self.conn();
+        ensure_sqlcipher(&conn)?;
+        conn.execute("ATTACH DATABASE ?1 AS export KEY ?2", params![dest, dest_key.unwrap_or("")])?;
+        let exported = conn.query_row("SELECT sqlcipher_export('export')", [], |_| Ok(()));
+        conn.execute("DETACH DATABASE export", [])?;
+        exported?;
+        Ok(())
+    }
+
+    /// 在阻塞线程池中执行任意 Store 操作
+    pub async fn run_blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Store) -> Result<T> + Send + 'static,
+    {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || f(&store)).await?
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 按 schema_version 依次执行尚未执行的迁移，每个迁移在单独的事务中完成
     fn migrate(&self) -> Result<()> {
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS wallet_keys (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                address TEXT NOT NULL UNIQUE,
-                key_type TEXT NOT NULL,
-                encrypted_key BLOB NOT NULL,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
-            )",
-            [],
+        self.conn().execute_batch(
+            "CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL)",
+        )?;
+        let version = match self.schema_version()? {
+            Some(version) => version,
+            None => {
+                // 引入版本表之前创建的数据库：先补齐旧版本逐步添加的列，再执行基线迁移（均为 IF NOT EXISTS）
+                if self.table_exists("wallet_keys")? {
+                    self.add_column_if_missing("wallet_keys", "expires_at", "TEXT")?;
+                    self.add_column_if_missing("wallet_keys", "label", "TEXT")?;
+                    self.add_column_if_missing("wallet_keys", "note", "TEXT")?;
+                }
+                self.conn().execute("INSERT INTO schema_version (version) VALUES (0)", [])?;
+                0
+            }
+        };
+
+        if version > CURRENT_VERSION {
+            anyhow::bail!(
+                "database schema version {} is newer than this lotus-sign supports ({}); please upgrade lotus-sign",
+                version,
+                CURRENT_VERSION
+            );
+        }
+
+        let mut conn = self.conn();
+        for (target, sql) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            let tx = conn.transaction()?;
+            tx.execute_batch(sql)?;
+            tx.execute("UPDATE schema_version SET version = ?1", params![target as u32 + 1])?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+
+    fn schema_version(&self) -> Result<Option<u32>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT version FROM schema_version LIMIT 1")?;
+        let mut rows = stmt.query([])?;
+        Ok(rows.next()?.map(|row| row.get(0)).transpose()?)
+    }
+
+    fn table_exists(&self, table: &str) -> Result<bool> {
+        let count: i64 = self.conn().query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            params![table],
+            |row| row.get(0),
         )?;
+        Ok(count > 0)
+    }
+
+    /// 为已存在的表补充新列（仅用于引入版本表之前创建的数据库）
+    fn add_column_if_missing(&self, table: &str, column: &str, decl: &str) -> Result<()> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+        let exists = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .filter_map(|name| name.ok())
+            .any(|name| name == column);
+
+        if !exists {
+            conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl),
+                [],
+            )?;
+        }
         Ok(())
     }
 
     pub fn insert_key(&self, key: &WalletKey) -> Result<i64> {
-        self.conn.execute(
-            "INSERT INTO wallet_keys (address, key_type, encrypted_key, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![
-                key.address,
-                key.key_type,
-                key.encrypted_key,
-                key.created_at.to_rfc3339(),
-                key.updated_at.to_rfc3339(),
-            ],
-        )?;
-        Ok(self.conn.last_insert_rowid())
+        let conn = self.conn();
+        insert_key_row(&conn, key)?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 在同一事务中插入多个密钥，任一失败则全部回滚
+    pub fn insert_keys(&self, keys: &[WalletKey]) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        for key in keys {
+            insert_key_row(&tx, key)?;
+        }
+        tx.commit()?;
+        Ok(())
     }
 
     pub fn get_key(&self, address: &str) -> Result<Option<WalletKey>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, address, key_type, encrypted_key, created_at, updated_at
-             FROM wallet_keys WHERE address = ?1"
-        )?;
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM wallet_keys WHERE address = ?1 AND deleted_at IS NULL",
+            KEY_COLUMNS
+        ))?;
 
         let mut rows = stmt.query(params![address])?;
 
         if let Some(row) = rows.next()? {
-            Ok(Some(WalletKey {
-                id: row.get(0)?,
-                address: row.get(1)?,
-                key_type: row.get(2)?,
-                encrypted_key: row.get(3)?,
-                created_at: row.get::<_, String>(4)?.parse().unwrap_or(Utc::now()),
-                updated_at: row.get::<_, String>(5)?.parse().unwrap_or(Utc::now()),
-            }))
+            Ok(Some(key_from_row(row)?))
         } else {
             Ok(None)
         }
     }
 
+    pub fn get_key_by_label(&self, label: &str) -> Result<Option<WalletKey>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM wallet_keys WHERE label = ?1 AND deleted_at IS NULL",
+            KEY_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query(params![label])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(key_from_row(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 按标签查找密钥，包括已归档的密钥（供 `wallet restore` 使用）；同名时优先返回最近归档的密钥
+    pub fn get_key_by_label_with_archived(&self, label: &str) -> Result<Option<WalletKey>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM wallet_keys WHERE label = ?1 ORDER BY deleted_at IS NULL, deleted_at DESC LIMIT 1",
+            KEY_COLUMNS
+        ))?;
+        Ok(stmt.query_row(params![label], key_from_row).optional()?)
+    }
+
+    /// 列出未归档的密钥
     pub fn list_keys(&self) -> Result<Vec<WalletKey>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, address, key_type, encrypted_key, created_at, updated_at
-             FROM wallet_keys ORDER BY id"
-        )?;
-
-        let rows = stmt.query_map([], |row| {
-            Ok(WalletKey {
-                id: row.get(0)?,
-                address: row.get(1)?,
-                key_type: row.get(2)?,
-                encrypted_key: row.get(3)?,
-                created_at: row.get::<_, String>(4)?.parse().unwrap_or(Utc::now()),
-                updated_at: row.get::<_, String>(5)?.parse().unwrap_or(Utc::now()),
-            })
-        })?;
+        self.query_keys("WHERE deleted_at IS NULL")
+    }
+
+    /// 列出全部密钥，包括已归档的
+    pub fn list_all_keys(&self) -> Result<Vec<WalletKey>> {
+        self.query_keys("")
+    }
+
+    fn query_keys(&self, filter: &str) -> Result<Vec<WalletKey>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM wallet_keys {} ORDER BY id",
+            KEY_COLUMNS, filter
+        ))?;
+
+        let rows = stmt.query_map([], key_from_row)?;
 
         let mut keys = Vec::new();
         for key in rows {
@@ -91,9 +349,9 @@ impl Store {
         Ok(keys)
     }
 
-    #[allow(dead_code)]
+    /// 地址是否已在数据库中，包括已归档的密钥（地址唯一，归档的密钥须先恢复或彻底删除才能重新导入）
     pub fn has_key(&self, address: &str) -> Result<bool> {
-        let count: i64 = self.conn.query_row(
+        let count: i64 = self.conn().query_row(
             "SELECT COUNT(*) FROM wallet_keys WHERE address = ?1",
             params![address],
             |row| row.get(0),
@@ -101,12 +359,751 @@ impl Store {
         Ok(count > 0)
     }
 
+    pub fn set_expiry(&self, address: &str, expires_at: Option<DateTime<Utc>>) -> Result<()> {
+        let updated = self.conn().execute(
+            "UPDATE wallet_keys SET expires_at = ?1, updated_at = ?2 WHERE address = ?3",
+            params![
+                expires_at.map(|t| t.to_rfc3339()),
+                Utc::now().to_rfc3339(),
+                address,
+            ],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("key not found: {}", address);
+        }
+        Ok(())
+    }
+
+    pub fn update_key_label(&self, address: &str, label: Option<&str>) -> Result<()> {
+        self.update_key_column(address, "label", label)
+    }
+
+    pub fn update_key_note(&self, address: &str, note: Option<&str>) -> Result<()> {
+        self.update_key_column(address, "note", note)
+    }
+
+    fn update_key_column(&self, address: &str, column: &str, value: Option<&str>) -> Result<()> {
+        let updated = self.conn().execute(
+            &format!("UPDATE wallet_keys SET {} = ?1, updated_at = ?2 WHERE address = ?3", column),
+            params![value, Utc::now().to_rfc3339(), address],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("key not found: {}", address);
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)]
+    pub fn update_key_encrypted(&self, address: &str, encrypted_key: &[u8]) -> Result<()> {
+        self.update_keys_encrypted(&[(address.to_string(), encrypted_key.to_vec())])
+    }
+
+    /// 在同一事务中更新多个密钥的密文，任一失败则全部回滚
+    pub fn update_keys_encrypted(&self, updates: &[(String, Vec<u8>)]) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        for (address, encrypted_key) in updates {
+            let updated = tx.execute(
+                "UPDATE wallet_keys SET encrypted_key = ?1, updated_at = ?2 WHERE address = ?3",
+                params![encrypted_key, now, address],
+            )?;
+            if updated == 0 {
+                anyhow::bail!("key not found: {}", address);
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 归档（软删除）密钥：保留密文以便 `restore_key` 恢复，归档后不再用于签名或列出
     pub fn delete_key(&self, address: &str) -> Result<()> {
-        self.conn.execute(
+        let now = Utc::now().to_rfc3339();
+        let updated = self.conn().execute(
+            "UPDATE wallet_keys SET deleted_at = ?1, updated_at = ?1 WHERE address = ?2 AND deleted_at IS NULL",
+            params![now, address],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("key not found: {}", address);
+        }
+        tracing::info!(address = %address, "key archived");
+        Ok(())
+    }
+
+    /// 恢复已归档的密钥
+    pub fn restore_key(&self, address: &str) -> Result<()> {
+        let updated = self.conn().execute(
+            "UPDATE wallet_keys SET deleted_at = NULL, updated_at = ?1 WHERE address = ?2 AND deleted_at IS NOT NULL",
+            params![Utc::now().to_rfc3339(), address],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("no archived key for {}", address);
+        }
+        tracing::info!(address = %address, "key restored");
+        Ok(())
+    }
+
+    /// 从数据库中彻底删除密钥（无论是否已归档），不可恢复
+    pub fn purge_key(&self, address: &str) -> Result<()> {
+        let deleted = self.conn().execute(
             "DELETE FROM wallet_keys WHERE address = ?1",
             params![address],
         )?;
+        if deleted == 0 {
+            anyhow::bail!("key not found: {}", address);
+        }
+        tracing::info!(address = %address, "key purged");
+        Ok(())
+    }
+
+    pub fn get_config(&self, key: &str) -> Result<Option<String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT value FROM config WHERE key = ?1")?;
+        let mut rows = stmt.query(params![key])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(row.get(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn set_config(&self, key: &str, value: &str) -> Result<()> {
+        self.conn().execute(
+            "INSERT INTO config (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_config(&self, key: &str) -> Result<()> {
+        self.conn().execute("DELETE FROM config WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+
+    pub fn insert_signing_log(&self, entry: &SigningLogEntry) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO signing_log (timestamp, from_address, to_address, value, method, nonce, cid, success, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entry.timestamp.to_rfc3339(),
+                entry.from_address,
+                entry.to_address,
+                entry.value,
+                entry.method as i64,
+                entry.nonce as i64,
+                entry.cid,
+                entry.success,
+                entry.error,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 按发送地址和起始时间查询签名日志，最新的在前
+    pub fn query_signing_log(
+        &self,
+        from: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<SigningLogEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM signing_log
+             WHERE (?1 IS NULL OR from_address = ?1) AND (?2 IS NULL OR timestamp >= ?2)
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?3",
+            LOG_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map(
+            params![from, since.map(|t| t.to_rfc3339()), limit as i64],
+            log_from_row,
+        )?;
+
+        let mut entries = Vec::new();
+        for entry in rows {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+
+    pub fn record_message(&self, msg: &MessageRecord) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO messages (cid, from_addr, to_addr, value, method, gas_limit, gas_fee_cap, gas_premium, nonce, key_type, pushed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                msg.cid,
+                msg.from_addr,
+                msg.to_addr,
+                msg.value,
+                msg.method as i64,
+                msg.gas_limit,
+                msg.gas_fee_cap,
+                msg.gas_premium,
+                msg.nonce as i64,
+                msg.key_type,
+                msg.pushed_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 查询已推送的消息，最新的在前；address 为 None 时返回所有地址
+    pub fn get_messages_by_address(&self, address: Option<&str>, limit: usize) -> Result<Vec<MessageRecord>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM messages
+             WHERE ?1 IS NULL OR from_addr = ?1
+             ORDER BY pushed_at DESC, id DESC
+             LIMIT ?2",
+            MESSAGE_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map(params![address, limit as i64], message_from_row)?;
+        let mut messages = Vec::new();
+        for msg in rows {
+            messages.push(msg?);
+        }
+        Ok(messages)
+    }
+
+    /// 删除指定 CID 的消息记录，返回是否有记录被删除
+    #[allow(dead_code)]
+    pub fn delete_message_by_cid(&self, cid: &str) -> Result<bool> {
+        let deleted = self.conn().execute("DELETE FROM messages WHERE cid = ?1", params![cid])?;
+        Ok(deleted > 0)
+    }
+
+    pub fn record_signing_event(&self, entry: &SigningAuditEntry) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO signing_audit (timestamp, address, key_type, cid_hex, to_addr, value_attofil, method, nonce, signed_by_pid)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entry.timestamp.to_rfc3339(),
+                entry.address,
+                entry.key_type,
+                entry.cid_hex,
+                entry.to_addr,
+                entry.value_attofil,
+                entry.method as i64,
+                entry.nonce as i64,
+                entry.signed_by_pid,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn query_signing_audit(
+        &self,
+        address: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<SigningAuditEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM signing_audit
+             WHERE (?1 IS NULL OR address = ?1) AND (?2 IS NULL OR timestamp >= ?2)
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?3",
+            AUDIT_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map(
+            params![address, since.map(|t| t.to_rfc3339()), limit as i64],
+            audit_from_row,
+        )?;
+
+        let mut entries = Vec::new();
+        for entry in rows {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+
+    /// 删除指定时间之前的审计记录，返回删除的条数
+    pub fn purge_signing_audit_before(&self, before: DateTime<Utc>) -> Result<usize> {
+        let deleted = self.conn().execute(
+            "DELETE FROM signing_audit WHERE timestamp < ?1",
+            params![before.to_rfc3339()],
+        )?;
+        Ok(deleted)
+    }
+
+    pub fn insert_address_book_entry(&self, name: &str, address: &str, note: Option<&str>) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO address_book (name, address, note, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![name, address, note, Utc::now().to_rfc3339()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn lookup_address_book_entry(&self, name: &str) -> Result<Option<AddressBookEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM address_book WHERE name = ?1",
+            ADDRESS_BOOK_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query(params![name])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(address_book_from_row(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 按名称解析地址簿中的地址
+    pub fn lookup_address_by_name(&self, name: &str) -> Result<Option<String>> {
+        Ok(self.lookup_address_book_entry(name)?.map(|e| e.address))
+    }
+
+    pub fn list_address_book(&self) -> Result<Vec<AddressBookEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM address_book ORDER BY name",
+            ADDRESS_BOOK_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([], address_book_from_row)?;
+        let mut entries = Vec::new();
+        for entry in rows {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+
+    /// 删除地址簿条目，返回是否有记录被删除
+    pub fn delete_address_book_entry(&self, name: &str) -> Result<bool> {
+        let deleted = self.conn().execute("DELETE FROM address_book WHERE name = ?1", params![name])?;
+        Ok(deleted > 0)
+    }
+
+    pub fn insert_scheduled_job(&self, cron_expr: &str, command_json: &str, next_run: Option<DateTime<Utc>>) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO scheduled_jobs (cron_expr, command_json, next_run, enabled, created_at) VALUES (?1, ?2, ?3, 1, ?4)",
+            params![cron_expr, command_json, next_run.map(|t| t.to_rfc3339()), Utc::now().to_rfc3339()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn list_scheduled_jobs(&self) -> Result<Vec<ScheduledJob>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM scheduled_jobs ORDER BY id",
+            SCHEDULE_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([], scheduled_job_from_row)?;
+        let mut jobs = Vec::new();
+        for job in rows {
+            jobs.push(job?);
+        }
+        Ok(jobs)
+    }
+
+    /// 启用或停用定时任务，同时更新下次执行时间；返回任务是否存在
+    pub fn set_scheduled_job_enabled(&self, id: i64, enabled: bool, next_run: Option<DateTime<Utc>>) -> Result<bool> {
+        let updated = self.conn().execute(
+            "UPDATE scheduled_jobs SET enabled = ?1, next_run = ?2 WHERE id = ?3",
+            params![enabled, next_run.map(|t| t.to_rfc3339()), id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// 记录一次执行（或跳过）后的时间；last_run 为 None 时保留原值
+    pub fn record_scheduled_run(&self, id: i64, last_run: Option<DateTime<Utc>>, next_run: Option<DateTime<Utc>>) -> Result<()> {
+        self.conn().execute(
+            "UPDATE scheduled_jobs SET last_run = COALESCE(?1, last_run), next_run = ?2 WHERE id = ?3",
+            params![last_run.map(|t| t.to_rfc3339()), next_run.map(|t| t.to_rfc3339()), id],
+        )?;
+        Ok(())
+    }
+
+    /// 删除定时任务，返回是否有记录被删除
+    pub fn delete_scheduled_job(&self, id: i64) -> Result<bool> {
+        let deleted = self.conn().execute("DELETE FROM scheduled_jobs WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    /// 设置（或替换）地址的余额告警阈值，并重置告警状态
+    pub fn set_balance_threshold(&self, address: &str, threshold_attofil: &str, alert_webhook_url: Option<&str>) -> Result<()> {
+        self.conn().execute(
+            "INSERT INTO balance_thresholds (address, threshold_attofil, alert_webhook_url, created_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(address) DO UPDATE SET
+                threshold_attofil = excluded.threshold_attofil,
+                alert_webhook_url = excluded.alert_webhook_url,
+                below_since = NULL",
+            params![address, threshold_attofil, alert_webhook_url, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_balance_thresholds(&self) -> Result<Vec<BalanceThreshold>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM balance_thresholds ORDER BY address",
+            THRESHOLD_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([], threshold_from_row)?;
+        let mut thresholds = Vec::new();
+        for threshold in rows {
+            thresholds.push(threshold?);
+        }
+        Ok(thresholds)
+    }
+
+    /// 记录（Some）或清除（None）低于阈值的告警时间
+    pub fn set_threshold_below_since(&self, address: &str, below_since: Option<DateTime<Utc>>) -> Result<()> {
+        self.conn().execute(
+            "UPDATE balance_thresholds SET below_since = ?1 WHERE address = ?2",
+            params![below_since.map(|t| t.to_rfc3339()), address],
+        )?;
+        Ok(())
+    }
+
+    /// 删除余额告警阈值，返回是否有记录被删除
+    pub fn delete_balance_threshold(&self, address: &str) -> Result<bool> {
+        let deleted = self.conn().execute("DELETE FROM balance_thresholds WHERE address = ?1", params![address])?;
+        Ok(deleted > 0)
+    }
+
+    pub fn insert_worker_rotation(&self, rotation: &WorkerRotation) -> Result<()> {
+        self.conn().execute(
+            "INSERT INTO worker_rotations (miner, new_worker, owner, propose_cid, change_epoch, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                rotation.miner,
+                rotation.new_worker,
+                rotation.owner,
+                rotation.propose_cid,
+                rotation.change_epoch,
+                rotation.created_at.to_rfc3339(),
+            ],
+        )?;
         Ok(())
     }
+
+    pub fn get_worker_rotation(&self, miner: &str) -> Result<Option<WorkerRotation>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM worker_rotations WHERE miner = ?1",
+            ROTATION_COLUMNS
+        ))?;
+        Ok(stmt.query_row(params![miner], rotation_from_row).optional()?)
+    }
+
+    pub fn set_worker_rotation_epoch(&self, miner: &str, change_epoch: i64) -> Result<()> {
+        self.conn().execute(
+            "UPDATE worker_rotations SET change_epoch = ?1 WHERE miner = ?2",
+            params![change_epoch, miner],
+        )?;
+        Ok(())
+    }
+
+    /// 删除 worker 更换记录，返回是否有记录被删除
+    pub fn delete_worker_rotation(&self, miner: &str) -> Result<bool> {
+        let deleted = self.conn().execute("DELETE FROM worker_rotations WHERE miner = ?1", params![miner])?;
+        Ok(deleted > 0)
+    }
+
+    /// 不超过 max_age_secs 秒的缓存；过期或无法解析（如字段变化）时返回 None
+    pub fn get_cached_miner_info(&self, miner: &str, max_age_secs: u64) -> Result<Option<MinerInfo>> {
+        let row: Option<(String, String)> = self.conn()
+            .query_row(
+                "SELECT info_json, cached_at FROM miner_info_cache WHERE miner_addr = ?1",
+                params![miner],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((info_json, cached_at)) = row else { return Ok(None) };
+        let Ok(cached_at) = cached_at.parse::<DateTime<Utc>>() else { return Ok(None) };
+        let age = Utc::now().signed_duration_since(cached_at).num_seconds();
+        if age < 0 || age as u64 > max_age_secs {
+            return Ok(None);
+        }
+        Ok(serde_json::from_str(&info_json).ok())
+    }
+
+    pub fn set_cached_miner_info(&self, miner: &str, info: &MinerInfo) -> Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO miner_info_cache (miner_addr, info_json, cached_at) VALUES (?1, ?2, ?3)",
+            params![miner, serde_json::to_string(info)?, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_cached_miner_info(&self, miner: &str) -> Result<()> {
+        self.conn().execute("DELETE FROM miner_info_cache WHERE miner_addr = ?1", params![miner])?;
+        Ok(())
+    }
+
+    /// 清空矿工信息缓存，返回删除的条目数
+    pub fn clear_miner_info_cache(&self) -> Result<usize> {
+        Ok(self.conn().execute("DELETE FROM miner_info_cache", [])?)
+    }
+
+    /// 汇总签名日志；金额以 TEXT 存储，在内存中求和以避免溢出
+    pub fn signing_log_aggregate(&self, from: Option<&str>) -> Result<SigningLogAggregate> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT value, success, timestamp FROM signing_log
+             WHERE ?1 IS NULL OR from_address = ?1",
+        )?;
+        let mut rows = stmt.query(params![from])?;
+
+        let mut agg = SigningLogAggregate::default();
+        while let Some(row) = rows.next()? {
+            let value: String = row.get(0)?;
+            let success: bool = row.get(1)?;
+            let timestamp: Option<DateTime<Utc>> = row.get::<_, String>(2)?.parse().ok();
+
+            agg.total_messages += 1;
+            if success {
+                agg.success_count += 1;
+                agg.total_value_attofil += value.parse::<num_bigint::BigInt>().unwrap_or_default();
+            } else {
+                agg.failure_count += 1;
+            }
+            if timestamp > agg.last_message_at {
+                agg.last_message_at = timestamp;
+            }
+        }
+        Ok(agg)
+    }
+}
+
+// 异步变体：在阻塞线程池中执行对应的同步方法，供守护进程等长时间运行的异步路径使用
+impl Store {
+    pub async fn get_key_async(&self, address: &str) -> Result<Option<WalletKey>> {
+        let address = address.to_string();
+        self.run_blocking(move |s| s.get_key(&address)).await
+    }
+
+    pub async fn list_keys_async(&self) -> Result<Vec<WalletKey>> {
+        self.run_blocking(|s| s.list_keys()).await
+    }
+
+    pub async fn insert_signing_log_async(&self, entry: SigningLogEntry) -> Result<i64> {
+        self.run_blocking(move |s| s.insert_signing_log(&entry)).await
+    }
+
+    pub async fn record_message_async(&self, msg: MessageRecord) -> Result<i64> {
+        self.run_blocking(move |s| s.record_message(&msg)).await
+    }
+
+    pub async fn record_signing_event_async(&self, entry: SigningAuditEntry) -> Result<i64> {
+        self.run_blocking(move |s| s.record_signing_event(&entry)).await
+    }
+
+    pub async fn get_cached_miner_info_async(&self, miner: &str, max_age_secs: u64) -> Result<Option<MinerInfo>> {
+        let miner = miner.to_string();
+        self.run_blocking(move |s| s.get_cached_miner_info(&miner, max_age_secs)).await
+    }
+
+    pub async fn set_cached_miner_info_async(&self, miner: &str, info: MinerInfo) -> Result<()> {
+        let miner = miner.to_string();
+        self.run_blocking(move |s| s.set_cached_miner_info(&miner, &info)).await
+    }
+
+    pub async fn delete_cached_miner_info_async(&self, miner: &str) -> Result<()> {
+        let miner = miner.to_string();
+        self.run_blocking(move |s| s.delete_cached_miner_info(&miner)).await
+    }
+}
+
+/// 普通 SQLite 会静默忽略 `PRAGMA key`，必须确认链接的是 SQLCipher
+fn ensure_sqlcipher(conn: &Connection) -> Result<()> {
+    let version: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+        .optional()?;
+    if version.is_none() {
+        anyhow::bail!("this build of lotus-sign does not include SQLCipher; rebuild with `--features sqlcipher`");
+    }
+    Ok(())
+}
+
+fn insert_key_row(conn: &Connection, key: &WalletKey) -> Result<()> {
+    conn.execute(
+        "INSERT INTO wallet_keys (address, key_type, encrypted_key, created_at, updated_at, expires_at, label, note, is_watch_only, cipher)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            key.address,
+            key.key_type,
+            key.encrypted_key,
+            key.created_at.to_rfc3339(),
+            key.updated_at.to_rfc3339(),
+            key.expires_at.map(|t| t.to_rfc3339()),
+            key.label,
+            key.note,
+            key.is_watch_only,
+            key.cipher.as_str(),
+        ],
+    )?;
+    tracing::info!(address = %key.address, key_type = %key.key_type, watch_only = key.is_watch_only, "key stored");
+    Ok(())
+}
+
+fn key_from_row(row: &Row) -> rusqlite::Result<WalletKey> {
+    Ok(WalletKey {
+        id: row.get(0)?,
+        address: row.get(1)?,
+        key_type: row.get(2)?,
+        encrypted_key: row.get(3)?,
+        created_at: row.get::<_, String>(4)?.parse().unwrap_or(Utc::now()),
+        updated_at: row.get::<_, String>(5)?.parse().unwrap_or(Utc::now()),
+        expires_at: row.get::<_, Option<String>>(6)?.and_then(|s| s.parse().ok()),
+        label: row.get(7)?,
+        note: row.get(8)?,
+        is_watch_only: row.get(9)?,
+        deleted_at: row.get::<_, Option<String>>(10)?.and_then(|s| s.parse().ok()),
+        cipher: CipherAlgorithm::from_str(&row.get::<_, String>(11)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(11, rusqlite::types::Type::Text, e.into()))?,
+    })
+}
+
+fn log_from_row(row: &Row) -> rusqlite::Result<SigningLogEntry> {
+    Ok(SigningLogEntry {
+        id: row.get(0)?,
+        timestamp: row.get::<_, String>(1)?.parse().unwrap_or(Utc::now()),
+        from_address: row.get(2)?,
+        to_address: row.get(3)?,
+        value: row.get(4)?,
+        method: row.get::<_, i64>(5)? as u64,
+        nonce: row.get::<_, i64>(6)? as u64,
+        cid: row.get(7)?,
+        success: row.get(8)?,
+        error: row.get(9)?,
+    })
+}
+
+fn message_from_row(row: &Row) -> rusqlite::Result<MessageRecord> {
+    Ok(MessageRecord {
+        id: row.get(0)?,
+        cid: row.get(1)?,
+        from_addr: row.get(2)?,
+        to_addr: row.get(3)?,
+        value: row.get(4)?,
+        method: row.get::<_, i64>(5)? as u64,
+        gas_limit: row.get(6)?,
+        gas_fee_cap: row.get(7)?,
+        gas_premium: row.get(8)?,
+        nonce: row.get::<_, i64>(9)? as u64,
+        key_type: row.get(10)?,
+        pushed_at: row.get::<_, String>(11)?.parse().unwrap_or(Utc::now()),
+    })
+}
+
+fn address_book_from_row(row: &Row) -> rusqlite::Result<AddressBookEntry> {
+    Ok(AddressBookEntry {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        address: row.get(2)?,
+        note: row.get(3)?,
+        created_at: row.get::<_, String>(4)?.parse().unwrap_or(Utc::now()),
+    })
+}
+
+fn audit_from_row(row: &Row) -> rusqlite::Result<SigningAuditEntry> {
+    Ok(SigningAuditEntry {
+        id: row.get(0)?,
+        timestamp: row.get::<_, String>(1)?.parse().unwrap_or(Utc::now()),
+        address: row.get(2)?,
+        key_type: row.get(3)?,
+        cid_hex: row.get(4)?,
+        to_addr: row.get(5)?,
+        value_attofil: row.get(6)?,
+        method: row.get::<_, i64>(7)? as u64,
+        nonce: row.get::<_, i64>(8)? as u64,
+        signed_by_pid: row.get(9)?,
+    })
+}
+
+fn scheduled_job_from_row(row: &Row) -> rusqlite::Result<ScheduledJob> {
+    Ok(ScheduledJob {
+        id: row.get(0)?,
+        cron_expr: row.get(1)?,
+        command_json: row.get(2)?,
+        last_run: row.get::<_, Option<String>>(3)?.and_then(|s| s.parse().ok()),
+        next_run: row.get::<_, Option<String>>(4)?.and_then(|s| s.parse().ok()),
+        enabled: row.get(5)?,
+        created_at: row.get::<_, String>(6)?.parse().unwrap_or(Utc::now()),
+    })
+}
+
+fn threshold_from_row(row: &Row) -> rusqlite::Result<BalanceThreshold> {
+    Ok(BalanceThreshold {
+        address: row.get(0)?,
+        threshold_attofil: row.get(1)?,
+        alert_webhook_url: row.get(2)?,
+        below_since: row.get::<_, Option<String>>(3)?.and_then(|s| s.parse().ok()),
+        created_at: row.get::<_, String>(4)?.parse().unwrap_or(Utc::now()),
+    })
+}
+
+fn rotation_from_row(row: &Row) -> rusqlite::Result<WorkerRotation> {
+    Ok(WorkerRotation {
+        miner: row.get(0)?,
+        new_worker: row.get(1)?,
+        owner: row.get(2)?,
+        propose_cid: row.get(3)?,
+        change_epoch: row.get(4)?,
+        created_at: row.get::<_, String>(5)?.parse().unwrap_or(Utc::now()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn concurrent_reads_from_two_handles_do_not_deadlock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lotus_sign.db").to_string_lossy().into_owned();
+        let first = Store::open(&path, None).unwrap();
+        let second = Store::open(&path, None).unwrap();
+        first.insert_key(&WalletKey::new("f01234".to_string(), "secp256k1".to_string(), vec![0u8; 48])).unwrap();
+
+        let journal_mode: String = first.conn().query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+        assert_eq!(journal_mode, "wal");
+
+        let (done, finished) = mpsc::channel();
+        for store in [first.clone(), second.clone(), first, second] {
+            let done = done.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    assert_eq!(store.list_keys().unwrap().len(), 1);
+                    assert!(store.get_key("f01234").unwrap().is_some());
+                }
+                done.send(()).unwrap();
+            });
+        }
+        for _ in 0..4 {
+            finished.recv_timeout(Duration::from_secs(10)).expect("concurrent reads deadlocked or panicked");
+        }
+    }
+
+    #[test]
+    fn archived_key_is_found_by_label_for_restore() {
+        let store = Store::open(":memory:", None).unwrap();
+        store.insert_key(&WalletKey::new("f01234".to_string(), "secp256k1".to_string(), vec![0u8; 48])).unwrap();
+        store.update_key_label("f01234", Some("cold")).unwrap();
+        store.delete_key("f01234").unwrap();
+
+        assert!(store.get_key_by_label("cold").unwrap().is_none());
+        let archived = store.get_key_by_label_with_archived("cold").unwrap().unwrap();
+        assert_eq!(archived.address, "f01234");
+
+        store.restore_key(&archived.address).unwrap();
+        assert!(store.get_key_by_label("cold").unwrap().is_some());
+    }
 }
@@ -0,0 +1,114 @@
+//! C ABI 接口：供 C / Go / Python 等非 Rust 工具链调用签名功能，声明见 include/lotus_sign.h
+//!
+//! 所有函数成功返回 0、失败返回 -1；失败时若 `out_error` 非空，写入一个需由
+//! `lotus_sign_free_error` 释放的错误字符串。输出缓冲区均需容纳结尾的 NUL
+
+use crate::chain::Message;
+use crate::wallet::{self, KeyType, PrivateKey};
+use anyhow::Result;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+
+/// 生成新密钥，将地址与 hex 私钥写入调用方提供的缓冲区
+///
+/// # Safety
+///
+/// `key_type` 必须是有效的 NUL 结尾字符串；`out_address` 与 `out_privkey_hex` 必须
+/// 分别可写 `out_address_len`、`out_privkey_len` 字节；`out_error` 可为空
+#[no_mangle]
+pub unsafe extern "C" fn lotus_sign_generate_key(
+    key_type: *const c_char,
+    out_address: *mut c_char,
+    out_address_len: usize,
+    out_privkey_hex: *mut c_char,
+    out_privkey_len: usize,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let key_type = KeyType::from_str(read_str(key_type, "key_type")?)?;
+        let key = PrivateKey::generate(key_type)?;
+        let address = key.address()?.to_string();
+        let privkey_hex = hex::encode(&key.private_key);
+        // 两个缓冲区都放得下再写入，避免只写出一半结果
+        check_capacity(&address, out_address_len, "out_address")?;
+        check_capacity(&privkey_hex, out_privkey_len, "out_privkey_hex")?;
+        write_str(&address, out_address, out_address_len, "out_address")?;
+        write_str(&privkey_hex, out_privkey_hex, out_privkey_len, "out_privkey_hex")
+    })
+}
+
+/// 签名 Lotus JSON 格式的消息，将 SignedMessage JSON 写入 `out_signed`；私钥必须对应消息的 From 地址
+///
+/// # Safety
+///
+/// `msg_json`、`privkey_hex`、`key_type` 必须是有效的 NUL 结尾字符串；`out_signed`
+/// 必须可写 `out_len` 字节；`out_error` 可为空
+#[no_mangle]
+pub unsafe extern "C" fn lotus_sign_message(
+    msg_json: *const c_char,
+    privkey_hex: *const c_char,
+    key_type: *const c_char,
+    out_signed: *mut c_char,
+    out_len: usize,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    guard(out_error, || {
+        let msg: Message = serde_json::from_str(read_str(msg_json, "msg_json")?)?;
+        let key_type = KeyType::from_str(read_str(key_type, "key_type")?)?;
+        let key = PrivateKey::from_bytes(key_type, &hex::decode(read_str(privkey_hex, "privkey_hex")?)?)?;
+        let signed = serde_json::to_string(&wallet::sign_message_with_key(msg, &key)?)?;
+        write_str(&signed, out_signed, out_len, "out_signed")
+    })
+}
+
+/// 释放失败时写入 `out_error` 的错误字符串；传入空指针时不做任何事
+///
+/// # Safety
+///
+/// `ptr` 必须为空，或是本库写入 `out_error` 且尚未释放的指针
+#[no_mangle]
+pub unsafe extern "C" fn lotus_sign_free_error(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(CString::from_raw(ptr));
+    }
+}
+
+/// 执行调用并转换为返回码；panic 不能跨越 C 边界展开，同样按错误处理
+unsafe fn guard(out_error: *mut *mut c_char, f: impl FnOnce() -> Result<()>) -> i32 {
+    let error = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return 0,
+        Ok(Err(e)) => format!("{:#}", e),
+        Err(_) => "internal error: panic in lotus-sign".to_string(),
+    };
+    if !out_error.is_null() {
+        // 错误信息不含 NUL，失败时退化为空字符串
+        *out_error = CString::new(error).unwrap_or_default().into_raw();
+    }
+    -1
+}
+
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str> {
+    if ptr.is_null() {
+        anyhow::bail!("{} is null", name);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| anyhow::anyhow!("{} is not valid UTF-8", name))
+}
+
+fn check_capacity(s: &str, len: usize, name: &str) -> Result<()> {
+    if s.len() >= len {
+        anyhow::bail!("{} buffer too small: need {} bytes, got {}", name, s.len() + 1, len);
+    }
+    Ok(())
+}
+
+unsafe fn write_str(s: &str, out: *mut c_char, len: usize, name: &str) -> Result<()> {
+    if out.is_null() {
+        anyhow::bail!("{} is null", name);
+    }
+    check_capacity(s, len, name)?;
+    std::ptr::copy_nonoverlapping(s.as_ptr(), out.cast::<u8>(), s.len());
+    *out.add(s.len()) = 0;
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! lotus-sign 的库目标：供二进制入口、fuzz/ 下的模糊测试目标、WASM 绑定与 C FFI 共用
+//!
+//! `native`（默认）启用 CLI、数据库、RPC 与守护进程；关闭后只保留链数据结构、
+//! 加密与签名算法，可编译到 wasm32
+
+#[cfg(feature = "native")]
+pub mod cli;
+pub mod chain;
+#[cfg(feature = "native")]
+pub mod config;
+pub mod crypto;
+#[cfg(feature = "native")]
+pub mod daemon;
+#[cfg(feature = "native")]
+pub mod db;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "native")]
+pub mod metrics;
+#[cfg(feature = "native")]
+pub mod notify;
+#[cfg(feature = "native")]
+pub mod rpc;
+#[cfg(feature = "native")]
+pub mod service;
+pub mod wallet;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,23 +1,62 @@
-mod cli;
-mod chain;
-mod config;
-mod crypto;
-mod db;
-mod rpc;
-mod service;
-mod wallet;
+use lotus_sign::{cli, config, db};
 
 use anyhow::Result;
 use clap::Parser;
-use tracing_subscriber;
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::sync::Mutex;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    let args = cli::Cli::parse();
+    if let cli::Commands::Completions { shell } = args.command {
+        cli::print_completions(shell);
+        return Ok(());
+    }
+    init_logging(args.log_format, args.log_file.as_deref())?;
 
-    let cfg = config::Config::load()?;
-    let store = db::Store::open(&cfg.database.path)?;
+    let mut cfg = config::Config::load(args.profile.as_deref())?;
+    cfg.dry_run = args.dry_run;
+    cfg.no_retry = args.no_retry;
+    cfg.keyring = args.keyring;
+    cfg.cli_password = args.password()?;
+    if args.network.is_some() {
+        cfg.lotus.network = args.network;
+    }
+    if let cli::Commands::Db(cmd) = args.command {
+        return cli::db::run(cmd, &cfg).await;
+    }
+    // config 不打开数据库：validate 需如实检查路径是否可写，init 不应创建数据库文件
+    if let cli::Commands::Config(cmd) = args.command {
+        return cli::config::run(cmd, &cfg).await;
+    }
+    let store = db::Store::open_async(&cfg.database.path, cfg.database.open_key()?).await?;
 
-    let args = cli::Cli::parse();
     cli::run(args, cfg, store).await
 }
+
+/// 初始化 tracing：日志写入标准错误（或 --log-file），避免混入命令输出
+fn init_logging(format: cli::LogFormat, file: Option<&Path>) -> Result<()> {
+    use tracing_subscriber::fmt::writer::BoxMakeWriter;
+    use tracing_subscriber::EnvFilter;
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"));
+    let writer = match file {
+        Some(path) => {
+            let f = OpenOptions::new().create(true).append(true).open(path)
+                .map_err(|e| anyhow::anyhow!("cannot open log file {}: {}", path.display(), e))?;
+            BoxMakeWriter::new(Mutex::new(f))
+        }
+        None => BoxMakeWriter::new(std::io::stderr),
+    };
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(file.is_none());
+    match format {
+        cli::LogFormat::Text => builder.init(),
+        cli::LogFormat::Json => builder.json().init(),
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Prometheus 指标
+//!
+//! 指标名称：
+//! - `lotus_sign_messages_signed_total{key_type}`：签名次数（消息与任意数据），按密钥类型区分
+//! - `lotus_sign_sign_duration_seconds`：单次签名耗时（不含私钥解密）
+//! - `lotus_sign_rpc_calls_total{method,status}`：Lotus RPC 调用次数，status 为 ok / error
+//! - `lotus_sign_rpc_duration_seconds{method}`：Lotus RPC 调用耗时
+//!
+//! 指标注册在默认 registry 中，常驻进程通过 `serve` 以纯文本格式暴露 `/metrics`
+
+use anyhow::Result;
+use prometheus::{
+    register_histogram, register_histogram_vec, register_int_counter_vec, Encoder, Histogram,
+    HistogramVec, IntCounterVec, TextEncoder,
+};
+use std::sync::LazyLock;
+use std::time::Instant;
+
+static MESSAGES_SIGNED: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "lotus_sign_messages_signed_total",
+        "Number of signatures produced, by key type",
+        &["key_type"]
+    )
+    .expect("register lotus_sign_messages_signed_total")
+});
+
+static SIGN_DURATION: LazyLock<Histogram> = LazyLock::new(|| {
+    register_histogram!(
+        "lotus_sign_sign_duration_seconds",
+        "Time spent producing a single signature"
+    )
+    .expect("register lotus_sign_sign_duration_seconds")
+});
+
+static RPC_CALLS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "lotus_sign_rpc_calls_total",
+        "Number of Lotus RPC calls, by method and status",
+        &["method", "status"]
+    )
+    .expect("register lotus_sign_rpc_calls_total")
+});
+
+static RPC_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "lotus_sign_rpc_duration_seconds",
+        "Lotus RPC call latency, by method",
+        &["method"]
+    )
+    .expect("register lotus_sign_rpc_duration_seconds")
+});
+
+/// 记录一次签名
+pub fn record_sign(key_type: &str, started: Instant) {
+    MESSAGES_SIGNED.with_label_values(&[key_type]).inc();
+    SIGN_DURATION.observe(started.elapsed().as_secs_f64());
+}
+
+/// 记录一次 RPC 调用
+pub fn record_rpc(method: &str, ok: bool, started: Instant) {
+    let status = if ok { "ok" } else { "error" };
+    RPC_CALLS.with_label_values(&[method, status]).inc();
+    RPC_DURATION.with_label_values(&[method]).observe(started.elapsed().as_secs_f64());
+}
+
+/// 以 Prometheus 文本格式导出全部指标
+pub fn render() -> Result<String> {
+    let mut buf = Vec::new();
+    TextEncoder::new().encode(&prometheus::gather(), &mut buf)?;
+    Ok(String::from_utf8(buf)?)
+}
+
+/// 在 addr 上提供 `GET /metrics`，其余路径返回 404
+pub async fn serve(addr: &str) -> Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    loop {
+        let (mut socket, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]);
+
+            let response = match (request.starts_with("GET /metrics "), render()) {
+                (true, Ok(body)) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(), body
+                ),
+                (true, Err(e)) => format!("HTTP/1.1 500 Internal Server Error\r\n\r\n{}", e),
+                _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string(),
+            };
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+    }
+}
@@ -1,26 +1,81 @@
 use super::LotusClient;
-use crate::chain::{BigInt, Message, SignedMessage};
+use crate::chain::{cbor, hamt, BigInt, BitfieldRle, Message, SignedMessage};
+use crate::config::Config;
+use crate::db::Store;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
 
 pub struct LotusApi {
     client: LotusClient,
+    /// StateMinerInfo 的本地缓存与有效期（秒），见 `with_miner_info_cache`
+    miner_info_cache: Option<(Store, u64)>,
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinerInfo {
     #[serde(rename = "Owner")]
     pub owner: String,
     #[serde(rename = "Worker")]
     pub worker: String,
+    /// ChangeWorkerAddress 提议的新 worker；没有待确认的变更时为 "<empty>"
+    #[serde(rename = "NewWorker", default)]
+    pub new_worker: Option<String>,
+    /// 新 worker 可被确认的纪元；没有待确认的变更时为 -1
+    #[serde(rename = "WorkerChangeEpoch", default)]
+    pub worker_change_epoch: Option<i64>,
     #[serde(rename = "ControlAddresses")]
     pub control_addresses: Option<Vec<String>>,
     #[serde(rename = "PeerId")]
     pub peer_id: Option<String>,
     #[serde(rename = "SectorSize")]
     pub sector_size: u64,
+    /// FIP-0029 受益人；网络版本 17 之前的节点不返回
+    #[serde(rename = "Beneficiary")]
+    pub beneficiary: Option<String>,
+    #[serde(rename = "BeneficiaryTerm")]
+    pub beneficiary_term: Option<BeneficiaryTerm>,
+    /// 等待确认的受益人变更
+    #[serde(rename = "PendingBeneficiaryTerm")]
+    pub pending_beneficiary_term: Option<PendingBeneficiaryTerm>,
+}
+
+impl MinerInfo {
+    /// 待确认的 worker 变更（新 worker，可确认的纪元）
+    pub fn pending_worker_change(&self) -> Option<(&str, i64)> {
+        match (self.new_worker.as_deref(), self.worker_change_epoch) {
+            (Some(worker), Some(epoch)) if epoch > 0 && worker != "<empty>" => Some((worker, epoch)),
+            _ => None,
+        }
+    }
+}
+
+/// 受益人额度：可提取 quota 内的收益，直到 expiration 纪元
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeneficiaryTerm {
+    #[serde(rename = "Quota")]
+    pub quota: BigInt,
+    #[serde(rename = "UsedQuota")]
+    pub used_quota: BigInt,
+    #[serde(rename = "Expiration")]
+    pub expiration: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingBeneficiaryTerm {
+    #[serde(rename = "NewBeneficiary")]
+    pub new_beneficiary: String,
+    #[serde(rename = "NewQuota")]
+    pub new_quota: BigInt,
+    #[serde(rename = "NewExpiration")]
+    pub new_expiration: i64,
+    #[serde(rename = "ApprovedByBeneficiary")]
+    pub approved_by_beneficiary: bool,
+    #[serde(rename = "ApprovedByNominee")]
+    pub approved_by_nominee: bool,
 }
 
 #[allow(dead_code)]
@@ -32,7 +87,6 @@ pub struct MarketBalance {
     pub locked: BigInt,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Clone, Deserialize)]
 pub struct MsgLookup {
     #[serde(rename = "Height")]
@@ -52,6 +106,152 @@ pub struct MsgReceipt {
     pub gas_used: i64,
 }
 
+/// StateCall 的模拟执行结果；Duration 为纳秒
+#[allow(dead_code)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct InvocResult {
+    #[serde(rename = "MsgCid")]
+    pub msg_cid: Option<Cid>,
+    #[serde(rename = "Msg")]
+    pub msg: Message,
+    #[serde(rename = "MsgRct")]
+    pub msg_rct: MsgReceipt,
+    /// 执行失败时的错误信息，成功时节点返回空字符串
+    #[serde(rename = "Error", default)]
+    pub error: Option<String>,
+    #[serde(rename = "Duration", default)]
+    pub duration: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PowerClaim {
+    #[serde(rename = "RawBytePower")]
+    pub raw_byte_power: BigInt,
+    #[serde(rename = "QualityAdjPower")]
+    pub quality_adj_power: BigInt,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct MinerPower {
+    #[serde(rename = "MinerPower")]
+    pub miner_power: PowerClaim,
+    #[serde(rename = "TotalPower")]
+    pub total_power: PowerClaim,
+    #[serde(rename = "HasMinPower")]
+    pub has_min_power: bool,
+}
+
+impl MinerPower {
+    /// 矿工 QA 算力占全网 QA 算力的百分比（保留 4 位小数）
+    pub fn network_share_percent(&self) -> String {
+        let scaled = BigInt(&self.miner_power.quality_adj_power.0 * 100);
+        scaled.scale_fraction(&self.total_power.quality_adj_power, 4)
+    }
+}
+
+/// 一个 WindowPoSt 截止期的时间信息（StateMinerProvingDeadline 的返回格式）
+#[allow(dead_code)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeadlineInfo {
+    #[serde(rename = "CurrentEpoch")]
+    pub current_epoch: i64,
+    #[serde(rename = "PeriodStart")]
+    pub period_start: i64,
+    #[serde(rename = "Index")]
+    pub index: u64,
+    #[serde(rename = "Open")]
+    pub open: i64,
+    #[serde(rename = "Close")]
+    pub close: i64,
+    #[serde(rename = "Challenge")]
+    pub challenge: i64,
+    #[serde(rename = "FaultCutoff")]
+    pub fault_cutoff: i64,
+    #[serde(rename = "WPoStPeriodDeadlines")]
+    pub wpost_period_deadlines: u64,
+    #[serde(rename = "WPoStProvingPeriod")]
+    pub wpost_proving_period: i64,
+    #[serde(rename = "WPoStChallengeWindow")]
+    pub wpost_challenge_window: i64,
+    #[serde(rename = "WPoStChallengeLookback")]
+    pub wpost_challenge_lookback: i64,
+    #[serde(rename = "FaultDeclarationCutoff")]
+    pub fault_declaration_cutoff: i64,
+}
+
+/// 截止期内的一个分区（StateMinerPartitions），均为扇区编号集合
+#[allow(dead_code)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Partition {
+    #[serde(rename = "AllSectors")]
+    pub all_sectors: BitfieldRle,
+    #[serde(rename = "FaultySectors")]
+    pub faulty_sectors: BitfieldRle,
+    #[serde(rename = "RecoveringSectors")]
+    pub recovering_sectors: BitfieldRle,
+    #[serde(rename = "LiveSectors")]
+    pub live_sectors: BitfieldRle,
+    #[serde(rename = "ActiveSectors")]
+    pub active_sectors: BitfieldRle,
+}
+
+/// 每个纪元约 30 秒
+pub const EPOCH_DURATION_SECONDS: i64 = 30;
+
+impl DeadlineInfo {
+    pub fn is_open(&self, current_epoch: i64) -> bool {
+        current_epoch >= self.open && current_epoch < self.close
+    }
+
+    /// 在错误声明截止之前，截止期内的分区仍可声明错误或恢复
+    pub fn is_mutable(&self, current_epoch: i64) -> bool {
+        current_epoch < self.fault_cutoff
+    }
+
+    /// 距离开启的大致秒数，已开启时为负数
+    pub fn opens_in_seconds(&self, current_epoch: i64) -> i64 {
+        (self.open - current_epoch) * EPOCH_DURATION_SECONDS
+    }
+
+    /// 以当前截止期为基准推算指定序号截止期的下一次（或正在进行的）时间窗口
+    /// 序号小于当前截止期的已在本证明周期结束，取下一证明周期
+    pub fn for_index(&self, index: u64) -> DeadlineInfo {
+        let mut period_start = self.period_start;
+        if index < self.index {
+            period_start += self.wpost_proving_period;
+        }
+        let open = period_start + index as i64 * self.wpost_challenge_window;
+        DeadlineInfo {
+            period_start,
+            index,
+            open,
+            close: open + self.wpost_challenge_window,
+            challenge: open - self.wpost_challenge_lookback,
+            fault_cutoff: open - self.fault_declaration_cutoff,
+            ..self.clone()
+        }
+    }
+}
+
+/// MpoolPending 返回的已签名消息（附带 CID）
+#[derive(Debug, Clone, Deserialize)]
+struct PendingMessage {
+    #[serde(rename = "Message")]
+    message: Message,
+    #[serde(rename = "CID")]
+    cid: Cid,
+}
+
+/// StateListMessages 的消息匹配条件，未设置的字段不参与匹配
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MessageMatch {
+    #[serde(rename = "To", skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(rename = "From", skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cid {
     #[serde(rename = "/")]
@@ -59,12 +259,31 @@ pub struct Cid {
 }
 
 impl LotusApi {
+    #[allow(dead_code)]
     pub fn new(url: &str, token: Option<String>) -> Self {
         Self {
             client: LotusClient::new(url, token),
+            miner_info_cache: None,
         }
     }
 
+    /// 按配置创建：lotus.host 为主节点，lotus.fallback_hosts 为备用节点
+    pub fn from_config(cfg: &Config) -> Self {
+        let mut endpoints = vec![cfg.lotus.host.clone()];
+        endpoints.extend(cfg.lotus.fallback_hosts.iter().cloned());
+        Self {
+            client: LotusClient::with_endpoints(endpoints, cfg.lotus.token.clone(), cfg.recovery_interval())
+                .with_retry_policy(cfg.retry_policy()),
+            miner_info_cache: None,
+        }
+    }
+
+    /// `state_miner_info` 优先读取数据库中不超过 ttl_secs 秒的缓存；ttl_secs 为 0 时不缓存
+    pub fn with_miner_info_cache(mut self, store: &Store, ttl_secs: u64) -> Self {
+        self.miner_info_cache = (ttl_secs > 0).then(|| (store.clone(), ttl_secs));
+        self
+    }
+
     pub async fn wallet_balance(&self, address: &str) -> Result<BigInt> {
         self.client.call("WalletBalance", json!([address])).await
     }
@@ -83,9 +302,72 @@ impl LotusApi {
             .await
     }
 
-    pub async fn state_miner_info(&self, miner: &str) -> Result<MinerInfo> {
+    /// 在链头状态上模拟执行消息，不需要签名，也不会上链
+    pub async fn state_call(&self, msg: &Message) -> Result<InvocResult> {
+        self.client
+            .call("StateCall", json!([msg, null]))
+            .await
+    }
+
+    pub async fn gas_estimate_gas_limit(&self, msg: &Message) -> Result<i64> {
+        self.client
+            .call("GasEstimateGasLimit", json!([msg, null]))
+            .await
+    }
+
+    /// 估算在 nblocksincl 个区块内被打包所需的 premium
+    pub async fn gas_estimate_premium(&self, nblocksincl: u64, sender: &str, gas_limit: i64) -> Result<BigInt> {
+        self.client
+            .call("GasEstimateGasPremium", json!([nblocksincl, sender, gas_limit, null]))
+            .await
+    }
+
+    /// 估算能承受 max_queue_blocks 个区块基础费用上涨的 fee cap
+    pub async fn gas_estimate_fee_cap(&self, msg: &Message, max_queue_blocks: i64) -> Result<BigInt> {
         self.client
+            .call("GasEstimateFeeCap", json!([msg, max_queue_blocks, null]))
+            .await
+    }
+
+    /// 启用缓存时可能返回最多 TTL 秒之前的信息；先读后改的场景使用 `state_miner_info_uncached`
+    pub async fn state_miner_info(&self, miner: &str) -> Result<MinerInfo> {
+        if let Some((store, ttl_secs)) = &self.miner_info_cache {
+            match store.get_cached_miner_info_async(miner, *ttl_secs).await {
+                Ok(Some(info)) => return Ok(info),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(miner = %miner, error = %e, "cannot read miner info cache"),
+            }
+        }
+        self.state_miner_info_uncached(miner).await
+    }
+
+    /// 总是查询节点，并用结果刷新缓存
+    pub async fn state_miner_info_uncached(&self, miner: &str) -> Result<MinerInfo> {
+        let info: MinerInfo = self.client
             .call("StateMinerInfo", json!([miner, null]))
+            .await?;
+        if let Some((store, _)) = &self.miner_info_cache {
+            // 缓存写入失败只影响后续调用的速度
+            if let Err(e) = store.set_cached_miner_info_async(miner, info.clone()).await {
+                tracing::warn!(miner = %miner, error = %e, "cannot write miner info cache");
+            }
+        }
+        Ok(info)
+    }
+
+    /// 向矿工发送消息后丢弃其缓存，下次读取时重新查询
+    pub async fn invalidate_miner_info(&self, miner: &str) {
+        if let Some((store, _)) = &self.miner_info_cache {
+            if let Err(e) = store.delete_cached_miner_info_async(miner).await {
+                tracing::warn!(miner = %miner, error = %e, "cannot invalidate miner info cache");
+            }
+        }
+    }
+
+    /// 当前链头上的全部矿工 ID 地址
+    pub async fn state_list_miners(&self) -> Result<Vec<String>> {
+        self.client
+            .call("StateListMiners", json!([null]))
             .await
     }
 
@@ -95,6 +377,115 @@ impl LotusApi {
             .await
     }
 
+    pub async fn state_miner_power(&self, miner: &str) -> Result<MinerPower> {
+        self.client
+            .call("StateMinerPower", json!([miner, null]))
+            .await
+    }
+
+    /// 当前处于故障状态的扇区编号
+    pub async fn state_miner_faults(&self, miner: &str) -> Result<BitfieldRle> {
+        self.client
+            .call("StateMinerFaults", json!([miner, null]))
+            .await
+    }
+
+    pub async fn state_miner_proving_deadline(&self, miner: &str) -> Result<DeadlineInfo> {
+        self.client
+            .call("StateMinerProvingDeadline", json!([miner, null]))
+            .await
+    }
+
+    /// 计算矿工全部截止期的时间窗口，按序号排列
+    pub async fn state_miner_deadlines(&self, miner: &str) -> Result<Vec<DeadlineInfo>> {
+        let current = self.state_miner_proving_deadline(miner).await?;
+        Ok((0..current.wpost_period_deadlines)
+            .map(|i| current.for_index(i))
+            .collect())
+    }
+
+    /// StateMinerDeadlines 返回的截止期数量（每个截止期的 PostSubmissions 等状态不在此解析）
+    pub async fn state_miner_deadline_count(&self, miner: &str) -> Result<u64> {
+        let deadlines: Vec<Value> = self.client
+            .call("StateMinerDeadlines", json!([miner, null]))
+            .await?;
+        Ok(deadlines.len() as u64)
+    }
+
+    pub async fn state_miner_partitions(&self, miner: &str, deadline: u64) -> Result<Vec<Partition>> {
+        let partitions: Option<Vec<Partition>> = self.client
+            .call_optional("StateMinerPartitions", json!([miner, deadline, null]))
+            .await?;
+        Ok(partitions.unwrap_or_default())
+    }
+
+    /// 读取 Actor 状态；epoch 为 None 时使用链头
+    pub async fn state_read_state(&self, addr: &str, tipset: Option<i64>) -> Result<Value> {
+        let tsk = match tipset {
+            Some(height) => self.chain_get_tipset_by_height(height).await?["Cids"].clone(),
+            None => Value::Null,
+        };
+        self.client
+            .call("StateReadState", json!([addr, tsk]))
+            .await
+    }
+
+    pub async fn chain_get_tipset_by_height(&self, height: i64) -> Result<Value> {
+        self.client
+            .call("ChainGetTipSetByHeight", json!([height, null]))
+            .await
+    }
+
+    /// 读取 IPLD 对象的原始 CBOR 字节
+    pub async fn chain_read_obj(&self, cid: &str) -> Result<Vec<u8>> {
+        use base64::Engine;
+        let data: String = self.client
+            .call("ChainReadObj", json!([{ "/": cid }]))
+            .await?;
+        Ok(base64::engine::general_purpose::STANDARD.decode(data)?)
+    }
+
+    /// 遍历以 root 为根的 HAMT，返回全部键值对
+    pub async fn hamt_entries(&self, root: &str) -> Result<Vec<(Vec<u8>, ciborium::Value)>> {
+        let mut entries = Vec::new();
+        let mut pending = vec![root.to_string()];
+        while let Some(cid) = pending.pop() {
+            for pointer in hamt::decode_node(&self.chain_read_obj(&cid).await?)? {
+                match pointer {
+                    hamt::Pointer::Link(child) => pending.push(cbor::cid_to_string(&child)),
+                    hamt::Pointer::Bucket(kvs) => entries.extend(kvs),
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    pub async fn state_network_version(&self) -> Result<u64> {
+        self.client
+            .call("StateNetworkVersion", json!([null]))
+            .await
+    }
+
+    /// 指定网络版本下内置 Actor 名称到 CodeCID 的映射（如 "multisig"）
+    pub async fn state_actor_code_cids(&self, network_version: u64) -> Result<HashMap<String, Cid>> {
+        self.client
+            .call("StateActorCodeCIDs", json!([network_version]))
+            .await
+    }
+
+    /// 查询地址的 DataCap 余额；非认证客户返回 None
+    pub async fn state_verified_client_status(&self, address: &str) -> Result<Option<BigInt>> {
+        self.client
+            .call_optional("StateVerifiedClientStatus", json!([address, null]))
+            .await
+    }
+
+    pub async fn state_verified_registry_root_key(&self) -> Result<String> {
+        self.client
+            .call("StateVerifiedRegistryRootKey", json!([null]))
+            .await
+    }
+
     #[allow(dead_code)]
     pub async fn state_market_balance(&self, address: &str) -> Result<MarketBalance> {
         self.client
@@ -102,13 +493,76 @@ impl LotusApi {
             .await
     }
 
-    #[allow(dead_code)]
+    /// 阻塞等待消息上链并达到 confidence 个确认
     pub async fn state_wait_msg(&self, cid: &Cid, confidence: u64) -> Result<MsgLookup> {
         self.client
             .call("StateWaitMsg", json!([cid, confidence]))
             .await
     }
 
+    /// 在已上链的消息中查找，未找到时返回 None（不会阻塞等待）
+    pub async fn state_search_msg(&self, cid: &Cid) -> Result<Option<MsgLookup>> {
+        self.client
+            .call_optional("StateSearchMsg", json!([cid]))
+            .await
+    }
+
+    /// 已上链消息的回执；StateGetReceipt 已从新版 Lotus 移除，改用 StateSearchMsg 查询
+    pub async fn state_get_receipt(&self, cid: &Cid) -> Result<MsgReceipt> {
+        match self.state_search_msg(cid).await? {
+            Some(lookup) => Ok(lookup.receipt),
+            None => anyhow::bail!("message {} not found on chain", cid.root),
+        }
+    }
+
+    pub async fn mpool_pending_cids(&self) -> Result<Vec<Cid>> {
+        Ok(self.mpool_pending().await?.into_iter().map(|m| m.cid).collect())
+    }
+
+    /// 在消息池中按 CID 查找待处理消息，返回未签名的原始消息
+    pub async fn mpool_pending_by_cid(&self, cid: &str) -> Result<Option<Message>> {
+        Ok(self.mpool_pending().await?
+            .into_iter()
+            .find(|m| m.cid.root == cid)
+            .map(|m| m.message))
+    }
+
+    async fn mpool_pending(&self) -> Result<Vec<PendingMessage>> {
+        let pending: Option<Vec<PendingMessage>> = self.client
+            .call_optional("MpoolPending", json!([null]))
+            .await?;
+        Ok(pending.unwrap_or_default())
+    }
+
+    /// 轮询消息池直到消息离开消息池并能查到回执
+    ///
+    /// 与 `state_wait_msg` 不同，不需要与节点保持长连接。每轮等待前以当前链头高度调用
+    /// `on_poll`（查询链头失败时为 None，不影响等待），由调用方决定如何显示进度
+    pub async fn wait_for_inclusion(
+        &self,
+        cid: &Cid,
+        poll_interval: Duration,
+        timeout: Duration,
+        mut on_poll: impl FnMut(Option<i64>),
+    ) -> Result<MsgLookup> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let pending = self.mpool_pending_cids().await?;
+            if !pending.iter().any(|c| c.root == cid.root) {
+                if let Some(lookup) = self.state_search_msg(cid).await? {
+                    return Ok(lookup);
+                }
+            }
+
+            if tokio::time::Instant::now() + poll_interval > deadline {
+                anyhow::bail!("timed out after {}s waiting for {} to be included", timeout.as_secs(), cid.root);
+            }
+            let epoch = self.chain_head().await.ok().and_then(|head| head["Height"].as_i64());
+            on_poll(epoch);
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
     #[allow(dead_code)]
     pub async fn state_lookup_id(&self, address: &str) -> Result<String> {
         self.client
@@ -123,8 +577,111 @@ impl LotusApi {
             .await
     }
 
-    #[allow(dead_code)]
     pub async fn chain_head(&self) -> Result<Value> {
         self.client.call("ChainHead", json!([])).await
     }
+
+    /// 以太坊兼容接口：按区块号查询区块（"latest"、"earliest" 或十六进制高度）
+    #[allow(dead_code)]
+    pub async fn eth_block_by_number(&self, block: &str, full_tx: bool) -> Result<Value> {
+        self.client
+            .call_eth("eth_getBlockByNumber", json!([block, full_tx]))
+            .await
+            .map(|v| v.unwrap_or(Value::Null))
+    }
+
+    /// 以太坊兼容接口：查询 EVM 交易回执，交易不存在时返回 Null
+    pub async fn eth_get_transaction_receipt(&self, tx_hash: &str) -> Result<Value> {
+        self.client
+            .call_eth("eth_getTransactionReceipt", json!([tx_hash]))
+            .await
+            .map(|v| v.unwrap_or(Value::Null))
+    }
+
+    pub async fn chain_get_message(&self, cid: &Cid) -> Result<Message> {
+        self.client
+            .call("ChainGetMessage", json!([cid]))
+            .await
+    }
+
+    /// 查找从链头回溯到 epoch 高度之间匹配 from/to 的消息 CID
+    /// 节点不支持按方法号过滤，指定 method 时会逐条获取消息并在本地过滤
+    pub async fn state_list_messages(
+        &self,
+        from: Option<&str>,
+        to: Option<&str>,
+        method: Option<u64>,
+        epoch: Option<i64>,
+    ) -> Result<Vec<Cid>> {
+        let filter = MessageMatch {
+            to: to.map(str::to_string),
+            from: from.map(str::to_string),
+        };
+        let cids: Vec<Cid> = self.client
+            .call_optional("StateListMessages", json!([filter, null, epoch.unwrap_or(0)]))
+            .await?
+            .unwrap_or_default();
+
+        let Some(method) = method else {
+            return Ok(cids);
+        };
+
+        let mut matched = Vec::new();
+        for cid in cids {
+            if self.chain_get_message(&cid).await?.method == method {
+                matched.push(cid);
+            }
+        }
+        Ok(matched)
+    }
+
+    pub async fn chain_get_block_messages(&self, block_cid: &Cid) -> Result<Value> {
+        self.client
+            .call("ChainGetBlockMessages", json!([block_cid]))
+            .await
+    }
+
+    /// 当前基础费用：链头区块的 ParentBaseFee（即链头消息执行时使用的基础费用）
+    pub async fn chain_base_fee(&self) -> Result<BigInt> {
+        let head = self.chain_head().await?;
+        parse_parent_base_fee(&head)
+    }
+
+    /// 根据链头 tipset 的 gas 用量估算下一纪元的基础费用
+    pub async fn estimate_next_base_fee(&self) -> Result<BigInt> {
+        let head = self.chain_head().await?;
+        let base_fee = parse_parent_base_fee(&head)?;
+
+        let block_cids: Vec<Cid> = serde_json::from_value(head["Cids"].clone())?;
+        let mut seen = std::collections::HashSet::new();
+        let mut gas_limit_used: i64 = 0;
+
+        for block_cid in &block_cids {
+            let msgs = self.chain_get_block_messages(block_cid).await?;
+            let cids = msgs["Cids"].as_array().cloned().unwrap_or_default();
+            let bls = msgs["BlsMessages"].as_array().cloned().unwrap_or_default();
+            let secpk = msgs["SecpkMessages"].as_array().cloned().unwrap_or_default();
+
+            // Cids 按 BLS 消息在前、secp 消息在后的顺序排列
+            let gas_limits = bls.iter()
+                .map(|m| m["GasLimit"].as_i64().unwrap_or(0))
+                .chain(secpk.iter().map(|m| m["Message"]["GasLimit"].as_i64().unwrap_or(0)));
+
+            for (cid, gas_limit) in cids.iter().zip(gas_limits) {
+                if seen.insert(cid.to_string()) {
+                    gas_limit_used += gas_limit;
+                }
+            }
+        }
+
+        let next = crate::chain::fil::compute_next_base_fee(&base_fee.0, gas_limit_used, block_cids.len());
+        Ok(BigInt(next))
+    }
+}
+
+fn parse_parent_base_fee(head: &Value) -> Result<BigInt> {
+    let fee = head["Blocks"][0]["ParentBaseFee"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("ChainHead response has no ParentBaseFee"))?;
+    Ok(BigInt::from_str(fee))
 }
@@ -1,12 +1,26 @@
+use crate::config::RetryPolicy;
+use crate::metrics;
 use anyhow::Result;
 use reqwest::Client;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
 
+/// JSON-RPC 客户端；配置了多个节点时，连接失败或超时会切换到下一个节点
 pub struct LotusClient {
     client: Client,
-    url: String,
+    /// 主节点在前，其后为备用节点
+    endpoints: Vec<String>,
     token: Option<String>,
+    /// 当前优先使用的节点序号
+    current: AtomicUsize,
+    /// 最近一次切换的时间，超过 recovery_interval 后重新尝试主节点
+    failed_over_at: Mutex<Option<Instant>>,
+    recovery_interval: Duration,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -31,37 +45,207 @@ struct RpcError {
 
 impl LotusClient {
     pub fn new(url: &str, token: Option<String>) -> Self {
+        Self::with_endpoints(vec![url.to_string()], token, Duration::from_secs(60))
+    }
+
+    pub fn with_endpoints(endpoints: Vec<String>, token: Option<String>, recovery_interval: Duration) -> Self {
+        assert!(!endpoints.is_empty(), "at least one endpoint is required");
         Self {
             client: Client::new(),
-            url: url.to_string(),
+            endpoints,
             token,
+            current: AtomicUsize::new(0),
+            failed_over_at: Mutex::new(None),
+            recovery_interval,
+            retry: RetryPolicy::default(),
         }
     }
 
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     pub async fn call<T: DeserializeOwned>(
         &self,
         method: &str,
         params: Value,
     ) -> Result<T> {
+        self.call_optional(method, params)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("empty result"))
+    }
+
+    /// 与 `call` 相同，但 `null` 结果返回 `None` 而不是错误
+    pub async fn call_optional<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<T>> {
+        self.request(format!("Filecoin.{}", method), params).await
+    }
+
+    /// 调用以太坊兼容接口（如 `eth_getBlockByNumber`），方法名不加 `Filecoin.` 前缀
+    pub async fn call_eth<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<T>> {
+        self.request(method.to_string(), params).await
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: String,
+        params: Value,
+    ) -> Result<Option<T>> {
         let req = RpcRequest {
             jsonrpc: "2.0",
-            method: format!("Filecoin.{}", method),
+            method,
             params,
             id: 1,
         };
 
-        let mut builder = self.client.post(&self.url).json(&req);
+        let started = Instant::now();
+        let span = tracing::info_span!("rpc", method = %req.method);
+        let result = self.send_with_retry(&req).instrument(span).await;
+        metrics::record_rpc(&req.method, result.is_ok(), started);
+        if let Err(e) = &result {
+            tracing::debug!(method = %req.method, error = %e, "rpc call failed");
+        }
+        result
+    }
 
-        if let Some(ref token) = self.token {
-            builder = builder.header("Authorization", format!("Bearer {}", token));
+    /// 传输错误与 5xx 响应按重试策略退避后重试；成功或 JSON-RPC 应用错误立即返回
+    async fn send_with_retry<T: DeserializeOwned>(&self, req: &RpcRequest) -> Result<Option<T>> {
+        let mut attempt = 0;
+        loop {
+            match self.send_with_failover(req).await {
+                Err(e) if attempt < self.retry.max_retries && is_retryable_error(&e) => {
+                    let delay = self.retry.delay(attempt);
+                    attempt += 1;
+                    tracing::warn!(
+                        method = %req.method,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "rpc call failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
         }
+    }
+
+    /// 从当前优先节点开始依次尝试；只有传输错误才切换，JSON-RPC 应用错误直接返回
+    async fn send_with_failover<T: DeserializeOwned>(&self, req: &RpcRequest) -> Result<Option<T>> {
+        let start = self.preferred_endpoint();
+        let count = self.endpoints.len();
 
-        let resp: RpcResponse<T> = builder.send().await?.json().await?;
+        let mut attempt = 0;
+        loop {
+            let index = (start + attempt) % count;
+            let mut builder = self.client.post(&self.endpoints[index]).json(req);
+            if let Some(ref token) = self.token {
+                builder = builder.header("Authorization", format!("Bearer {}", token));
+            }
+
+            let result = Self::send(builder).await;
+            attempt += 1;
+            match result {
+                Err(e) if attempt < count && is_transport_error(&e) => {
+                    let next = (start + attempt) % count;
+                    tracing::warn!(
+                        from = %self.endpoints[index],
+                        to = %self.endpoints[next],
+                        error = %e,
+                        "lotus endpoint unreachable, failing over"
+                    );
+                    self.current.store(next, Ordering::Relaxed);
+                    *self.failed_over_at.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// 当前优先节点；切换后超过 recovery_interval 时回到主节点
+    fn preferred_endpoint(&self) -> usize {
+        let current = self.current.load(Ordering::Relaxed);
+        if current == 0 {
+            return 0;
+        }
+        let mut failed_over_at = self.failed_over_at.lock().unwrap_or_else(|e| e.into_inner());
+        if failed_over_at.is_some_and(|t| t.elapsed() >= self.recovery_interval) {
+            *failed_over_at = None;
+            self.current.store(0, Ordering::Relaxed);
+            return 0;
+        }
+        current
+    }
+
+    async fn send<T: DeserializeOwned>(builder: reqwest::RequestBuilder) -> Result<Option<T>> {
+        let resp = builder.send().await?;
+        let status = resp.status();
+        let body = resp.bytes().await?;
+
+        // 5xx 响应体带有 JSON-RPC 错误时按应用错误处理，否则视为节点暂时不可用
+        let resp: RpcResponse<T> = match serde_json::from_slice(&body) {
+            Ok(resp) => resp,
+            Err(_) if status == reqwest::StatusCode::TOO_MANY_REQUESTS => return Err(RateLimited.into()),
+            Err(_) if status.is_server_error() => return Err(ServerError(status).into()),
+            Err(e) => return Err(e.into()),
+        };
 
         if let Some(err) = resp.error {
             anyhow::bail!("RPC error {}: {}", err.code, err.message);
         }
 
-        resp.result.ok_or_else(|| anyhow::anyhow!("empty result"))
+        Ok(resp.result)
+    }
+}
+
+/// 连接失败或超时（而不是节点返回的错误响应）
+fn is_transport_error(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| e.is_connect() || e.is_timeout())
+}
+
+/// 响应体不是 JSON-RPC 的 5xx 响应
+#[derive(Debug)]
+struct ServerError(reqwest::StatusCode);
+
+impl std::fmt::Display for ServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "lotus node returned HTTP {}", self.0)
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+/// 节点或网关返回 HTTP 429
+#[derive(Debug)]
+struct RateLimited;
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "lotus node returned HTTP 429 Too Many Requests")
     }
 }
+
+impl std::error::Error for RateLimited {}
+
+/// 被节点限流：HTTP 429，或网关以 JSON-RPC 错误返回的限流提示（如 Glif 的 "rate limit exceeded"）
+pub fn is_rate_limited(e: &anyhow::Error) -> bool {
+    if e.is::<RateLimited>() {
+        return true;
+    }
+    let msg = e.to_string().to_lowercase();
+    msg.contains("rate limit") || msg.contains("too many requests")
+}
+
+/// 可重试的错误：传输错误或 5xx 响应
+fn is_retryable_error(e: &anyhow::Error) -> bool {
+    is_transport_error(e) || e.is::<ServerError>()
+}
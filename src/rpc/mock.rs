@@ -0,0 +1,139 @@
+//! 模拟 Lotus JSON-RPC 节点，供无真实节点时的集成测试使用
+//!
+//! 内置 `LotusApi` 签名与广播流程用到的方法的默认响应；可按方法覆盖响应或注入错误，
+//! 并记录收到的全部请求
+
+use anyhow::Result;
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+
+/// 收到的一次调用；方法名不含 "Filecoin." 前缀
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct ReceivedRequest {
+    pub method: String,
+    pub params: Value,
+}
+
+#[derive(Default)]
+struct MockState {
+    responses: HashMap<String, std::result::Result<Value, (i64, String)>>,
+    requests: Vec<ReceivedRequest>,
+}
+
+#[allow(dead_code)]
+pub struct MockLotusServer {
+    state: Arc<Mutex<MockState>>,
+    server: JoinHandle<()>,
+}
+
+#[allow(dead_code)]
+impl MockLotusServer {
+    /// 在 127.0.0.1 的随机端口上启动，返回服务与 RPC 地址
+    pub async fn spawn() -> Result<(Self, String)> {
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let app = Router::new().route("/", post(handle)).with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/", listener.local_addr()?);
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+        Ok((Self { state, server }, url))
+    }
+
+    /// 覆盖某方法的返回值（如 "WalletBalance"）
+    pub fn set_response(&self, method: &str, result: Value) {
+        lock(&self.state).responses.insert(method.to_string(), Ok(result));
+    }
+
+    /// 令某方法返回 JSON-RPC 错误
+    pub fn set_error(&self, method: &str, code: i64, message: &str) {
+        lock(&self.state).responses.insert(method.to_string(), Err((code, message.to_string())));
+    }
+
+    /// 恢复某方法的默认响应
+    pub fn clear_response(&self, method: &str) {
+        lock(&self.state).responses.remove(method);
+    }
+
+    /// 按到达顺序返回收到的全部请求
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        lock(&self.state).requests.clone()
+    }
+
+    /// 某方法收到的请求
+    pub fn requests_for(&self, method: &str) -> Vec<ReceivedRequest> {
+        lock(&self.state).requests.iter().filter(|r| r.method == method).cloned().collect()
+    }
+}
+
+impl Drop for MockLotusServer {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+fn lock(state: &Mutex<MockState>) -> std::sync::MutexGuard<'_, MockState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+async fn handle(State(state): State<Arc<Mutex<MockState>>>, Json(req): Json<Value>) -> Json<Value> {
+    let id = req.get("id").cloned().unwrap_or(Value::Null);
+    let full_method = req.get("method").and_then(Value::as_str).unwrap_or_default();
+    let method = full_method.strip_prefix("Filecoin.").unwrap_or(full_method).to_string();
+    let params = req.get("params").cloned().unwrap_or(Value::Null);
+
+    let response = {
+        let mut state = lock(&state);
+        state.requests.push(ReceivedRequest { method: method.clone(), params: params.clone() });
+        state.responses.get(&method).cloned()
+    };
+    let result = match response {
+        Some(result) => result,
+        None => default_response(&method, &params),
+    };
+
+    Json(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } }),
+    })
+}
+
+/// 默认响应：余额 1 FIL、nonce 0、基础费用 100 attoFIL；gas 估算回填固定值
+fn default_response(method: &str, params: &Value) -> std::result::Result<Value, (i64, String)> {
+    match method {
+        "WalletBalance" => Ok(json!("1000000000000000000")),
+        "MpoolGetNonce" => Ok(json!(0)),
+        "MpoolPush" => Ok(json!({ "/": "bafy2bzacea3wsdh6y3a36tb3skempjoxqpuyompjbmfeyf34fi3uy6uue42v4" })),
+        "GasEstimateMessageGas" => {
+            let mut msg = params.get(0).cloned().unwrap_or(Value::Null);
+            if !msg.is_object() {
+                return Err((1, "GasEstimateMessageGas: missing message".to_string()));
+            }
+            msg["GasLimit"] = json!(1_000_000);
+            msg["GasFeeCap"] = json!("100000");
+            msg["GasPremium"] = json!("100000");
+            Ok(msg)
+        }
+        "ChainHead" => Ok(json!({
+            "Cids": [{ "/": "bafy2bzacebpwnhacqfqo7ztcr6tqgzayifykm6zzffnfwwdyvqwwb5yzhojfs" }],
+            "Blocks": [{ "ParentBaseFee": "100", "Messages": { "/": "bafy2bzacecmda75ovposbdateg7eyhwij65zklgyijgcjwynlklmqazpwlhba" } }],
+            "Height": 1000
+        })),
+        "StateMinerInfo" => Ok(json!({
+            "Owner": "f01000",
+            "Worker": "f01001",
+            "ControlAddresses": [],
+            "PeerId": null,
+            "SectorSize": 34359738368u64
+        })),
+        "StateMinerAvailableBalance" => Ok(json!("0")),
+        _ => Err((1, format!("method '{}' not supported by mock", method))),
+    }
+}
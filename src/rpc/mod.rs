@@ -1,5 +1,11 @@
 mod client;
 mod api;
+mod ws;
+mod mock;
 
-pub use client::LotusClient;
-pub use api::{LotusApi, Cid};
+pub use client::{is_rate_limited, LotusClient};
+#[allow(unused_imports)]
+pub use ws::LotusWsClient;
+#[allow(unused_imports)]
+pub use mock::{MockLotusServer, ReceivedRequest};
+pub use api::{LotusApi, Cid, MinerInfo, MinerPower, MsgLookup, EPOCH_DURATION_SECONDS};
@@ -0,0 +1,221 @@
+//! Lotus WebSocket JSON-RPC 客户端，支持 ChainNotify / MpoolSub 等订阅
+//!
+//! 订阅遵循 go-jsonrpc 的通道协议：调用返回通道 id，之后服务端以
+//! `xrpc.ch.val` 推送 `[通道 id, 值]`，以 `xrpc.ch.close` 关闭通道
+
+use anyhow::Result;
+use futures::stream::{SplitSink, Stream, StreamExt};
+use futures::SinkExt;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+type WsSink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, WsMessage>;
+
+/// 每个订阅缓冲的推送数，消费过慢时读取任务会等待
+const SUBSCRIPTION_BUFFER: usize = 64;
+
+#[derive(Default)]
+struct Pending {
+    /// 普通调用：请求 id → 响应
+    calls: HashMap<u64, oneshot::Sender<Result<Value>>>,
+    /// 已发出但尚未收到通道 id 的订阅请求：请求 id → 推送通道
+    subscribe_requests: HashMap<u64, mpsc::Sender<Result<Value>>>,
+    /// 已建立的订阅：通道 id → 推送通道
+    subscriptions: HashMap<u64, mpsc::Sender<Result<Value>>>,
+}
+
+/// 由 `daemon` 用于订阅 ChainNotify 跟踪链头，替代轮询 ChainHead
+pub struct LotusWsClient {
+    sink: tokio::sync::Mutex<WsSink>,
+    pending: Arc<Mutex<Pending>>,
+    next_id: AtomicU64,
+    reader: JoinHandle<()>,
+}
+
+impl LotusWsClient {
+    /// 连接节点；http(s):// 地址自动换成 ws(s)://
+    pub async fn connect(url: &str, token: Option<&str>) -> Result<Self> {
+        let url = ws_url(url);
+        let mut request = url.as_str().into_client_request()?;
+        if let Some(token) = token {
+            request.headers_mut().insert("Authorization", format!("Bearer {}", token).parse()?);
+        }
+
+        let (stream, _) = tokio_tungstenite::connect_async(request).await?;
+        let (sink, mut source) = stream.split();
+        let pending = Arc::new(Mutex::new(Pending::default()));
+
+        let reader_pending = pending.clone();
+        let reader = tokio::spawn(async move {
+            while let Some(frame) = source.next().await {
+                let text = match frame {
+                    Ok(WsMessage::Text(text)) => text,
+                    Ok(WsMessage::Close(_)) => break,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "websocket read failed");
+                        break;
+                    }
+                };
+                match serde_json::from_str::<Value>(&text) {
+                    Ok(msg) => dispatch(&reader_pending, msg).await,
+                    Err(e) => tracing::debug!(error = %e, "ignoring malformed websocket frame"),
+                }
+            }
+            close_all(&reader_pending);
+        });
+
+        Ok(Self {
+            sink: tokio::sync::Mutex::new(sink),
+            pending,
+            next_id: AtomicU64::new(1),
+            reader,
+        })
+    }
+
+    /// 与 `LotusClient::call` 相同：方法名不含 `Filecoin.` 前缀，`null` 结果视为错误
+    pub async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        self.call_optional(method, params)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("empty result"))
+    }
+
+    /// 与 `call` 相同，但 `null` 结果返回 `None` 而不是错误
+    pub async fn call_optional<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<Option<T>> {
+        let (tx, rx) = oneshot::channel();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        lock(&self.pending).calls.insert(id, tx);
+        self.send(id, &format!("Filecoin.{}", method), params).await?;
+
+        let result = rx.await.map_err(|_| anyhow::anyhow!("websocket connection closed"))??;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// 订阅链头变化（ChainNotify），每项为一组 HeadChange
+    pub async fn subscribe_chain_notify(&self) -> Result<impl Stream<Item = Result<Value>>> {
+        self.subscribe("ChainNotify", json!([])).await
+    }
+
+    /// 订阅消息池更新（MpoolSub），每项为一个 MpoolUpdate
+    pub async fn subscribe_mpool(&self) -> Result<impl Stream<Item = Result<Value>>> {
+        self.subscribe("MpoolSub", json!([])).await
+    }
+
+    async fn subscribe(&self, method: &str, params: Value) -> Result<impl Stream<Item = Result<Value>>> {
+        let (tx, rx) = oneshot::channel();
+        let (sub_tx, sub_rx) = mpsc::channel(SUBSCRIPTION_BUFFER);
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        {
+            // 先登记推送通道，避免响应之后紧跟的推送在登记前到达而丢失
+            let mut pending = lock(&self.pending);
+            pending.calls.insert(id, tx);
+            pending.subscribe_requests.insert(id, sub_tx);
+        }
+        self.send(id, &format!("Filecoin.{}", method), params).await?;
+        rx.await.map_err(|_| anyhow::anyhow!("websocket connection closed"))??;
+
+        Ok(futures::stream::unfold(sub_rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        }))
+    }
+
+    async fn send(&self, id: u64, method: &str, params: Value) -> Result<()> {
+        let req = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": id });
+        let result = self.sink.lock().await.send(WsMessage::Text(req.to_string())).await;
+        if let Err(e) = result {
+            let mut pending = lock(&self.pending);
+            pending.calls.remove(&id);
+            pending.subscribe_requests.remove(&id);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LotusWsClient {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+fn lock(pending: &Mutex<Pending>) -> std::sync::MutexGuard<'_, Pending> {
+    pending.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 分发一帧：调用响应、订阅推送或订阅关闭
+async fn dispatch(pending: &Mutex<Pending>, msg: Value) {
+    match msg.get("method").and_then(Value::as_str) {
+        Some("xrpc.ch.val") => {
+            let params = msg.get("params").and_then(Value::as_array);
+            let Some((chan, value)) = params.and_then(|p| Some((p.first()?.as_u64()?, p.get(1)?.clone()))) else {
+                return;
+            };
+            let sender = lock(pending).subscriptions.get(&chan).cloned();
+            if let Some(sender) = sender {
+                // 订阅端已丢弃时移除通道
+                if sender.send(Ok(value)).await.is_err() {
+                    lock(pending).subscriptions.remove(&chan);
+                }
+            }
+        }
+        Some("xrpc.ch.close") => {
+            let chan = msg.get("params").and_then(|p| p.get(0)).and_then(Value::as_u64);
+            if let Some(chan) = chan {
+                lock(pending).subscriptions.remove(&chan);
+            }
+        }
+        Some(_) => {}
+        None => {
+            let Some(id) = msg.get("id").and_then(Value::as_u64) else { return };
+            let result = match msg.get("error") {
+                Some(err) if !err.is_null() => Err(anyhow::anyhow!(
+                    "RPC error {}: {}",
+                    err.get("code").and_then(Value::as_i64).unwrap_or_default(),
+                    err.get("message").and_then(Value::as_str).unwrap_or_default()
+                )),
+                _ => Ok(msg.get("result").cloned().unwrap_or(Value::Null)),
+            };
+
+            let mut pending = lock(pending);
+            if let Some(sub) = pending.subscribe_requests.remove(&id) {
+                if let Some(chan) = result.as_ref().ok().and_then(Value::as_u64) {
+                    pending.subscriptions.insert(chan, sub);
+                }
+            }
+            if let Some(tx) = pending.calls.remove(&id) {
+                let _ = tx.send(result);
+            }
+        }
+    }
+}
+
+/// 连接断开：所有等待中的调用与订阅都收到错误
+fn close_all(pending: &Mutex<Pending>) {
+    let mut pending = lock(pending);
+    for (_, tx) in pending.calls.drain() {
+        let _ = tx.send(Err(anyhow::anyhow!("websocket connection closed")));
+    }
+    pending.subscribe_requests.clear();
+    for (_, sub) in pending.subscriptions.drain() {
+        let _ = sub.try_send(Err(anyhow::anyhow!("websocket connection closed")));
+    }
+}
+
+fn ws_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        url.to_string()
+    }
+}
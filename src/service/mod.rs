@@ -1,35 +1,96 @@
 use crate::chain::{
-    cbor, Address, BigInt, Message, SignedMessage,
-    WithdrawBalanceParams, ChangeOwnerParams, ChangeWorkerParams,
-    MarketWithdrawParams, METHOD_WITHDRAW_BALANCE, METHOD_CHANGE_OWNER,
-    METHOD_CHANGE_WORKER, METHOD_CONFIRM_CHANGE_WORKER,
-    METHOD_MARKET_WITHDRAW, STORAGE_MARKET_ACTOR,
+    cbor, format_fil, parse_fil, Address, BigInt, Message, SignedMessage,
+    WithdrawBalanceParams, ChangeOwnerParams, ChangeWorkerParams, ChangeBeneficiaryParams,
+    MarketWithdrawParams, AddVerifiedClientParams, CreateMinerParams, ExecParams, METHOD_WITHDRAW_BALANCE, METHOD_CHANGE_OWNER,
+    METHOD_CHANGE_WORKER, METHOD_CONFIRM_CHANGE_WORKER, METHOD_CHANGE_BENEFICIARY,
+    METHOD_MARKET_WITHDRAW, METHOD_ADD_VERIFIED_CLIENT, METHOD_INIT_EXEC,
+    METHOD_CREATE_MINER, INIT_ACTOR, STORAGE_MARKET_ACTOR, STORAGE_POWER_ACTOR, VERIFIED_REGISTRY_ACTOR,
 };
-use crate::config::Config;
-use crate::db::Store;
+use crate::chain::actors::multisig::{
+    self, ConstructorParams, ProposalHashData, ProposeParams, TxnIDParams,
+};
+use crate::config::{Config, GasConfig, GasStrategy};
+use crate::crypto;
+use crate::db::{MessageRecord, SigningAuditEntry, SigningLogEntry, Store};
 use crate::rpc::{LotusApi, Cid};
 use crate::wallet::Wallet;
 use anyhow::Result;
 
+/// 仅估算费用、不签名的结果
+pub struct FeeEstimate {
+    pub message: Message,
+    pub base_fee: BigInt,
+    /// 低于当前基础费用的 fee cap 会被消息池直接拒绝
+    pub min_fee_cap: BigInt,
+    /// gas_limit * gas_fee_cap，消息最多可能支付的费用
+    pub max_fee: BigInt,
+}
+
+/// withdraw_all_miners 中一个矿工的结果：(矿工, 提取金额 attoFIL, 广播结果)
+pub type MinerWithdrawal = (String, String, Result<Option<Cid>>);
+
+/// Miner Actor 允许的控制地址上限
+const MAX_CONTROL_ADDRESSES: usize = 10;
+
+/// 低于该值的 gas 优先费通常不会被矿工优先打包
+const TYPICAL_MIN_GAS_PREMIUM: i64 = 100_000;
+
 pub struct Executor<'a> {
     pub api: LotusApi,
     pub wallet: Wallet<'a>,
+    store: &'a Store,
+    min_send: BigInt,
+    gas: GasConfig,
+    dry_run: bool,
+    show_fees: bool,
+    assume_yes: bool,
 }
 
 impl<'a> Executor<'a> {
-    pub fn new(cfg: &Config, store: &'a Store) -> Self {
-        let api = LotusApi::new(&cfg.lotus.host, cfg.lotus.token.clone());
-        let password = cfg.get_password();
+    #[allow(dead_code)]
+    pub fn new(cfg: &Config, store: &'a Store) -> Result<Self> {
+        let api = LotusApi::from_config(cfg).with_miner_info_cache(store, cfg.miner_info_ttl_secs());
+        let password = if cfg.dry_run { String::new() } else { cfg.get_password_or_prompt()? };
         let wallet = Wallet::new(store, &password);
-        Self { api, wallet }
+        let min_send = min_send_amount(cfg);
+        let gas = cfg.gas.clone().unwrap_or_default();
+        Ok(Self { api, wallet, store, min_send, gas, dry_run: cfg.dry_run, show_fees: false, assume_yes: false })
+    }
+
+    /// 与 `new` 相同，但在阻塞线程池中派生加密密钥，避免 KDF 阻塞 tokio 运行时
+    pub async fn new_async(cfg: &Config, store: &'a Store) -> Result<Self> {
+        let api = LotusApi::from_config(cfg).with_miner_info_cache(store, cfg.miner_info_ttl_secs());
+        // dry-run 不签名，无需询问密码；repl 等会话复用已派生的密钥
+        let enc_key = match &cfg.session_key {
+            Some(key) => key.clone(),
+            None => {
+                let password = if cfg.dry_run { String::new() } else { cfg.get_password_or_prompt()? };
+                tokio::task::spawn_blocking(move || crypto::derive_key(&password)).await?
+            }
+        };
+        let wallet = Wallet::with_enc_key(store, enc_key);
+        let min_send = min_send_amount(cfg);
+        let gas = cfg.gas.clone().unwrap_or_default();
+        Ok(Self { api, wallet, store, min_send, gas, dry_run: cfg.dry_run, show_fees: false, assume_yes: false })
+    }
+
+    /// 签名前打印费用明细；assume_yes 为 false 时在终端询问是否继续
+    pub fn with_fee_preview(mut self, show_fees: bool, assume_yes: bool) -> Self {
+        self.show_fees = show_fees;
+        self.assume_yes = assume_yes;
+        self
     }
 
     #[allow(dead_code)]
-    pub async fn transfer(&self, from: &str, to: &str, amount: &str) -> Result<Cid> {
+    #[tracing::instrument(skip(self))]
+    pub async fn transfer(&self, from: &str, to: &str, amount: &str) -> Result<Option<Cid>> {
         let msg = self.build_message(from, to, 0, amount, vec![]).await?;
         self.sign_and_push(msg, from).await
     }
 
+    /// 按指定的 gas 参数转账；amount 单位为 FIL（如 "0.1"，也接受 "attoFIL" 后缀），无法解析时返回错误
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip(self))]
     pub async fn transfer_with_options(
         &self,
         from: &str,
@@ -40,7 +101,13 @@ impl<'a> Executor<'a> {
         gas_limit: i64,
         method: u64,
         nonce: Option<u64>,
-    ) -> Result<Cid> {
+        strategy: Option<GasStrategy>,
+    ) -> Result<Option<Cid>> {
+        let value = BigInt(parse_fil(amount).map_err(|e| anyhow::anyhow!("invalid amount '{}': {}", amount, e))?);
+        if method == 0 && value.0 < self.min_send.0 {
+            anyhow::bail!("Amount too small: {} < minimum {}", value, self.min_send);
+        }
+
         let actual_nonce = match nonce {
             Some(n) if n > 0 => n,
             _ => self.api.mpool_get_nonce(from).await?,
@@ -51,7 +118,7 @@ impl<'a> Executor<'a> {
             to: Address::from_string(to)?,
             from: Address::from_string(from)?,
             nonce: actual_nonce,
-            value: BigInt::from_str(amount),
+            value,
             gas_limit,
             gas_fee_cap: BigInt::from_str(gas_feecap),
             gas_premium: BigInt::from_str(gas_premium),
@@ -60,62 +127,457 @@ impl<'a> Executor<'a> {
         };
 
         if gas_limit == 0 {
-            msg = self.api.gas_estimate(&msg).await?;
+            msg = match strategy.or(self.gas.strategy) {
+                Some(strategy) => self.estimate_gas_with_strategy(&msg, strategy).await?,
+                None => self.api.gas_estimate(&msg).await?,
+            };
+        }
+
+        // fee cap 低于基础费用的消息会被消息池直接拒绝
+        let base_fee = self.api.chain_base_fee().await?;
+        if msg.gas_fee_cap.0 < base_fee.0 {
+            anyhow::bail!("gas fee cap {} is below the current base fee {}", msg.gas_fee_cap, base_fee);
+        }
+        if msg.gas_premium.0 < num_bigint::BigInt::from(TYPICAL_MIN_GAS_PREMIUM) {
+            eprintln!(
+                "Warning: gas premium {} attoFIL is below the typical miner tip of {} attoFIL; the message may be slow to land",
+                msg.gas_premium, TYPICAL_MIN_GAS_PREMIUM
+            );
         }
 
         self.sign_and_push(msg, from).await
     }
 
-    pub async fn miner_withdraw(&self, miner: &str, from: &str, amount: &str) -> Result<Cid> {
+    /// 转出 from 的全部余额减去最大 gas 费用（gas_limit * gas_fee_cap）
+    /// 实际消耗的 gas 通常低于上限，差额会留在 from 中
+    #[tracing::instrument(skip(self))]
+    pub async fn sweep(&self, from: &str, to: &str, min_amount: &num_bigint::BigInt) -> Result<Option<Cid>> {
+        use num_traits::Zero;
+
+        let balance = self.api.wallet_balance(from).await?;
+        let mut msg = self.build_message(from, to, 0, "0", vec![]).await?;
+        let max_fee = &msg.gas_fee_cap.0 * msg.gas_limit;
+        let value = &balance.0 - &max_fee;
+        if value <= num_bigint::BigInt::zero() {
+            anyhow::bail!(
+                "balance {} of {} does not cover the estimated max fee {} (gas limit {} x fee cap {})",
+                format_fil(&balance.0), from, format_fil(&max_fee), msg.gas_limit, msg.gas_fee_cap
+            );
+        }
+        if value < *min_amount {
+            anyhow::bail!("sweep amount {} is below --min-amount {}", format_fil(&value), format_fil(min_amount));
+        }
+        if value < self.min_send.0 {
+            anyhow::bail!("Amount too small: {} < minimum {}", value, self.min_send);
+        }
+
+        msg.value = BigInt(value);
+        self.sign_and_push(msg, from).await
+    }
+
+    /// 以新的 gas 参数重新签名消息池中的消息；nonce 保持不变，以替换原消息
+    #[tracing::instrument(skip(self, msg), fields(address = %msg.from, nonce = msg.nonce))]
+    pub async fn replace_message(&self, msg: Message) -> Result<Option<Cid>> {
+        let from = msg.from.to_string();
+        self.sign_and_push(msg, &from).await
+    }
+
+    /// 按策略分别估算 gas limit、premium 与 fee cap，再乘以策略倍数
+    /// slow 不放大 gas limit；其余策略与 Lotus 默认一样放大 1.25 倍以防 out of gas
+    pub async fn estimate_gas_with_strategy(&self, msg: &Message, strategy: GasStrategy) -> Result<Message> {
+        use num_traits::Zero;
+
+        let (limit_mult, feecap_mult, premium_mult, nblocks) = match strategy {
+            GasStrategy::Slow => (1.0, 1.0, 1.0, 20),
+            GasStrategy::Medium => (1.25, 1.25, 1.5, 10),
+            GasStrategy::Fast => (1.25, 1.5, 2.0, 2),
+            GasStrategy::Custom => (
+                1.25,
+                self.gas.feecap_multiplier.unwrap_or(1.0),
+                self.gas.premium_multiplier.unwrap_or(1.0),
+                10,
+            ),
+        };
+
+        let mut msg = msg.clone();
+        let gas_limit = self.api.gas_estimate_gas_limit(&msg).await?;
+        msg.gas_limit = (gas_limit as f64 * limit_mult) as i64;
+
+        // 与 GasEstimateMessageGas 一致，只填充调用方未指定（为 0）的字段
+        if msg.gas_premium.0.is_zero() {
+            let premium = self.api.gas_estimate_premium(nblocks, &msg.from.to_string(), msg.gas_limit).await?;
+            msg.gas_premium = scale_bigint(&premium, premium_mult);
+        }
+        if msg.gas_fee_cap.0.is_zero() {
+            let fee_cap = self.api.gas_estimate_fee_cap(&msg, 20).await?;
+            msg.gas_fee_cap = scale_bigint(&fee_cap, feecap_mult);
+        }
+
+        // fee cap 必须覆盖 premium，否则矿工拿不到全部小费
+        if msg.gas_fee_cap.0 < msg.gas_premium.0 {
+            msg.gas_fee_cap = msg.gas_premium.clone();
+        }
+        Ok(msg)
+    }
+
+    /// 构建并估算消息 gas，但不签名也不广播
+    pub async fn estimate_fee_only(&self, from: &str, to: &str, method: u64, amount: &str) -> Result<FeeEstimate> {
+        let message = self.build_message(from, to, method, amount, vec![]).await?;
+        let base_fee = self.api.chain_base_fee().await?;
+        let max_fee = BigInt(&message.gas_fee_cap.0 * message.gas_limit);
+        Ok(FeeEstimate {
+            message,
+            min_fee_cap: base_fee.clone(),
+            base_fee,
+            max_fee,
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn miner_withdraw(&self, miner: &str, from: &str, amount: &str) -> Result<Option<Cid>> {
         let params = WithdrawBalanceParams {
             amount: BigInt::from_str(amount),
         };
-        let params_bytes = cbor::serialize(&params)?;
+        let params_bytes = cbor::serialize_withdraw_balance(&params);
 
         let msg = self.build_message(from, miner, METHOD_WITHDRAW_BALANCE, "0", params_bytes).await?;
         self.sign_and_push(msg, from).await
     }
 
-    pub async fn market_withdraw(&self, address: &str, from: &str, amount: &str) -> Result<Cid> {
+    /// 并行查询多个矿工的可用余额，返回 (矿工, 余额)；单个矿工查询失败不影响其他矿工
+    pub async fn miner_available_balances(&self, miners: &[&str]) -> Vec<(String, Result<BigInt>)> {
+        let balances = futures::future::join_all(
+            miners.iter().map(|m| self.api.state_miner_available_balance(m)),
+        ).await;
+        miners.iter().map(|m| m.to_string()).zip(balances).collect()
+    }
+
+    /// 从多个矿工提取全部可用余额
+    ///
+    /// `miners` 为 (矿工, 可用余额) 列表，余额为零的矿工被跳过；
+    /// 所有消息由同一地址发送，nonce 依次递增。单个矿工失败不影响其他矿工（失败的消息不占用 nonce），
+    /// 返回每个矿工的 (矿工, 提取金额, 结果)；dry-run 时结果为 Ok(None)
+    #[tracing::instrument(skip(self, miners))]
+    pub async fn withdraw_all_miners(&self, miners: Vec<(&str, &str)>, from: &str) -> Result<Vec<MinerWithdrawal>> {
+        let mut nonce = self.api.mpool_get_nonce(from).await?;
+        let mut results = Vec::new();
+
+        for (miner, balance) in miners {
+            let amount = BigInt::from_str(balance);
+            if amount.0 <= num_bigint::BigInt::from(0) {
+                continue;
+            }
+
+            let params_bytes = cbor::serialize_withdraw_balance(&WithdrawBalanceParams { amount });
+            let result = match self.build_message_with_nonce(from, miner, METHOD_WITHDRAW_BALANCE, "0", params_bytes, nonce).await {
+                Ok(msg) => self.sign_and_push(msg, from).await,
+                Err(e) => Err(e),
+            };
+            if result.is_ok() {
+                nonce += 1;
+            }
+            results.push((miner.to_string(), balance.to_string(), result));
+        }
+        Ok(results)
+    }
+
+    /// 向多个地址分别转账：nonce 从链上 nonce 起依次递增，全部签名后以最多 `concurrency` 个并发广播
+    ///
+    /// 单条广播失败不影响其他消息；每条完成时调用 `on_result(序号, 结果)`，
+    /// 返回的结果与 `recipients` 顺序一致；dry-run 时只打印消息并返回 None
+    #[tracing::instrument(skip(self, recipients, on_result), fields(count = recipients.len()))]
+    pub async fn multi_send(
+        &self,
+        from: &str,
+        recipients: &[(String, BigInt)],
+        concurrency: usize,
+        mut on_result: impl FnMut(usize, &Result<Cid>),
+    ) -> Result<Option<Vec<Result<Cid>>>> {
+        use futures::StreamExt;
+
+        let nonce = self.api.mpool_get_nonce(from).await?;
+        let mut messages = Vec::with_capacity(recipients.len());
+        for (i, (to, amount)) in recipients.iter().enumerate() {
+            if amount.0 < self.min_send.0 {
+                anyhow::bail!("recipient {} ({}): amount too small: {} < minimum {}", i + 1, to, amount, self.min_send);
+            }
+            let msg = self.build_message_with_nonce(from, to, 0, &amount.to_string(), vec![], nonce + i as u64).await
+                .map_err(|e| e.context(format!("recipient {} ({})", i + 1, to)))?;
+            messages.push(msg);
+        }
+
+        if self.dry_run {
+            for msg in &messages {
+                println!("{}", serde_json::to_string_pretty(msg)?);
+            }
+            println!("Dry run: {} messages were not signed or pushed", messages.len());
+            return Ok(None);
+        }
+
+        // 先全部签名：任一签名或审计记录失败时不广播任何消息
+        let mut signed = Vec::with_capacity(messages.len());
+        for msg in messages {
+            signed.push(self.sign_message(msg, from).await?);
+        }
+
+        let mut results: Vec<Option<Result<Cid>>> = (0..signed.len()).map(|_| None).collect();
+        let mut pushes = futures::stream::iter(signed.into_iter().enumerate())
+            .map(|(i, signed)| async move { (i, self.push_signed(signed).await) })
+            .buffer_unordered(concurrency.max(1));
+        while let Some((i, result)) = pushes.next().await {
+            on_result(i, &result);
+            results[i] = Some(result);
+        }
+        Ok(Some(results.into_iter().flatten().collect()))
+    }
+
+    /// 通过多签 owner 发起矿工提现提案：内层为矿工 WithdrawBalance，外层为多签 Propose
+    #[tracing::instrument(skip(self))]
+    pub async fn multisig_propose_miner_withdraw(&self, msig: &str, miner: &str, from: &str, amount: &str) -> Result<Option<Cid>> {
+        let withdraw_params = WithdrawBalanceParams {
+            amount: BigInt::from_str(amount),
+        };
+        let params = cbor::serialize_withdraw_balance(&withdraw_params);
+        self.multisig_propose(msig, miner, "0", METHOD_WITHDRAW_BALANCE, params, from).await
+    }
+
+    /// 通过 Init Actor 创建多签钱包，value 为创建时转入的金额（attoFIL）
+    #[tracing::instrument(skip(self))]
+    pub async fn multisig_create(
+        &self,
+        signers: &[String],
+        threshold: u64,
+        unlock_duration: i64,
+        start_epoch: i64,
+        value: &str,
+        from: &str,
+    ) -> Result<Option<Cid>> {
+        if threshold == 0 || threshold as usize > signers.len() {
+            anyhow::bail!("threshold must be between 1 and the number of signers ({})", signers.len());
+        }
+        let constructor = ConstructorParams {
+            signers: signers.iter().map(|s| Address::from_string(s)).collect::<Result<_>>()?,
+            num_approvals_threshold: threshold,
+            unlock_duration,
+            start_epoch,
+        };
+
+        let network_version = self.api.state_network_version().await?;
+        let code_cids = self.api.state_actor_code_cids(network_version).await?;
+        let code_cid = code_cids.get("multisig")
+            .ok_or_else(|| anyhow::anyhow!("no multisig code CID for network version {}", network_version))?;
+
+        let params = ExecParams {
+            code_cid: cbor::cid_from_string(&code_cid.root)?,
+            constructor_params: cbor::serialize_constructor_params(&constructor),
+        };
+        let params_bytes = cbor::serialize_exec_params(&params);
+
+        let msg = self.build_message(from, INIT_ACTOR, METHOD_INIT_EXEC, value, params_bytes).await?;
+        self.sign_and_push(msg, from).await
+    }
+
+    /// 发起多签提案：由多签向 to 发送 value 并调用 method
+    #[tracing::instrument(skip(self, params))]
+    pub async fn multisig_propose(
+        &self,
+        msig: &str,
+        to: &str,
+        value: &str,
+        method: u64,
+        params: Vec<u8>,
+        from: &str,
+    ) -> Result<Option<Cid>> {
+        let propose = ProposeParams {
+            to: Address::from_string(to)?,
+            value: BigInt::from_str(value),
+            method,
+            params,
+        };
+        let params_bytes = cbor::serialize_propose_params(&propose);
+
+        let msg = self.build_message(from, msig, multisig::METHOD_PROPOSE, "0", params_bytes).await?;
+        self.sign_and_push(msg, from).await
+    }
+
+    /// 批准多签提案；proposal_hash 为空时不校验提案内容
+    #[tracing::instrument(skip(self, proposal_hash))]
+    pub async fn multisig_approve(&self, msig: &str, txid: i64, proposal_hash: Vec<u8>, from: &str) -> Result<Option<Cid>> {
+        let params = cbor::serialize_txn_id_params(&TxnIDParams { id: txid, proposal_hash });
+        let msg = self.build_message(from, msig, multisig::METHOD_APPROVE, "0", params).await?;
+        self.sign_and_push(msg, from).await
+    }
+
+    /// 取消多签提案（只有提案人可以取消）
+    #[tracing::instrument(skip(self, proposal_hash))]
+    pub async fn multisig_cancel(&self, msig: &str, txid: i64, proposal_hash: Vec<u8>, from: &str) -> Result<Option<Cid>> {
+        let params = cbor::serialize_txn_id_params(&TxnIDParams { id: txid, proposal_hash });
+        let msg = self.build_message(from, msig, multisig::METHOD_CANCEL, "0", params).await?;
+        self.sign_and_push(msg, from).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn market_withdraw(&self, address: &str, from: &str, amount: &str) -> Result<Option<Cid>> {
         let params = MarketWithdrawParams {
             provider_or_client: Address::from_string(address)?,
             amount: BigInt::from_str(amount),
         };
-        let params_bytes = cbor::serialize(&params)?;
+        let params_bytes = cbor::serialize_market_withdraw(&params);
 
         let msg = self.build_message(from, STORAGE_MARKET_ACTOR, METHOD_MARKET_WITHDRAW, "0", params_bytes).await?;
         self.sign_and_push(msg, from).await
     }
 
-    pub async fn change_owner(&self, miner: &str, new_owner: &str, from: &str) -> Result<Cid> {
+    /// 公证人向客户分配 DataCap（Verified Registry AddVerifiedClient）
+    #[tracing::instrument(skip(self))]
+    pub async fn datacap_transfer(&self, to: &str, amount_bytes: &str, from: &str) -> Result<Option<Cid>> {
+        let params = AddVerifiedClientParams {
+            address: Address::from_string(to)?,
+            allowance: BigInt::from_str(amount_bytes),
+        };
+        let params_bytes = cbor::serialize_add_verified_client(&params);
+
+        let msg = self.build_message(from, VERIFIED_REGISTRY_ACTOR, METHOD_ADD_VERIFIED_CLIENT, "0", params_bytes).await?;
+        self.sign_and_push(msg, from).await
+    }
+
+    /// 创建矿工 Actor（Power Actor CreateMiner），initial_collateral 作为消息金额发送
+    #[tracing::instrument(skip(self))]
+    pub async fn create_miner(
+        &self,
+        owner: &str,
+        worker: &str,
+        window_post_proof_type: i64,
+        peer_id_hex: &str,
+        from: &str,
+        initial_collateral: &str,
+    ) -> Result<Option<Cid>> {
+        let params = CreateMinerParams {
+            owner: Address::from_string(owner)?,
+            worker: Address::from_string(worker)?,
+            window_post_proof_type,
+            peer: hex::decode(peer_id_hex)?,
+            multiaddrs: vec![],
+        };
+        let params_bytes = cbor::serialize_create_miner(&params);
+
+        let msg = self.build_message(from, STORAGE_POWER_ACTOR, METHOD_CREATE_MINER, initial_collateral, params_bytes).await?;
+        self.sign_and_push(msg, from).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn change_owner(&self, miner: &str, new_owner: &str, from: &str) -> Result<Option<Cid>> {
         let params = ChangeOwnerParams {
             new_owner: Address::from_string(new_owner)?,
         };
-        let params_bytes = cbor::serialize(&params)?;
+        let params_bytes = cbor::serialize_change_owner(&params);
 
         let msg = self.build_message(from, miner, METHOD_CHANGE_OWNER, "0", params_bytes).await?;
         self.sign_and_push(msg, from).await
     }
 
-    pub async fn propose_change_worker(&self, miner: &str, new_worker: &str, from: &str) -> Result<Cid> {
+    /// 提议更换 worker；与 lotus-miner 相同，保留现有的控制地址
+    #[tracing::instrument(skip(self))]
+    pub async fn propose_change_worker(&self, miner: &str, new_worker: &str, from: &str) -> Result<Option<Cid>> {
+        let info = self.api.state_miner_info_uncached(miner).await?;
         let params = ChangeWorkerParams {
             new_worker: Address::from_string(new_worker)?,
-            new_control_addresses: vec![],
+            new_control_addresses: info.control_addresses.unwrap_or_default().iter()
+                .map(|a| Address::from_string(a))
+                .collect::<Result<Vec<_>>>()?,
         };
-        let params_bytes = cbor::serialize(&params)?;
+        let params_bytes = cbor::serialize_change_worker(&params);
 
         let msg = self.build_message(from, miner, METHOD_CHANGE_WORKER, "0", params_bytes).await?;
         self.sign_and_push(msg, from).await
     }
 
-    pub async fn confirm_change_worker(&self, miner: &str, from: &str) -> Result<Cid> {
+    /// 为矿工添加控制地址；worker 保持不变，只修改控制地址列表
+    #[tracing::instrument(skip(self))]
+    pub async fn add_control_address(&self, miner: &str, control: &str, from: &str) -> Result<Option<Cid>> {
+        self.change_control_addresses(miner, control, from, true).await
+    }
+
+    /// 从矿工移除控制地址；worker 保持不变
+    #[tracing::instrument(skip(self))]
+    pub async fn remove_control_address(&self, miner: &str, control: &str, from: &str) -> Result<Option<Cid>> {
+        self.change_control_addresses(miner, control, from, false).await
+    }
+
+    /// 以当前 worker 调用 ChangeWorkerAddress，只增删一个控制地址；
+    /// 链上控制地址为 ID 地址，因此先通过 StateLookupID 解析（同时确认地址已在链上）
+    async fn change_control_addresses(&self, miner: &str, control: &str, from: &str, add: bool) -> Result<Option<Cid>> {
+        let info = self.api.state_miner_info_uncached(miner).await?;
+        let owner = Address::from_string(&info.owner)?;
+        let sender = Address::from_string(&self.api.state_lookup_id(from).await?)?;
+        if sender != owner {
+            anyhow::bail!("control addresses can only be changed by the owner {}, not {}", info.owner, from);
+        }
+
+        let control_id = self.api.state_lookup_id(control).await
+            .map_err(|e| anyhow::anyhow!("control address {} not found on chain: {}", control, e))?;
+        let control_id = Address::from_string(&control_id)?;
+        let mut controls = info.control_addresses.unwrap_or_default().iter()
+            .map(|a| Address::from_string(a))
+            .collect::<Result<Vec<_>>>()?;
+
+        let exists = controls.contains(&control_id);
+        match (add, exists) {
+            (true, true) => anyhow::bail!("{} is already a control address of {}", control, miner),
+            (false, false) => anyhow::bail!("{} is not a control address of {}", control, miner),
+            (true, false) => {
+                if controls.len() >= MAX_CONTROL_ADDRESSES {
+                    anyhow::bail!("miner {} already has the maximum of {} control addresses", miner, MAX_CONTROL_ADDRESSES);
+                }
+                controls.push(control_id);
+            }
+            (false, true) => controls.retain(|a| *a != control_id),
+        }
+
+        let params = ChangeWorkerParams {
+            new_worker: Address::from_string(&info.worker)?,
+            new_control_addresses: controls,
+        };
+        let msg = self.build_message(from, miner, METHOD_CHANGE_WORKER, "0", cbor::serialize_change_worker(&params)).await?;
+        self.sign_and_push(msg, from).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn confirm_change_worker(&self, miner: &str, from: &str) -> Result<Option<Cid>> {
         let msg = self.build_message(from, miner, METHOD_CONFIRM_CHANGE_WORKER, "0", vec![]).await?;
         self.sign_and_push(msg, from).await
     }
 
-    async fn build_message(&self, from: &str, to: &str, method: u64, value: &str, params: Vec<u8>) -> Result<Message> {
+    /// 修改矿工受益人（FIP-0029）：由 owner 发起提案；新受益人不是 owner 时，
+    /// 还需新受益人以相同参数再次调用确认，提案才会生效
+    #[tracing::instrument(skip(self))]
+    pub async fn change_beneficiary(&self, miner: &str, new_beneficiary: &str, quota: &str, expiration: i64, from: &str) -> Result<Option<Cid>> {
+        let params = ChangeBeneficiaryParams {
+            new_beneficiary: Address::from_string(new_beneficiary)?,
+            new_quota: BigInt::from_str(quota),
+            new_expiration: expiration,
+        };
+        let params_bytes = cbor::serialize_change_beneficiary(&params);
+
+        let msg = self.build_message(from, miner, METHOD_CHANGE_BENEFICIARY, "0", params_bytes).await?;
+        self.sign_and_push(msg, from).await
+    }
+
+    /// 构建消息并估算 gas（使用链上 nonce），不签名
+    pub async fn build_message(&self, from: &str, to: &str, method: u64, value: &str, params: Vec<u8>) -> Result<Message> {
         let nonce = self.api.mpool_get_nonce(from).await?;
+        self.build_message_with_nonce(from, to, method, value, params, nonce).await
+    }
 
+    async fn build_message_with_nonce(
+        &self,
+        from: &str,
+        to: &str,
+        method: u64,
+        value: &str,
+        params: Vec<u8>,
+        nonce: u64,
+    ) -> Result<Message> {
         let msg = Message {
             version: 0,
             to: Address::from_string(to)?,
@@ -132,9 +594,182 @@ impl<'a> Executor<'a> {
         self.api.gas_estimate(&msg).await
     }
 
-    async fn sign_and_push(&self, msg: Message, from: &str) -> Result<Cid> {
+    /// 签名并广播；dry-run 时只打印消息并返回 None，调用方可以继续预览后续消息
+    #[tracing::instrument(skip_all, fields(address = from, method = msg.method, cid))]
+    async fn sign_and_push(&self, msg: Message, from: &str) -> Result<Option<Cid>> {
+        if self.show_fees {
+            self.preview_fees(&msg).await?;
+        }
+        if self.dry_run {
+            self.sign_only(&msg)?;
+            return Ok(None);
+        }
+        // 审计记录写入失败时不广播，保证每条离开本进程的签名都有记录
+        let signed = self.sign_message(msg, from).await?;
+        self.push_signed(signed).await.map(Some)
+    }
+
+    /// 广播已签名（并已写入审计记录）的消息，记录签名日志与消息历史
+    async fn push_signed(&self, signed: SignedMessage) -> Result<Cid> {
+        let result = self.api.mpool_push(&signed).await;
+        self.record_signing(&signed.message, &result).await;
+        match &result {
+            Ok(cid) => {
+                self.record_message(&signed, cid).await;
+                self.api.invalidate_miner_info(&signed.message.to.to_string()).await;
+                tracing::Span::current().record("cid", cid.root.as_str());
+                tracing::info!(nonce = signed.message.nonce, "message signed and pushed");
+            }
+            Err(e) => tracing::warn!(error = %e, "message push failed"),
+        }
+        result
+    }
+
+    /// 签名已构建好的消息（nonce 与 gas 不变）并广播
+    pub async fn sign_and_push_message(&self, msg: Message) -> Result<Option<Cid>> {
+        let from = msg.from.to_string();
+        self.sign_and_push(msg, &from).await
+    }
+
+    /// 只签名不广播；与广播路径一样先写入审计记录，写入失败时不返回签名
+    pub async fn sign_message(&self, msg: Message, from: &str) -> Result<SignedMessage> {
         let sig = self.wallet.sign(&msg, from)?;
         let signed = SignedMessage { message: msg, signature: sig };
-        self.api.mpool_push(&signed).await
+        self.record_signing_event(&signed).await?;
+        Ok(signed)
+    }
+
+    /// --show-fees：打印 gas 费用明细，未指定 --yes 时询问是否继续（dry-run 不会签名，无需询问）
+    async fn preview_fees(&self, msg: &Message) -> Result<()> {
+        let base_fee = self.api.chain_base_fee().await?;
+        let max_fee = &msg.gas_fee_cap.0 * msg.gas_limit;
+        // 每单位 gas 实际支付 min(基础费用 + 优先费, fee cap)
+        let expected_per_gas = (&base_fee.0 + &msg.gas_premium.0).min(msg.gas_fee_cap.0.clone());
+        let expected_fee = &expected_per_gas * msg.gas_limit;
+        let priority_fee = &msg.gas_premium.0 * msg.gas_limit;
+
+        println!("GasLimit:     {} units", msg.gas_limit);
+        println!("BaseFee:      {} attoFIL", base_fee);
+        println!("MaxFee:       {} attoFIL ({})", max_fee, format_fil(&max_fee));
+        println!("ExpectedFee:  {} attoFIL ({})", expected_fee, format_fil(&expected_fee));
+        println!("Priority Fee: {} attoFIL ({})", priority_fee, format_fil(&priority_fee));
+
+        if !self.assume_yes && !self.dry_run && !confirm_proceed()? {
+            anyhow::bail!("aborted");
+        }
+        Ok(())
     }
+
+    /// --dry-run：打印构建好的消息与摘要，不调用 Wallet::sign，也不广播
+    fn sign_only(&self, msg: &Message) -> Result<()> {
+        println!("{}", serde_json::to_string_pretty(msg)?);
+        println!("From:             {}", msg.from);
+        println!("To:               {}", msg.to);
+        println!("Value:            {}", format_fil(&msg.value.0));
+        println!("Method:           {}", msg.method);
+        println!("EstimatedGasCost: {} (max: gas limit x fee cap)", format_fil(&(&msg.gas_fee_cap.0 * msg.gas_limit)));
+        println!("Dry run: message was not signed or pushed");
+        Ok(())
+    }
+
+    async fn record_signing_event(&self, signed: &SignedMessage) -> Result<()> {
+        let msg = &signed.message;
+        let entry = SigningAuditEntry {
+            id: 0,
+            timestamp: chrono::Utc::now(),
+            address: msg.from.to_string(),
+            key_type: if signed.signature.sig_type == 2 { "bls" } else { "secp256k1" }.to_string(),
+            cid_hex: hex::encode(cbor::compute_cid_bytes(&cbor::serialize_message(msg)?)),
+            to_addr: msg.to.to_string(),
+            value_attofil: msg.value.to_string(),
+            method: msg.method,
+            nonce: msg.nonce,
+            signed_by_pid: std::process::id(),
+        };
+        self.store.record_signing_event_async(entry).await
+            .map_err(|e| anyhow::anyhow!("failed to write signing audit record, message not pushed: {}", e))?;
+        Ok(())
+    }
+
+    /// 写入 messages 表；与签名日志相同，记录失败只打印警告
+    async fn record_message(&self, signed: &SignedMessage, cid: &Cid) {
+        let msg = &signed.message;
+        let record = MessageRecord {
+            id: 0,
+            cid: cid.root.clone(),
+            from_addr: msg.from.to_string(),
+            to_addr: msg.to.to_string(),
+            value: msg.value.to_string(),
+            method: msg.method,
+            gas_limit: msg.gas_limit,
+            gas_fee_cap: msg.gas_fee_cap.to_string(),
+            gas_premium: msg.gas_premium.to_string(),
+            nonce: msg.nonce,
+            key_type: if signed.signature.sig_type == 2 { "bls" } else { "secp256k1" }.to_string(),
+            pushed_at: chrono::Utc::now(),
+        };
+        if let Err(e) = self.store.record_message_async(record).await {
+            eprintln!("Warning: failed to record message history: {}", e);
+        }
+    }
+
+    /// 写入签名日志；记录失败只打印警告，不影响已推送的消息
+    async fn record_signing(&self, msg: &Message, result: &Result<Cid>) {
+        let entry = SigningLogEntry {
+            id: 0,
+            timestamp: chrono::Utc::now(),
+            from_address: msg.from.to_string(),
+            to_address: msg.to.to_string(),
+            value: msg.value.0.to_string(),
+            method: msg.method,
+            nonce: msg.nonce,
+            cid: result.as_ref().ok().map(|c| c.root.clone()),
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        if let Err(e) = self.store.insert_signing_log_async(entry).await {
+            eprintln!("Warning: failed to write signing log: {}", e);
+        }
+    }
+}
+
+/// 按浮点倍数缩放金额（精确到千分之一）
+fn scale_bigint(value: &BigInt, multiplier: f64) -> BigInt {
+    let permille = (multiplier * 1000.0).round().max(0.0) as u64;
+    BigInt(&value.0 * permille / 1000u64)
+}
+
+/// 在终端询问 "Proceed? [y/N]"，仅输入 y/yes 时返回 true
+fn confirm_proceed() -> Result<bool> {
+    use std::io::Write;
+    print!("Proceed? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+fn min_send_amount(cfg: &Config) -> BigInt {
+    BigInt::from_str(cfg.lotus.min_send_attofil.as_deref().unwrap_or("0"))
+}
+
+/// 计算多签提案哈希：blake2b-256(cbor(ProposalHashData))
+pub fn multisig_proposal_hash(
+    proposer: &str,
+    to: &str,
+    value: &str,
+    method: u64,
+    params: Vec<u8>,
+) -> Result<Vec<u8>> {
+    let data = ProposalHashData {
+        requester: Address::from_string(proposer)?,
+        to: Address::from_string(to)?,
+        value: BigInt::from_str(value),
+        method,
+        params,
+    };
+    let hash = blake2b_simd::Params::new()
+        .hash_length(32)
+        .hash(&cbor::serialize_proposal_hash_data(&data));
+    Ok(hash.as_bytes().to_vec())
 }
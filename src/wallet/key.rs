@@ -1,7 +1,10 @@
+use crate::chain::Address;
 use anyhow::Result;
 use rand::rngs::OsRng;
 use secp256k1::Secp256k1;
+use zeroize::Zeroizing;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyType {
     Secp256k1,
@@ -16,6 +19,7 @@ impl KeyType {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
             "secp256k1" => Ok(KeyType::Secp256k1),
@@ -28,7 +32,8 @@ impl KeyType {
 #[allow(dead_code)]
 pub struct PrivateKey {
     pub key_type: KeyType,
-    pub private_key: Vec<u8>,
+    /// 释放时自动清零
+    pub private_key: Zeroizing<Vec<u8>>,
     pub public_key: Vec<u8>,
 }
 
@@ -40,33 +45,128 @@ impl PrivateKey {
         }
     }
 
+    /// 从私钥字节恢复密钥对（BLS 私钥为 Filecoin 小端格式）
+    pub fn from_bytes(key_type: KeyType, private_key: &[u8]) -> Result<Self> {
+        let public_key = match key_type {
+            KeyType::Secp256k1 => {
+                let secp = Secp256k1::new();
+                let secret = secp256k1::SecretKey::from_slice(private_key)?;
+                let pubkey = secp256k1::PublicKey::from_secret_key(&secp, &secret);
+                pubkey.serialize_uncompressed().to_vec()
+            }
+            KeyType::BLS => {
+                use blst::min_pk::SecretKey as BlsSecretKey;
+
+                if private_key.len() != 32 {
+                    anyhow::bail!("invalid BLS private key length: {}", private_key.len());
+                }
+                let mut key_be = Zeroizing::new([0u8; 32]);
+                for i in 0..32 {
+                    key_be[i] = private_key[31 - i];
+                }
+                let sk = BlsSecretKey::from_bytes(&*key_be)
+                    .map_err(|e| anyhow::anyhow!("invalid BLS key: {:?}", e))?;
+                sk.sk_to_pk().to_bytes().to_vec()
+            }
+        };
+
+        Ok(Self {
+            key_type,
+            private_key: Zeroizing::new(private_key.to_vec()),
+            public_key,
+        })
+    }
+
+    /// 密钥对应的 Filecoin 地址（f1 或 f3）
+    pub fn address(&self) -> Result<Address> {
+        address_from_public_key(self.key_type, &self.public_key)
+    }
+
     fn generate_secp256k1() -> Result<Self> {
         let secp = Secp256k1::new();
         let (secret_key, public_key) = secp.generate_keypair(&mut OsRng);
 
         Ok(Self {
             key_type: KeyType::Secp256k1,
-            private_key: secret_key.secret_bytes().to_vec(),
+            private_key: Zeroizing::new(secret_key.secret_bytes().to_vec()),
             public_key: public_key.serialize_uncompressed().to_vec(),
         })
     }
 
     fn generate_bls() -> Result<Self> {
-        use blst::min_pk::{SecretKey as BlsSecretKey};
-
         // Generate random 32 bytes for private key
-        let mut ikm = [0u8; 32];
-        rand::RngCore::fill_bytes(&mut OsRng, &mut ikm);
+        let mut ikm = Zeroizing::new([0u8; 32]);
+        rand::RngCore::fill_bytes(&mut OsRng, &mut *ikm);
+        Self::bls_from_ikm(&*ikm)
+    }
+
+    /// 从主种子确定性派生第 index 个密钥
+    /// okm = HKDF-SHA256(ikm=master_seed, salt=index 大端字节, info="lotus-sign-key")
+    pub fn derive_deterministic(key_type: KeyType, master_seed: &[u8], index: u64) -> Result<Self> {
+        use hkdf::Hkdf;
+        use sha2::Sha256;
+
+        let hk = Hkdf::<Sha256>::new(Some(&index.to_be_bytes()), master_seed);
+        let mut okm = Zeroizing::new([0u8; 32]);
+        hk.expand(b"lotus-sign-key", &mut *okm)
+            .map_err(|e| anyhow::anyhow!("HKDF expand failed: {}", e))?;
+
+        match key_type {
+            KeyType::Secp256k1 => Self::from_bytes(KeyType::Secp256k1, &*okm),
+            KeyType::BLS => Self::bls_from_ikm(&*okm),
+        }
+    }
+
+    /// 从 BIP-39 种子派生 secp256k1 密钥（BIP-32 根密钥 m）
+    pub fn from_bip39_seed(seed: &[u8]) -> Result<Self> {
+        let (key, _chain_code) = bip32_master(seed)?;
+        Self::from_bytes(KeyType::Secp256k1, &*key)
+    }
+
+    /// 按 BIP-32 路径从种子派生 secp256k1 子密钥，如 "m/44'/461'/0'/0/0"（461 为 Filecoin 币种号）
+    ///
+    /// 种子可以是 BIP-39 种子或任意原始种子（16-64 字节）；路径 "m" 等同于 `from_bip39_seed`
+    pub fn derive_child(seed: &[u8], path: &str) -> Result<Self> {
+        use secp256k1::{PublicKey, Scalar, SecretKey};
+
+        let secp = Secp256k1::new();
+        let (key, mut chain_code) = bip32_master(seed)?;
+        let mut secret = SecretKey::from_slice(&*key)?;
+
+        for index in parse_derivation_path(path)? {
+            // 硬化派生时 data 含父私钥，释放时清零
+            let mut data = Zeroizing::new(Vec::with_capacity(37));
+            if index >= BIP32_HARDENED {
+                data.push(0);
+                data.extend_from_slice(&*Zeroizing::new(secret.secret_bytes()));
+            } else {
+                data.extend_from_slice(&PublicKey::from_secret_key(&secp, &secret).serialize());
+            }
+            data.extend_from_slice(&index.to_be_bytes());
+
+            let (il, ir) = hmac_sha512(&*chain_code, &data)?;
+            let tweak = Scalar::from_be_bytes(*il)
+                .map_err(|_| anyhow::anyhow!("invalid child key at index {} (IL >= n)", index))?;
+            secret = secret.add_tweak(&tweak)
+                .map_err(|_| anyhow::anyhow!("invalid child key at index {}", index))?;
+            chain_code = ir;
+        }
+
+        Self::from_bytes(KeyType::Secp256k1, &*Zeroizing::new(secret.secret_bytes()))
+    }
+
+    fn bls_from_ikm(ikm: &[u8]) -> Result<Self> {
+        use blst::min_pk::{SecretKey as BlsSecretKey};
 
         // Derive BLS secret key
-        let sk = BlsSecretKey::key_gen(&ikm, &[])
+        let sk = BlsSecretKey::key_gen(ikm, &[])
             .map_err(|e| anyhow::anyhow!("BLS key generation failed: {:?}", e))?;
 
         // Get private key bytes (big-endian from blst)
         let sk_bytes = sk.to_bytes();
 
         // Reverse to little-endian for Filecoin storage
-        let mut private_key = vec![0u8; 32];
+        let mut private_key = Zeroizing::new(vec![0u8; 32]);
         for i in 0..32 {
             private_key[i] = sk_bytes[31 - i];
         }
@@ -82,3 +182,108 @@ impl PrivateKey {
         })
     }
 }
+
+/// BIP-32 硬化派生的序号起点（2^31）
+const BIP32_HARDENED: u32 = 0x8000_0000;
+
+/// HMAC-SHA512 输出的左右两半 (IL, IR)
+type SplitKey = (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>);
+
+/// BIP-32 主密钥生成：I = HMAC-SHA512(key="Bitcoin seed", seed)，返回 (私钥 IL, 链码 IR)
+fn bip32_master(seed: &[u8]) -> Result<SplitKey> {
+    hmac_sha512(b"Bitcoin seed", seed)
+}
+
+/// HMAC-SHA512，拆分为左右各 32 字节；两半均为密钥材料，释放时清零
+fn hmac_sha512(key: &[u8], data: &[u8]) -> Result<SplitKey> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha512;
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(key)
+        .map_err(|e| anyhow::anyhow!("HMAC init failed: {}", e))?;
+    mac.update(data);
+    let mut i = mac.finalize().into_bytes();
+
+    let mut left = Zeroizing::new([0u8; 32]);
+    let mut right = Zeroizing::new([0u8; 32]);
+    left.copy_from_slice(&i[..32]);
+    right.copy_from_slice(&i[32..]);
+    zeroize::Zeroize::zeroize(i.as_mut_slice());
+    Ok((left, right))
+}
+
+/// 解析 BIP-32 路径，硬化序号可用 ' 或 h 标记
+fn parse_derivation_path(path: &str) -> Result<Vec<u32>> {
+    let mut parts = path.trim().split('/');
+    if parts.next() != Some("m") {
+        anyhow::bail!("derivation path must start with 'm': {}", path);
+    }
+
+    parts
+        .map(|part| {
+            let (num, hardened) = match part.strip_suffix(['\'', 'h', 'H']) {
+                Some(num) => (num, true),
+                None => (part, false),
+            };
+            let index: u32 = num.parse()
+                .map_err(|_| anyhow::anyhow!("invalid path component '{}' in {}", part, path))?;
+            if index >= BIP32_HARDENED {
+                anyhow::bail!("path index out of range: {}", part);
+            }
+            Ok(if hardened { index + BIP32_HARDENED } else { index })
+        })
+        .collect()
+}
+
+/// 从公钥派生地址
+/// secp256k1 接受 33 字节（压缩）或 65 字节（未压缩）公钥，BLS 要求 48 字节公钥
+pub fn address_from_public_key(key_type: KeyType, public_key: &[u8]) -> Result<Address> {
+    match key_type {
+        KeyType::Secp256k1 => {
+            let pk = secp256k1::PublicKey::from_slice(public_key)
+                .map_err(|e| anyhow::anyhow!("invalid secp256k1 public key: {}", e))?;
+            Address::new_secp256k1(&pk.serialize_uncompressed())
+        }
+        KeyType::BLS => {
+            if public_key.len() != 48 {
+                anyhow::bail!("invalid BLS public key length: {} (expected 48)", public_key.len());
+            }
+            Address::new_bls(public_key)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BIP-32 测试向量 1 的种子
+    const BIP32_TV1_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn bip32_test_vector_1_master_key() {
+        let seed = hex::decode(BIP32_TV1_SEED).unwrap();
+        let key = PrivateKey::from_bip39_seed(&seed).unwrap();
+        assert_eq!(
+            hex::encode(&*key.private_key),
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+        );
+    }
+
+    #[test]
+    fn bip32_test_vector_1_child_key() {
+        let seed = hex::decode(BIP32_TV1_SEED).unwrap();
+        let key = PrivateKey::derive_child(&seed, "m/0'/1/2'/2/1000000000").unwrap();
+        assert_eq!(
+            hex::encode(&*key.private_key),
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8"
+        );
+    }
+
+    #[test]
+    fn derive_child_root_path_is_master_key() {
+        let seed = hex::decode(BIP32_TV1_SEED).unwrap();
+        let root = PrivateKey::derive_child(&seed, "m").unwrap();
+        assert_eq!(*root.private_key, *PrivateKey::from_bip39_seed(&seed).unwrap().private_key);
+    }
+}
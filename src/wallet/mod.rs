@@ -1,5 +1,9 @@
 mod key;
+mod scheme;
+#[cfg(feature = "native")]
 mod signer;
 
-pub use key::{KeyType, PrivateKey};
+pub use key::{KeyType, PrivateKey, address_from_public_key};
+pub use scheme::{sign_message_with_key, sign_with_private_key, verify_signature};
+#[cfg(feature = "native")]
 pub use signer::Wallet;
@@ -0,0 +1,124 @@
+//! secp256k1 与 BLS 签名算法：只处理原始私钥字节，不依赖密钥库，WASM 构建也可使用
+
+use super::PrivateKey;
+use crate::chain::{cbor, Address, Message, Signature, SignedMessage};
+use anyhow::Result;
+use blake2b_simd::Params;
+use secp256k1::{Message as SecpMsg, Secp256k1, SecretKey};
+
+// Filecoin BLS 域分离标签，用于 BLS 签名
+// 此标签确保签名具有域分离性，不能跨协议重用
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// 按密钥类型（"secp256k1" / "bls"）用原始私钥签名任意字节（与 Lotus WalletSign 语义一致）
+/// secp256k1 对 blake2b-256(data) 签名，BLS 直接对 data 签名
+pub fn sign_with_private_key(key_type: &str, private_key: &[u8], data: &[u8]) -> Result<Signature> {
+    match key_type {
+        "secp256k1" => sign_secp256k1(private_key, data),
+        "bls" => sign_bls(private_key, data),
+        _ => Err(anyhow::anyhow!("unsupported key type")),
+    }
+}
+
+/// 用原始私钥签名消息（对消息 CID 签名）；私钥必须对应消息的 From 地址
+pub fn sign_message_with_key(msg: Message, key: &PrivateKey) -> Result<SignedMessage> {
+    let address = key.address()?;
+    if address != msg.from {
+        anyhow::bail!("private key is for {}, but the message is from {}", address, msg.from);
+    }
+
+    let cid = cbor::compute_cid_bytes(&cbor::serialize_message(&msg)?);
+    let signature = sign_with_private_key(key.key_type.as_str(), &key.private_key, &cid)?;
+    Ok(SignedMessage { message: msg, signature })
+}
+
+/// 使用 secp256k1 ECDSA 签名（带恢复 ID）
+/// Filecoin 使用 CID 字节的 blake2b-256 哈希作为消息摘要
+fn sign_secp256k1(key: &[u8], data: &[u8]) -> Result<Signature> {
+    let secp = Secp256k1::new();
+    let secret = SecretKey::from_slice(key)?;
+
+    let hash = blake2b_hash(data, 32);
+    let msg = SecpMsg::from_digest_slice(&hash)?;
+    let sig = secp.sign_ecdsa_recoverable(&msg, &secret);
+    let (rec_id, sig_bytes) = sig.serialize_compact();
+
+    let mut data = sig_bytes.to_vec();
+    data.push(rec_id.to_i32() as u8);
+
+    Ok(Signature { sig_type: 1, data })
+}
+
+/// 使用 BLS12-381 签名方案签名
+/// 注意：Filecoin 使用小端存储 BLS 密钥，blst 库使用大端
+fn sign_bls(key: &[u8], data: &[u8]) -> Result<Signature> {
+    use blst::min_pk::{SecretKey as BlsSecretKey};
+
+    if key.len() != 32 {
+        return Err(anyhow::anyhow!("invalid BLS private key length"));
+    }
+
+    // Filecoin uses little-endian, blst uses big-endian, so reverse bytes
+    let mut key_reversed = zeroize::Zeroizing::new([0u8; 32]);
+    for i in 0..32 {
+        key_reversed[i] = key[31 - i];
+    }
+
+    let sk = BlsSecretKey::from_bytes(&*key_reversed)
+        .map_err(|e| anyhow::anyhow!("invalid BLS key: {:?}", e))?;
+
+    let sig = sk.sign(data, BLS_DST, &[]);
+    let sig_bytes = sig.to_bytes();
+
+    Ok(Signature { sig_type: 2, data: sig_bytes.to_vec() })
+}
+
+/// 验证 sign_raw 生成的签名
+/// secp256k1：从签名恢复公钥并比对派生地址；BLS：f3 地址载荷即为公钥
+pub fn verify_signature(addr: &Address, data: &[u8], sig: &Signature) -> Result<bool> {
+    use crate::chain::Protocol;
+
+    match (sig.sig_type, &addr.protocol) {
+        (1, Protocol::Secp256k1) => {
+            use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+
+            if sig.data.len() != 65 {
+                return Ok(false);
+            }
+            let rec_id = RecoveryId::from_i32(sig.data[64] as i32)?;
+            let rec_sig = RecoverableSignature::from_compact(&sig.data[..64], rec_id)?;
+            let hash = blake2b_hash(data, 32);
+            let msg = SecpMsg::from_digest_slice(&hash)?;
+
+            let secp = Secp256k1::new();
+            let pubkey = match secp.recover_ecdsa(&msg, &rec_sig) {
+                Ok(pk) => pk,
+                Err(_) => return Ok(false),
+            };
+            let recovered = Address::new_secp256k1(&pubkey.serialize_uncompressed())?;
+            Ok(recovered.payload == addr.payload)
+        }
+        (2, Protocol::BLS) => {
+            use blst::min_pk::{PublicKey as BlsPublicKey, Signature as BlsSignature};
+            use blst::BLST_ERROR;
+
+            let pk = BlsPublicKey::from_bytes(&addr.payload)
+                .map_err(|e| anyhow::anyhow!("invalid BLS public key: {:?}", e))?;
+            let bls_sig = match BlsSignature::from_bytes(&sig.data) {
+                Ok(s) => s,
+                Err(_) => return Ok(false),
+            };
+            Ok(bls_sig.verify(true, data, BLS_DST, &[], &pk, true) == BLST_ERROR::BLST_SUCCESS)
+        }
+        _ => anyhow::bail!("signature type {} does not match address protocol {:?}", sig.sig_type, addr.protocol),
+    }
+}
+
+/// 计算指定长度的 blake2b 哈希
+fn blake2b_hash(data: &[u8], size: usize) -> Vec<u8> {
+    Params::new()
+        .hash_length(size)
+        .hash(data)
+        .as_bytes()
+        .to_vec()
+}
@@ -1,22 +1,22 @@
 //! Filecoin 交易签名模块
 //!
-//! 支持 Filecoin 使用的 secp256k1 和 BLS 两种签名方案。
+//! 从密钥库解密私钥后签名；签名算法本身见 `scheme`
 
-use crate::chain::{cbor, Message, Signature};
+use super::scheme;
+use crate::chain::{cbor, rlp, Address, Message, Signature};
 use crate::crypto;
+use crate::metrics;
 use crate::db::Store;
 use anyhow::Result;
-use blake2b_simd::Params;
 use secp256k1::{Message as SecpMsg, Secp256k1, SecretKey};
-
-// Filecoin BLS 域分离标签，用于 BLS 签名
-// 此标签确保签名具有域分离性，不能跨协议重用
-const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+use std::collections::HashMap;
+use std::time::Instant;
+use zeroize::Zeroizing;
 
 /// 钱包结构体，管理私钥并签名 Filecoin 消息
 pub struct Wallet<'a> {
     store: &'a Store,       // 数据库存储（加密的密钥）
-    enc_key: [u8; 32],      // 从密码派生的加密密钥
+    enc_key: Zeroizing<[u8; 32]>, // 从密码派生的加密密钥，释放时清零
 }
 
 impl<'a> Wallet<'a> {
@@ -28,66 +28,127 @@ impl<'a> Wallet<'a> {
         }
     }
 
+    /// 使用预先派生的加密密钥创建钱包实例
+    pub fn with_enc_key(store: &'a Store, enc_key: Zeroizing<[u8; 32]>) -> Self {
+        Self { store, enc_key }
+    }
+
     /// 使用 'from' 地址关联的私钥签名 Filecoin 消息
     /// 返回带有相应类型的签名（1=secp256k1, 2=BLS）
     pub fn sign(&self, msg: &Message, from: &str) -> Result<Signature> {
-        let key = self.store.get_key(from)?
-            .ok_or_else(|| anyhow::anyhow!("key not found: {}", from))?;
-
-        let private_key = crypto::decrypt(&key.encrypted_key, &self.enc_key)?;
         let cid_bytes = self.message_cid_bytes(msg)?;
-
-        match key.key_type.as_str() {
-            "secp256k1" => self.sign_secp256k1(&private_key, &cid_bytes),
-            "bls" => self.sign_bls(&private_key, &cid_bytes),
-            _ => Err(anyhow::anyhow!("unsupported key type")),
-        }
+        self.sign_raw(&cid_bytes, from)
     }
 
-    #[allow(dead_code)]
-    pub fn has_key(&self, address: &str) -> Result<bool> {
-        self.store.has_key(address)
+    /// 使用 'from' 地址关联的私钥签名任意字节（与 Lotus WalletSign 语义一致）
+    /// secp256k1 对 blake2b-256(data) 签名，BLS 直接对 data 签名
+    pub fn sign_raw(&self, data: &[u8], from: &str) -> Result<Signature> {
+        let (key_type, private_key) = self.load_private_key(from)?;
+        self.sign_with_key(&key_type, &private_key, data)
     }
 
-    /// 使用 secp256k1 ECDSA 签名（带恢复 ID）
-    /// Filecoin 使用 CID 字节的 blake2b-256 哈希作为消息摘要
-    fn sign_secp256k1(&self, key: &[u8], data: &[u8]) -> Result<Signature> {
-        let secp = Secp256k1::new();
-        let secret = SecretKey::from_slice(key)?;
+    /// 批量签名：按发送地址分组，每个地址只解密一次私钥，签完该地址的全部消息后立即释放（清零）私钥
+    /// 返回结果与输入一一对应，部分失败不影响其他消息
+    pub fn batch_sign(&self, messages: &[(&Message, &str)]) -> Vec<Result<Signature>> {
+        let mut results: Vec<Option<Result<Signature>>> = messages.iter().map(|_| None).collect();
+
+        // 按解析后的地址分组，f/t 前缀不同的同一地址也只解密一次；保留首次出现的字符串用于查找密钥
+        let mut groups: HashMap<Address, (&str, Vec<usize>)> = HashMap::new();
+        for (i, (_, from)) in messages.iter().enumerate() {
+            match Address::from_string(from) {
+                Ok(addr) => groups.entry(addr).or_insert_with(|| (*from, Vec::new())).1.push(i),
+                Err(e) => results[i] = Some(Err(e)),
+            }
+        }
 
-        let hash = blake2b_hash(data, 32);
-        let msg = SecpMsg::from_digest_slice(&hash)?;
-        let sig = secp.sign_ecdsa_recoverable(&msg, &secret);
-        let (rec_id, sig_bytes) = sig.serialize_compact();
+        for (from, indices) in groups.into_values() {
+            match self.load_private_key(from) {
+                Ok((key_type, private_key)) => {
+                    for &i in &indices {
+                        let signed = self.message_cid_bytes(messages[i].0)
+                            .and_then(|cid| self.sign_with_key(&key_type, &private_key, &cid));
+                        results[i] = Some(signed);
+                    }
+                }
+                Err(e) => {
+                    for &i in &indices {
+                        results[i] = Some(Err(anyhow::anyhow!("{}", e)));
+                    }
+                }
+            }
+        }
 
-        let mut data = sig_bytes.to_vec();
-        data.push(rec_id.to_i32() as u8);
+        results.into_iter()
+            .map(|r| r.unwrap_or_else(|| Err(anyhow::anyhow!("message was not signed"))))
+            .collect()
+    }
 
-        Ok(Signature { sig_type: 1, data })
+    /// 读取并解密 'from' 地址的私钥，返回 (密钥类型, 私钥字节)；私钥字节释放时自动清零
+    fn load_private_key(&self, from: &str) -> Result<(String, Zeroizing<Vec<u8>>)> {
+        let key = self.store.get_key(from)?.ok_or_else(|| match Address::from_string(from) {
+            Ok(address) => anyhow::anyhow!("key not found: {} (fingerprint {})", from, address.fingerprint()),
+            Err(_) => anyhow::anyhow!("key not found: {}", from),
+        })?;
+
+        if key.is_watch_only {
+            anyhow::bail!("watch-only address cannot sign");
+        }
+        if key.is_expired() {
+            let expires_at = key.expires_at.map(|t| t.to_rfc3339()).unwrap_or_default();
+            anyhow::bail!("key expired: {} (expired at {})", from, expires_at);
+        }
+
+        let private_key = crypto::decrypt(&key.encrypted_key, &self.enc_key, key.cipher)?;
+        Ok((key.key_type, private_key))
     }
 
-    /// 使用 BLS12-381 签名方案签名
-    /// 注意：Filecoin 使用小端存储 BLS 密钥，blst 库使用大端
-    fn sign_bls(&self, key: &[u8], data: &[u8]) -> Result<Signature> {
-        use blst::min_pk::{SecretKey as BlsSecretKey};
+    fn sign_with_key(&self, key_type: &str, private_key: &[u8], data: &[u8]) -> Result<Signature> {
+        let started = Instant::now();
+        let sig = scheme::sign_with_private_key(key_type, private_key, data)?;
+        metrics::record_sign(key_type, started);
+        Ok(sig)
+    }
 
-        if key.len() != 32 {
-            return Err(anyhow::anyhow!("invalid BLS private key length"));
+    /// 以以太坊方式签名 legacy 交易（EIP-155）：对 keccak256(rlp_tx) 做可恢复 ECDSA 签名
+    /// 返回 r || s || recovery_id；EIP-155 的 v = chain_id * 2 + 35 + recovery_id
+    /// 在 chain_id 较大时（如 Filecoin 主网 314）超出单字节，由调用方写入交易
+    pub fn sign_eth(&self, rlp_tx: &[u8], chain_id: u64, from: &str) -> Result<[u8; 65]> {
+        use sha3::{Digest, Keccak256};
+
+        // EIP-155 待签交易的第 7 项必须是 chain_id，防止签出其他链可重放的交易；最后两项必须为空
+        let fields = rlp::decode_list(rlp_tx)?;
+        if fields.len() == 9 {
+            if rlp::decode_uint(&fields[6])? != chain_id {
+                anyhow::bail!("transaction chain id {} does not match --chain-id {}", rlp::decode_uint(&fields[6])?, chain_id);
+            }
+            if !fields[7].is_empty() || !fields[8].is_empty() {
+                anyhow::bail!("EIP-155 signing payload must end with [chainId, 0, 0]; the transaction appears to be signed already");
+            }
         }
 
-        // Filecoin uses little-endian, blst uses big-endian, so reverse bytes
-        let mut key_reversed = [0u8; 32];
-        for i in 0..32 {
-            key_reversed[i] = key[31 - i];
+        let (key_type, private_key) = self.load_private_key(from)?;
+        if key_type != "secp256k1" {
+            anyhow::bail!("Ethereum signing requires a secp256k1 key, {} is {}", from, key_type);
         }
+        let secret = SecretKey::from_slice(&private_key);
+        drop(private_key);
 
-        let sk = BlsSecretKey::from_bytes(&key_reversed)
-            .map_err(|e| anyhow::anyhow!("invalid BLS key: {:?}", e))?;
+        let started = Instant::now();
+        let digest = Keccak256::digest(rlp_tx);
+        let msg = SecpMsg::from_digest_slice(&digest)?;
+        let sig = Secp256k1::new().sign_ecdsa_recoverable(&msg, &secret?);
+        let (rec_id, sig_bytes) = sig.serialize_compact();
 
-        let sig = sk.sign(data, BLS_DST, &[]);
-        let sig_bytes = sig.to_bytes();
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&sig_bytes);
+        out[64] = rec_id.to_i32() as u8;
+        metrics::record_sign(&key_type, started);
+        Ok(out)
+    }
 
-        Ok(Signature { sig_type: 2, data: sig_bytes.to_vec() })
+    #[allow(dead_code)]
+    pub fn has_key(&self, address: &str) -> Result<bool> {
+        self.store.has_key(address)
     }
 
     /// 计算消息的 CID 字节（用于签名）
@@ -97,12 +158,3 @@ impl<'a> Wallet<'a> {
         Ok(cbor::compute_cid_bytes(&cbor_data))
     }
 }
-
-/// 计算指定长度的 blake2b 哈希
-fn blake2b_hash(data: &[u8], size: usize) -> Vec<u8> {
-    Params::new()
-        .hash_length(size)
-        .hash(data)
-        .as_bytes()
-        .to_vec()
-}
@@ -0,0 +1,64 @@
+//! 浏览器 / Node.js 绑定：在客户端生成密钥、签名消息并计算 CID，不需要密钥库与节点
+//!
+//! 私钥以 hex 传入传出，由调用方负责保管；错误以 JS 异常抛出
+
+use crate::chain::{cbor, Message, SignedMessage};
+use crate::wallet::{self, KeyType, PrivateKey};
+use anyhow::Result;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeneratedKey {
+    key_type: &'static str,
+    address: String,
+    private_key: String,
+    public_key: String,
+}
+
+/// 生成新密钥，返回 `{ keyType, address, privateKey, publicKey }`（hex）
+#[wasm_bindgen]
+pub fn generate_key(key_type: &str) -> Result<JsValue, JsError> {
+    let key = PrivateKey::generate(KeyType::from_str(key_type).map_err(js_error)?).map_err(js_error)?;
+    let generated = GeneratedKey {
+        key_type: key.key_type.as_str(),
+        address: key.address().map_err(js_error)?.to_string(),
+        private_key: hex::encode(&key.private_key),
+        public_key: hex::encode(&key.public_key),
+    };
+    Ok(serde_wasm_bindgen::to_value(&generated)?)
+}
+
+/// 签名 Lotus JSON 格式的消息，返回 SignedMessage 对象；私钥必须对应消息的 From 地址
+#[wasm_bindgen]
+pub fn sign_message(message_json: &str, private_key_hex: &str, key_type: &str) -> Result<JsValue, JsError> {
+    let signed = sign(message_json, private_key_hex, key_type).map_err(js_error)?;
+    Ok(signed.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+/// 计算 Lotus JSON 格式消息的 CID（multibase base32）
+#[wasm_bindgen]
+pub fn compute_cid(message_json: &str) -> Result<String, JsError> {
+    let msg: Message = serde_json::from_str(message_json)?;
+    Ok(cbor::compute_cid(&cbor::serialize_message(&msg).map_err(js_error)?))
+}
+
+/// 从 hex 公钥派生地址：secp256k1 为 33 或 65 字节，BLS 为 48 字节
+#[wasm_bindgen]
+pub fn address_from_public_key(pubkey_hex: &str, key_type: &str) -> Result<String, JsError> {
+    let key_type = KeyType::from_str(key_type).map_err(js_error)?;
+    let address = wallet::address_from_public_key(key_type, &hex::decode(pubkey_hex)?).map_err(js_error)?;
+    Ok(address.to_string())
+}
+
+fn sign(message_json: &str, private_key_hex: &str, key_type: &str) -> Result<SignedMessage> {
+    let msg: Message = serde_json::from_str(message_json)?;
+    let key = PrivateKey::from_bytes(KeyType::from_str(key_type)?, &hex::decode(private_key_hex)?)?;
+    wallet::sign_message_with_key(msg, &key)
+}
+
+/// anyhow::Error 未实现 std::error::Error，无法直接转换为 JsError
+fn js_error(e: anyhow::Error) -> JsError {
+    JsError::new(&format!("{:#}", e))
+}
@@ -0,0 +1,57 @@
+/* C FFI 测试：make test-ffi */
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "lotus_sign.h"
+
+static void test_sign(const char *key_type, char addr_prefix) {
+    char address[128], privkey[65], msg[512], signed_msg[1024];
+    char *error = NULL;
+
+    assert(lotus_sign_generate_key(key_type, address, sizeof address, privkey, sizeof privkey, &error) == 0);
+    assert(error == NULL);
+    assert(address[1] == addr_prefix);
+    assert(strlen(privkey) == 64);
+
+    snprintf(msg, sizeof msg,
+             "{\"Version\":0,\"To\":\"f01234\",\"From\":\"%s\",\"Nonce\":1,"
+             "\"Value\":\"1000\",\"GasLimit\":1000000,\"GasFeeCap\":\"100000\","
+             "\"GasPremium\":\"1000\",\"Method\":0,\"Params\":\"\"}",
+             address);
+    assert(lotus_sign_message(msg, privkey, key_type, signed_msg, sizeof signed_msg, &error) == 0);
+    assert(strstr(signed_msg, "\"Signature\"") != NULL);
+    assert(strstr(signed_msg, address) != NULL);
+
+    /* 输出缓冲区过小 */
+    assert(lotus_sign_message(msg, privkey, key_type, signed_msg, 16, &error) == -1);
+    assert(error != NULL && strstr(error, "too small") != NULL);
+    lotus_sign_free_error(error);
+    error = NULL;
+
+    /* 私钥与 From 不匹配 */
+    char other_address[128], other_privkey[65];
+    assert(lotus_sign_generate_key(key_type, other_address, sizeof other_address, other_privkey,
+                                   sizeof other_privkey, NULL) == 0);
+    assert(lotus_sign_message(msg, other_privkey, key_type, signed_msg, sizeof signed_msg, &error) == -1);
+    assert(strstr(error, "message is from") != NULL);
+    lotus_sign_free_error(error);
+}
+
+int main(void) {
+    char address[128], privkey[65];
+    char *error = NULL;
+
+    test_sign("secp256k1", '1');
+    test_sign("bls", '3');
+
+    assert(lotus_sign_generate_key("ed25519", address, sizeof address, privkey, sizeof privkey, &error) == -1);
+    assert(strstr(error, "unknown key type") != NULL);
+    lotus_sign_free_error(error);
+
+    assert(lotus_sign_generate_key(NULL, address, sizeof address, privkey, sizeof privkey, NULL) == -1);
+    lotus_sign_free_error(NULL);
+
+    printf("ffi tests passed\n");
+    return 0;
+}
@@ -0,0 +1,244 @@
+//! 基于 MockLotusServer 的签名与广播集成测试，无需真实 Lotus 节点
+
+#![cfg(feature = "native")]
+
+use lotus_sign::chain::{
+    ChangeOwnerParams, MarketWithdrawParams, SignedMessage, WithdrawBalanceParams, METHOD_CHANGE_OWNER,
+    METHOD_MARKET_WITHDRAW, METHOD_WITHDRAW_BALANCE, STORAGE_MARKET_ACTOR,
+};
+use lotus_sign::config::{Config, WalletConfig};
+use lotus_sign::crypto::{self, CipherAlgorithm};
+use lotus_sign::db::{Store, WalletKey};
+use lotus_sign::rpc::{Cid, MockLotusServer};
+use lotus_sign::service::Executor;
+use lotus_sign::wallet::{KeyType, PrivateKey};
+use serde_json::json;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const PASSWORD: &str = "integration-test";
+const RECIPIENT: &str = "f1abjxfbp274xpdqcpuaykwkfb43omjotacm2p3za";
+
+struct Harness {
+    mock: MockLotusServer,
+    cfg: Config,
+    store: Store,
+    _dir: TempDir,
+}
+
+impl Harness {
+    async fn new() -> Self {
+        let (mock, url) = MockLotusServer::spawn().await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut cfg = Config::default();
+        cfg.lotus.host = url;
+        cfg.database.path = dir.path().join("lotus_sign.db").to_string_lossy().into_owned();
+        cfg.wallet = Some(WalletConfig { password: Some(PASSWORD.to_string()), master_seed_hex: None, use_keyring: false });
+        cfg.no_retry = true;
+
+        let store = Store::open(&cfg.database.path, None).unwrap();
+        Self { mock, cfg, store, _dir: dir }
+    }
+
+    /// 生成并存储一个密钥，返回其地址
+    fn add_key(&self, key_type: KeyType) -> String {
+        let key = PrivateKey::generate(key_type).unwrap();
+        let address = key.address().unwrap().to_string();
+        let encrypted = crypto::encrypt(&key.private_key, &crypto::derive_key(PASSWORD), CipherAlgorithm::Aes256Gcm).unwrap();
+        self.store.insert_key(&WalletKey::new(address.clone(), key_type.as_str().to_string(), encrypted)).unwrap();
+        address
+    }
+
+    async fn executor(&self) -> Executor<'_> {
+        Executor::new_async(&self.cfg, &self.store).await.unwrap()
+    }
+
+    /// MpoolPush 收到的已签名消息
+    fn pushed(&self) -> Vec<SignedMessage> {
+        self.mock.requests_for("MpoolPush")
+            .into_iter()
+            .map(|r| serde_json::from_value(r.params[0].clone()).unwrap())
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn secp256k1_transfer_is_signed_and_pushed() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+
+    let cid = h.executor().await.transfer(&from, RECIPIENT, "1000").await.unwrap().unwrap();
+    assert!(cid.root.starts_with("bafy2bz"));
+
+    let pushed = h.pushed();
+    assert_eq!(pushed.len(), 1);
+    let msg = &pushed[0];
+    assert_eq!(msg.message.from.to_string(), from);
+    assert_eq!(msg.message.to.to_string(), RECIPIENT);
+    assert_eq!(msg.message.value.to_string(), "1000");
+    assert_eq!(msg.message.gas_limit, 1_000_000);
+    assert!(msg.verify().unwrap());
+}
+
+#[tokio::test]
+async fn bls_transfer_is_signed_and_pushed() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::BLS);
+
+    h.executor().await.transfer(&from, RECIPIENT, "1000").await.unwrap();
+
+    let pushed = h.pushed();
+    assert_eq!(pushed.len(), 1);
+    assert_eq!(pushed[0].signature.sig_type, 2);
+    assert!(pushed[0].verify().unwrap());
+}
+
+#[tokio::test]
+async fn transfer_uses_nonce_from_node() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+    h.mock.set_response("MpoolGetNonce", json!(42));
+
+    h.executor().await.transfer(&from, RECIPIENT, "1").await.unwrap();
+
+    assert_eq!(h.mock.requests_for("MpoolGetNonce")[0].params[0], json!(from));
+    assert_eq!(h.pushed()[0].message.nonce, 42);
+}
+
+#[tokio::test]
+async fn push_error_is_returned() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+    h.mock.set_error("MpoolPush", 1, "insufficient funds");
+
+    let err = h.executor().await.transfer(&from, RECIPIENT, "1").await.unwrap_err();
+    assert!(format!("{:#}", err).contains("insufficient funds"));
+    assert_eq!(h.mock.requests_for("MpoolPush").len(), 1);
+}
+
+#[tokio::test]
+async fn dry_run_does_not_push() {
+    let mut h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+    h.cfg.dry_run = true;
+
+    let cid = h.executor().await.transfer(&from, RECIPIENT, "1").await.unwrap();
+    assert!(cid.is_none());
+    assert!(h.mock.requests_for("MpoolPush").is_empty());
+}
+
+#[tokio::test]
+async fn miner_withdraw_pushes_tuple_encoded_params() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+
+    h.executor().await.miner_withdraw("f01000", &from, "5000").await.unwrap();
+
+    let pushed = h.pushed();
+    assert_eq!(pushed[0].message.to.to_string(), "f01000");
+    assert_eq!(pushed[0].message.method, METHOD_WITHDRAW_BALANCE);
+    let params = WithdrawBalanceParams::from_cbor(&pushed[0].message.params).unwrap();
+    assert_eq!(params.amount.to_string(), "5000");
+    assert!(pushed[0].verify().unwrap());
+}
+
+#[tokio::test]
+async fn change_owner_pushes_transparent_address_params() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+
+    h.executor().await.change_owner("f01000", RECIPIENT, &from).await.unwrap();
+
+    let pushed = h.pushed();
+    assert_eq!(pushed[0].message.to.to_string(), "f01000");
+    assert_eq!(pushed[0].message.method, METHOD_CHANGE_OWNER);
+    let params = ChangeOwnerParams::from_cbor(&pushed[0].message.params).unwrap();
+    assert_eq!(params.new_owner.to_string(), RECIPIENT);
+    assert!(pushed[0].verify().unwrap());
+}
+
+#[tokio::test]
+async fn market_withdraw_pushes_tuple_encoded_params() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+
+    h.executor().await.market_withdraw(RECIPIENT, &from, "5000").await.unwrap();
+
+    let pushed = h.pushed();
+    assert_eq!(pushed[0].message.to.to_string(), STORAGE_MARKET_ACTOR);
+    assert_eq!(pushed[0].message.method, METHOD_MARKET_WITHDRAW);
+    let params = MarketWithdrawParams::from_cbor(&pushed[0].message.params).unwrap();
+    assert_eq!(params.provider_or_client.to_string(), RECIPIENT);
+    assert_eq!(params.amount.to_string(), "5000");
+    assert!(pushed[0].verify().unwrap());
+}
+
+#[tokio::test]
+async fn dry_run_previews_every_withdrawal() {
+    let mut h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+    h.cfg.dry_run = true;
+
+    let results = h.executor().await
+        .withdraw_all_miners(vec![("f01000", "5000"), ("f01001", "7000")], &from)
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|(_, _, result)| matches!(result, Ok(None))));
+    assert_eq!(h.mock.requests_for("GasEstimateMessageGas").len(), 2);
+    assert!(h.mock.requests_for("MpoolPush").is_empty());
+}
+
+#[tokio::test]
+async fn withdraw_all_miners_reports_each_failure() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+    h.mock.set_error("MpoolPush", 1, "insufficient funds");
+
+    let results = h.executor().await
+        .withdraw_all_miners(vec![("f01000", "5000"), ("f01001", "7000")], &from)
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].0, "f01001");
+    assert!(results.iter().all(|(_, _, result)| result.is_err()));
+    // 失败的消息不占用 nonce
+    let pushed = h.mock.requests_for("MpoolPush");
+    assert_eq!(pushed[0].params[0]["Message"]["Nonce"], pushed[1].params[0]["Message"]["Nonce"]);
+}
+
+#[tokio::test]
+async fn transfer_with_options_parses_fil_amounts() {
+    let h = Harness::new().await;
+    let from = h.add_key(KeyType::Secp256k1);
+    let executor = h.executor().await;
+
+    executor.transfer_with_options(&from, RECIPIENT, "0.1", "0", "0", 0, 0, None, None).await.unwrap();
+    assert_eq!(h.pushed()[0].message.value.to_string(), "100000000000000000");
+
+    let err = executor.transfer_with_options(&from, RECIPIENT, "abc", "0", "0", 0, 0, None, None).await.unwrap_err();
+    assert!(err.to_string().contains("invalid amount"));
+    assert_eq!(h.pushed().len(), 1);
+}
+
+#[tokio::test]
+async fn wait_for_inclusion_ignores_chain_head_errors() {
+    let h = Harness::new().await;
+    h.mock.set_response("MpoolPending", json!([]));
+    h.mock.set_response("StateSearchMsg", json!(null));
+    h.mock.set_error("ChainHead", 1, "head unavailable");
+    let cid = Cid { root: "bafy2bzacea3wsdh6y3a36tb3skempjoxqpuyompjbmfeyf34fi3uy6uue42v4".to_string() };
+
+    let mut polls = Vec::new();
+    let err = h.executor().await.api
+        .wait_for_inclusion(&cid, Duration::from_millis(10), Duration::from_millis(50), |epoch| polls.push(epoch))
+        .await
+        .unwrap_err();
+    // 链头只用于进度显示，查询失败不中断等待
+    assert!(err.to_string().contains("timed out"));
+    assert!(!polls.is_empty());
+    assert!(polls.iter().all(Option::is_none));
+}
@@ -0,0 +1,45 @@
+// 覆盖 wasm 绑定的四个导出；先运行 `make wasm-node` 生成 pkg-node/
+import assert from "node:assert/strict";
+import { createRequire } from "node:module";
+
+const require = createRequire(import.meta.url);
+const lotus = require("../../pkg-node/lotus_sign.js");
+
+for (const keyType of ["secp256k1", "bls"]) {
+  const key = lotus.generate_key(keyType);
+  assert.equal(key.keyType, keyType);
+  assert.match(key.address, keyType === "bls" ? /^[ft]3/ : /^[ft]1/);
+  assert.equal(lotus.address_from_public_key(key.publicKey, keyType), key.address);
+
+  const message = {
+    Version: 0,
+    To: "f01234",
+    From: key.address,
+    Nonce: 1,
+    Value: "1000000000000000000",
+    GasLimit: 1000000,
+    GasFeeCap: "100000",
+    GasPremium: "1000",
+    Method: 0,
+    Params: "",
+  };
+  const messageJson = JSON.stringify(message);
+
+  const cid = lotus.compute_cid(messageJson);
+  assert.match(cid, /^bafy2bza/);
+  assert.equal(lotus.compute_cid(messageJson), cid);
+
+  const signed = lotus.sign_message(messageJson, key.privateKey, keyType);
+  assert.equal(signed.Message.From, key.address);
+  assert.equal(signed.Signature.Type, keyType === "bls" ? 2 : 1);
+  assert.ok(signed.Signature.Data.length > 0);
+
+  // 私钥与 From 不匹配时应抛出异常
+  const other = lotus.generate_key(keyType);
+  assert.throws(() => lotus.sign_message(messageJson, other.privateKey, keyType), /message is from/);
+}
+
+assert.throws(() => lotus.generate_key("ed25519"));
+assert.throws(() => lotus.compute_cid("not json"));
+
+console.log("wasm tests passed");